@@ -1,6 +1,11 @@
-use std::str::FromStr;
+use std::{str::FromStr, sync::Arc};
 
-use teloxide::{prelude::*, types::ChatPermissions, utils::command::BotCommand};
+use teloxide::{
+    dispatching::{CommandGuard, Permission, RequiresPermission},
+    prelude::*,
+    types::ChatPermissions,
+    utils::command::BotCommand,
+};
 
 // Derive BotCommand to parse text with a command into this enumeration.
 //
@@ -33,6 +38,19 @@ enum Command {
     Help,
 }
 
+// Only administrators that can restrict members are allowed to kick, ban and
+// mute. Other users will receive a reply configured in `CommandGuard`.
+impl RequiresPermission for Command {
+    fn required_permission(&self) -> Permission {
+        match self {
+            Command::Help => Permission::Anyone,
+            Command::Kick | Command::Ban { .. } | Command::Mute { .. } => {
+                Permission::CanRestrictMembers
+            }
+        }
+    }
+}
+
 enum UnitOfTime {
     Seconds,
     Minutes,
@@ -140,5 +158,19 @@ async fn run() {
     let bot = Bot::from_env();
 
     let bot_name: String = panic!("Your bot's name here");
-    teloxide::commands_repl(bot, bot_name, action).await;
+    let guard = Arc::new(
+        CommandGuard::new().denial_text("Only administrators can restrict chat members!"),
+    );
+
+    Dispatcher::new(bot)
+        .messages_handler(move |rx: DispatcherHandlerRx<Message>| {
+            rx.guarded_commands::<Command, _>(bot_name, guard).for_each_concurrent(
+                None,
+                |(cx, command)| async move {
+                    action(cx, command).await.log_on_error().await;
+                },
+            )
+        })
+        .dispatch()
+        .await;
 }
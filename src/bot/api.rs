@@ -12,12 +12,13 @@ use crate::{
         SendInvoice, SendLocation, SendMediaGroup, SendMessage, SendPhoto, SendPoll, SendSticker,
         SendVenue, SendVideo, SendVideoNote, SendVoice, SetChatAdministratorCustomTitle,
         SetChatDescription, SetChatPermissions, SetChatPhoto, SetChatStickerSet, SetChatTitle,
-        SetGameScore, SetMyCommands, SetStickerPositionInSet, SetStickerSetThumb, SetWebhook,
-        StopMessageLiveLocation, StopPoll, UnbanChatMember, UnpinChatMessage, UploadStickerFile,
+        SetGameScore, SetMyCommands, SetPassportDataErrors, SetStickerPositionInSet,
+        SetStickerSetThumb, SetWebhook, StopMessageLiveLocation, StopPoll, UnbanChatMember,
+        UnpinChatMessage, UploadStickerFile,
     },
     types::{
         BotCommand, ChatId, ChatOrInlineMessage, ChatPermissions, InlineQueryResult, InputFile,
-        InputMedia, LabeledPrice, ParseMode, StickerType,
+        InputMedia, LabeledPrice, ParseMode, PassportElementError, StickerType,
     },
     Bot,
 };
@@ -1525,6 +1526,21 @@ impl Bot {
         SetStickerSetThumb::new(self.clone(), name, user_id)
     }
 
+    /// Informs a user that some of the Telegram Passport elements they
+    /// provided contains errors.
+    ///
+    /// [The official docs](https://core.telegram.org/bots/api#setpassportdataerrors).
+    ///
+    /// # Params
+    ///    - `user_id`: User identifier.
+    ///    - `errors`: A JSON-serialized array describing the errors.
+    pub fn set_passport_data_errors<E>(&self, user_id: i32, errors: E) -> SetPassportDataErrors
+    where
+        E: Into<Vec<PassportElementError>>,
+    {
+        SetPassportDataErrors::new(self.clone(), user_id, errors)
+    }
+
     fn with_default_parse_mode_if_specified<Builder>(
         &self,
         builder: Builder,
@@ -1,11 +1,16 @@
-use tokio::io::AsyncWrite;
+use std::path::{Path, PathBuf};
 
-#[cfg(feature = "unstable-stream")]
-use ::{bytes::Bytes, tokio::stream::Stream};
+use bytes::Bytes;
+use futures::{Stream, StreamExt};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
 
-#[cfg(feature = "unstable-stream")]
-use crate::net::download_file_stream;
-use crate::{bot::Bot, net::download_file, DownloadError};
+use crate::{
+    bot::Bot,
+    net::{download_file, download_file_stream},
+    requests::Request,
+    types::File,
+    DownloadError,
+};
 
 impl Bot {
     /// Download a file from Telegram into `destination`.
@@ -13,6 +18,8 @@ impl Bot {
     /// `path` can be obtained from [`Bot::get_file`].
     ///
     /// To download as a stream of chunks, see [`Bot::download_file_stream`].
+    /// To get a path and download a file in one call, see
+    /// [`Bot::download_file_by_id`].
     ///
     /// ## Examples
     ///
@@ -33,6 +40,7 @@ impl Bot {
     ///
     /// [`Bot::get_file`]: crate::Bot::get_file
     /// [`Bot::download_file_stream`]: crate::Bot::download_file_stream
+    /// [`Bot::download_file_by_id`]: crate::Bot::download_file_by_id
     pub async fn download_file<D>(
         &self,
         path: &str,
@@ -55,11 +63,203 @@ impl Bot {
     /// [`AsyncWrite`]: tokio::io::AsyncWrite
     /// [`tokio::fs::File`]: tokio::fs::File
     /// [`Bot::download_file`]: crate::Bot::download_file
-    #[cfg(feature = "unstable-stream")]
     pub async fn download_file_stream(
         &self,
         path: &str,
     ) -> Result<impl Stream<Item = Result<Bytes, reqwest::Error>>, reqwest::Error> {
         download_file_stream(&self.client, &self.token, path).await
     }
+
+    /// Download a file with the specified `file_id` into `destination`.
+    ///
+    /// Calls [`Bot::get_file`] and downloads the file, checking that all of
+    /// its bytes were written. See [`FileDownload`] for a maximum size and
+    /// progress reporting.
+    ///
+    /// ## Examples
+    ///
+    /// ```no_run
+    /// use teloxide::Bot;
+    ///
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// let bot = Bot::new("TOKEN");
+    /// let mut buf = Vec::new();
+    ///
+    /// let file = bot
+    ///     .download_file_by_id("*file_id*", &mut buf)
+    ///     .max_size(1024 * 1024)
+    ///     .progress(|downloaded, total| log::info!("{}/{} bytes", downloaded, total))
+    ///     .send()
+    ///     .await?;
+    /// assert_eq!(buf.len(), file.file_size as usize);
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`Bot::get_file`]: crate::Bot::get_file
+    /// [`FileDownload`]: crate::FileDownload
+    pub fn download_file_by_id<'a, F, D>(
+        &'a self,
+        file_id: F,
+        destination: &'a mut D,
+    ) -> FileDownload<'a>
+    where
+        F: Into<String>,
+        D: AsyncWrite + Unpin + Send,
+    {
+        FileDownload::new(self, file_id.into(), Destination::Writer(destination))
+    }
+
+    /// Download a file with the specified `file_id` into a file at `path`.
+    ///
+    /// The file at `path` is created (or truncated) and removed if the
+    /// download fails. Otherwise, this is the same as
+    /// [`Bot::download_file_by_id`].
+    ///
+    /// ## Examples
+    ///
+    /// ```no_run
+    /// use teloxide::Bot;
+    ///
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// let bot = Bot::new("TOKEN");
+    /// bot.download_to_path("*file_id*", "/tmp/photo.jpg").max_size(20 * 1024 * 1024).send().await?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`Bot::download_file_by_id`]: crate::Bot::download_file_by_id
+    pub fn download_to_path<F, P>(&self, file_id: F, path: P) -> FileDownload<'_>
+    where
+        F: Into<String>,
+        P: AsRef<Path>,
+    {
+        FileDownload::new(self, file_id.into(), Destination::Path(path.as_ref().to_owned()))
+    }
+}
+
+enum Destination<'a> {
+    Writer(&'a mut (dyn AsyncWrite + Unpin + Send)),
+    Path(PathBuf),
+}
+
+type Progress<'a> = Box<dyn FnMut(u64, u64) + Send + 'a>;
+
+/// A download of a file by its `file_id`.
+///
+/// Created by [`Bot::download_file_by_id`] and [`Bot::download_to_path`].
+///
+/// [`Bot::download_file_by_id`]: crate::Bot::download_file_by_id
+/// [`Bot::download_to_path`]: crate::Bot::download_to_path
+#[must_use = "downloads do nothing unless sent"]
+pub struct FileDownload<'a> {
+    bot: &'a Bot,
+    file_id: String,
+    destination: Destination<'a>,
+    max_size: Option<u32>,
+    progress: Option<Progress<'a>>,
+}
+
+impl<'a> FileDownload<'a> {
+    fn new(bot: &'a Bot, file_id: String, destination: Destination<'a>) -> Self {
+        Self { bot, file_id, destination, max_size: None, progress: None }
+    }
+
+    /// The maximum size of a file in bytes.
+    ///
+    /// Larger files are not downloaded and [`DownloadError::TooLarge`] is
+    /// returned instead.
+    ///
+    /// [`DownloadError::TooLarge`]: crate::DownloadError::TooLarge
+    pub fn max_size(mut self, bytes: u32) -> Self {
+        self.max_size = Some(bytes);
+        self
+    }
+
+    /// A callback, called with the number of downloaded bytes and the size
+    /// of a file after every downloaded chunk.
+    pub fn progress<F>(mut self, f: F) -> Self
+    where
+        F: FnMut(u64, u64) + Send + 'a,
+    {
+        self.progress = Some(Box::new(f));
+        self
+    }
+
+    /// Downloads the file.
+    ///
+    /// Returns information about the downloaded file.
+    pub async fn send(self) -> Result<File, DownloadError> {
+        let Self { bot, file_id, destination, max_size, mut progress } = self;
+
+        let file = bot.get_file(file_id).send().await?;
+        if let Some(max_size) = max_size {
+            if file.file_size > max_size {
+                return Err(DownloadError::TooLarge {
+                    size: file.file_size.into(),
+                    max_size: max_size.into(),
+                });
+            }
+        }
+
+        match destination {
+            Destination::Writer(writer) => {
+                write_file(bot, &file, writer, max_size, &mut progress).await?;
+            }
+            Destination::Path(path) => {
+                let mut writer = tokio::fs::File::create(&path).await?;
+                let res = write_file(bot, &file, &mut writer, max_size, &mut progress).await;
+                if res.is_err() {
+                    drop(writer);
+                    if let Err(error) = tokio::fs::remove_file(&path).await {
+                        log::error!("Cannot remove a partially downloaded file: {}", error);
+                    }
+                }
+                res?;
+            }
+        }
+
+        Ok(file)
+    }
+}
+
+async fn write_file<W>(
+    bot: &Bot,
+    file: &File,
+    writer: &mut W,
+    max_size: Option<u32>,
+    progress: &mut Option<Progress<'_>>,
+) -> Result<(), DownloadError>
+where
+    W: AsyncWrite + Unpin + ?Sized,
+{
+    let expected = u64::from(file.file_size);
+    let mut downloaded = 0;
+
+    let mut stream = Box::pin(bot.download_file_stream(&file.file_path).await?);
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk?;
+        downloaded += chunk.len() as u64;
+
+        // `file_size` may be absent, so the size is checked while downloading
+        // too.
+        if let Some(max_size) = max_size {
+            if downloaded > u64::from(max_size) {
+                return Err(DownloadError::TooLarge {
+                    size: downloaded,
+                    max_size: max_size.into(),
+                });
+            }
+        }
+
+        writer.write_all(&chunk).await?;
+        if let Some(progress) = progress {
+            progress(downloaded, expected);
+        }
+    }
+    writer.flush().await?;
+
+    if expected != 0 && downloaded != expected {
+        return Err(DownloadError::SizeMismatch { expected, downloaded });
+    }
+
+    Ok(())
 }
@@ -1,4 +1,7 @@
-use crate::types::ParseMode;
+use crate::{
+    requests::{upload_cache::UploadCache, UploadLimits},
+    types::ParseMode,
+};
 use reqwest::{
     header::{HeaderMap, CONNECTION},
     Client, ClientBuilder,
@@ -8,6 +11,8 @@ use std::{sync::Arc, time::Duration};
 mod api;
 mod download;
 
+pub use download::FileDownload;
+
 pub(crate) const TELOXIDE_TOKEN: &str = "TELOXIDE_TOKEN";
 pub(crate) const TELOXIDE_PROXY: &str = "TELOXIDE_PROXY";
 
@@ -21,6 +26,9 @@ pub struct Bot {
     token: Arc<str>,
     client: Client,
     parse_mode: Arc<Option<ParseMode>>,
+    upload_cache: Option<Arc<dyn UploadCache>>,
+    upload_limits: UploadLimits,
+    skip_markup_validation: bool,
 }
 
 impl Bot {
@@ -96,6 +104,9 @@ impl Bot {
             token: Into::<Arc<str>>::into(Into::<String>::into(token)),
             client,
             parse_mode: Arc::new(None),
+            upload_cache: None,
+            upload_limits: UploadLimits::new(),
+            skip_markup_validation: false,
         }
     }
 }
@@ -138,6 +149,18 @@ impl Bot {
     pub fn client(&self) -> &Client {
         &self.client
     }
+
+    pub(crate) fn upload_cache(&self) -> Option<&Arc<dyn UploadCache>> {
+        self.upload_cache.as_ref()
+    }
+
+    pub(crate) fn upload_limits(&self) -> &UploadLimits {
+        &self.upload_limits
+    }
+
+    pub(crate) fn skips_markup_validation(&self) -> bool {
+        self.skip_markup_validation
+    }
 }
 
 /// A builder of [`Bot`], supporting some extra settings.
@@ -148,6 +171,9 @@ pub struct BotBuilder {
     token: Option<String>,
     client: Option<Client>,
     parse_mode: Option<ParseMode>,
+    upload_cache: Option<Arc<dyn UploadCache>>,
+    upload_limits: UploadLimits,
+    skip_markup_validation: bool,
 }
 
 impl BotBuilder {
@@ -216,6 +242,45 @@ impl BotBuilder {
         self
     }
 
+    /// Specifies [`UploadCache`], which will be used to send `file_id`s of
+    /// already uploaded files instead of uploading them again.
+    ///
+    /// See [the module-level documentation] for details.
+    ///
+    /// [`UploadCache`]: crate::requests::upload_cache::UploadCache
+    /// [the module-level documentation]: crate::requests::upload_cache
+    #[must_use]
+    pub fn upload_cache<C>(mut self, cache: Arc<C>) -> Self
+    where
+        C: UploadCache + 'static,
+    {
+        self.upload_cache = Some(cache);
+        self
+    }
+
+    /// Specifies [`UploadLimits`] of files checked before uploading them.
+    ///
+    /// Otherwise, limits of the Telegram's Bot API server will be used.
+    ///
+    /// [`UploadLimits`]: crate::requests::UploadLimits
+    #[must_use]
+    pub fn upload_limits(mut self, limits: UploadLimits) -> Self {
+        self.upload_limits = limits;
+        self
+    }
+
+    /// Disables checking markup of [`SendMessage`] and [`EditMessageText`]
+    /// in debug builds (see [`markup::validate`]).
+    ///
+    /// [`SendMessage`]: crate::requests::SendMessage
+    /// [`EditMessageText`]: crate::requests::EditMessageText
+    /// [`markup::validate`]: crate::utils::markup::validate
+    #[must_use]
+    pub fn skip_markup_validation(mut self, val: bool) -> Self {
+        self.skip_markup_validation = val;
+        self
+    }
+
     /// Builds [`Bot`].
     ///
     /// This method will attempt to build a new client with a proxy, specified
@@ -237,6 +302,9 @@ impl BotBuilder {
             client: self.client.unwrap_or_else(crate::utils::client_from_env),
             token: self.token.unwrap_or_else(|| get_env(TELOXIDE_TOKEN)).into(),
             parse_mode: Arc::new(self.parse_mode),
+            upload_cache: self.upload_cache,
+            upload_limits: self.upload_limits,
+            skip_markup_validation: self.skip_markup_validation,
         }
     }
 }
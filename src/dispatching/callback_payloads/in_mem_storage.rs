@@ -0,0 +1,107 @@
+use super::PayloadStorage;
+use futures::future::BoxFuture;
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::Mutex;
+
+/// A payload storage based on a hash map. Stores all the payloads directly in
+/// RAM.
+///
+/// Expired payloads are removed when they're requested and periodically when
+/// new ones are saved.
+///
+/// ## Note
+/// All the payloads will be lost after you restart your bot, so buttons sent
+/// before will be considered expired.
+#[derive(Debug)]
+pub struct InMemPayloadStorage<P> {
+    entries: Mutex<Entries<P>>,
+}
+
+#[derive(Debug)]
+struct Entries<P> {
+    map: HashMap<String, (Instant, P)>,
+    /// The number of entries after the last removal of expired ones.
+    len_after_sweep: usize,
+}
+
+impl<P> InMemPayloadStorage<P> {
+    #[must_use]
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self { entries: Mutex::new(Entries { map: HashMap::new(), len_after_sweep: 0 }) })
+    }
+}
+
+impl<P> PayloadStorage<P> for InMemPayloadStorage<P>
+where
+    P: Clone + Sync,
+{
+    type Error = std::convert::Infallible;
+
+    fn save_payload(
+        self: Arc<Self>,
+        key: String,
+        payload: P,
+        ttl: Duration,
+    ) -> BoxFuture<'static, Result<(), Self::Error>>
+    where
+        P: Send + 'static,
+    {
+        Box::pin(async move {
+            let mut entries = self.entries.lock().await;
+
+            // Sweeping when the map doubles keeps saving amortized O(1).
+            if entries.map.len() >= 2 * entries.len_after_sweep.max(64) {
+                let now = Instant::now();
+                entries.map.retain(|_, (expires_at, _)| *expires_at > now);
+                entries.len_after_sweep = entries.map.len();
+            }
+
+            entries.map.insert(key, (Instant::now() + ttl, payload));
+            Ok(())
+        })
+    }
+
+    fn get_payload(
+        self: Arc<Self>,
+        key: String,
+    ) -> BoxFuture<'static, Result<Option<P>, Self::Error>>
+    where
+        P: Send + 'static,
+    {
+        Box::pin(async move {
+            let mut entries = self.entries.lock().await;
+            match entries.map.get(&key) {
+                Some((expires_at, payload)) if *expires_at > Instant::now() => {
+                    Ok(Some(payload.clone()))
+                }
+                Some(_) => {
+                    entries.map.remove(&key);
+                    Ok(None)
+                }
+                None => Ok(None),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn expiry() {
+        let storage = InMemPayloadStorage::new();
+
+        Arc::clone(&storage).save_payload("a".into(), 1, Duration::from_secs(60)).await.unwrap();
+        Arc::clone(&storage).save_payload("b".into(), 2, Duration::from_secs(0)).await.unwrap();
+
+        assert_eq!(Arc::clone(&storage).get_payload("a".into()).await, Ok(Some(1)));
+        assert_eq!(Arc::clone(&storage).get_payload("a".into()).await, Ok(Some(1)));
+        assert_eq!(Arc::clone(&storage).get_payload("b".into()).await, Ok(None));
+        assert_eq!(Arc::clone(&storage).get_payload("c".into()).await, Ok(None));
+    }
+}
@@ -0,0 +1,291 @@
+//! A server-side store of callback payloads.
+//!
+//! Callback data of an inline button is limited to 64 bytes, which isn't
+//! enough for many payloads (even with [`CallbackData`]). [`CallbackPayloads`]
+//! saves a payload into a [`PayloadStorage`] and puts only a short random key
+//! into the button. When the button is pressed, the payload is restored by
+//! [`DispatcherHandlerRxExt::payloads`] or [`CallbackPayloads::resolve`].
+//!
+//! Payloads expire after a configurable time-to-live. Pressing a button with
+//! an expired payload is answered with a configurable text.
+//!
+//! # Example
+//! ```no_run
+//! use std::sync::Arc;
+//! use teloxide::{
+//!     dispatching::callback_payloads::{CallbackPayloads, InMemPayloadStorage},
+//!     prelude::*,
+//!     types::{CallbackQuery, InlineKeyboardMarkup},
+//! };
+//!
+//! #[derive(Clone)]
+//! struct Order {
+//!     items: Vec<String>,
+//! }
+//!
+//! # async fn run(cx: UpdateWithCx<Message>) -> Result<(), Box<dyn std::error::Error>> {
+//! let payloads = Arc::new(CallbackPayloads::new(InMemPayloadStorage::new()));
+//!
+//! let order = Order { items: vec!["A very long product name".to_owned(); 10] };
+//! let button = payloads.button("Confirm", order).await?;
+//! cx.answer("Confirm the order?")
+//!     .reply_markup(InlineKeyboardMarkup::default().append_row(vec![button]))
+//!     .send()
+//!     .await?;
+//!
+//! Dispatcher::new(cx.bot.clone())
+//!     .callback_queries_handler(move |rx: DispatcherHandlerRx<CallbackQuery>| {
+//!         rx.payloads(Arc::clone(&payloads)).for_each(|(cx, order): (_, Order)| async move {
+//!             log::info!("{} items ordered", order.items.len());
+//!             cx.bot.answer_callback_query(cx.update.id).send().await.log_on_error().await;
+//!         })
+//!     })
+//!     .dispatch()
+//!     .await;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`CallbackData`]: crate::utils::callback_data::CallbackData
+//! [`DispatcherHandlerRxExt::payloads`]: crate::dispatching::DispatcherHandlerRxExt::payloads
+
+mod in_mem_storage;
+
+#[cfg(feature = "redis-storage")]
+mod redis_storage;
+
+pub use in_mem_storage::InMemPayloadStorage;
+#[cfg(feature = "redis-storage")]
+pub use redis_storage::RedisPayloadStorage;
+
+use crate::{
+    dispatching::UpdateWithCx,
+    requests::Request,
+    types::{CallbackQuery, InlineKeyboardButton},
+};
+use futures::future::BoxFuture;
+use std::{
+    collections::hash_map::RandomState,
+    fmt::{Debug, Display},
+    hash::{BuildHasher, Hasher},
+    marker::PhantomData,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use thiserror::Error;
+
+/// A storage of callback payloads.
+///
+/// You can implement this trait for a structure that communicates with a DB
+/// and be sure that after you restart your bot, buttons sent before will still
+/// work.
+///
+/// For a storage based on a simple hash map, see [`InMemPayloadStorage`].
+///
+/// [`InMemPayloadStorage`]: crate::dispatching::callback_payloads::InMemPayloadStorage
+pub trait PayloadStorage<P> {
+    type Error;
+
+    /// Saves a `payload` with the specified `key` for `ttl`.
+    fn save_payload(
+        self: Arc<Self>,
+        key: String,
+        payload: P,
+        ttl: Duration,
+    ) -> BoxFuture<'static, Result<(), Self::Error>>
+    where
+        P: Send + 'static;
+
+    /// Returns a payload with the specified `key`.
+    ///
+    /// Returns `None` if there isn't such a payload or it has expired.
+    fn get_payload(
+        self: Arc<Self>,
+        key: String,
+    ) -> BoxFuture<'static, Result<Option<P>, Self::Error>>
+    where
+        P: Send + 'static;
+}
+
+/// An error returned from [`CallbackPayloads::resolve`].
+///
+/// [`CallbackPayloads::resolve`]: crate::dispatching::callback_payloads::CallbackPayloads::resolve
+#[derive(Debug, Error, PartialEq)]
+pub enum PayloadError<E>
+where
+    E: Debug + Display,
+{
+    /// Callback data wasn't created by [`CallbackPayloads`].
+    ///
+    /// [`CallbackPayloads`]: crate::dispatching::callback_payloads::CallbackPayloads
+    #[error("callback data doesn't contain a payload key")]
+    NotPayload,
+
+    /// A payload has expired or is unknown to the storage.
+    #[error("a payload has expired")]
+    Expired,
+
+    #[error("error from a payload storage: {0}")]
+    Storage(E),
+}
+
+/// A prefix of callback data containing payload keys.
+///
+/// Data produced by [`CallbackData`] starts with a digit, so they can be used
+/// together.
+///
+/// [`CallbackData`]: crate::utils::callback_data::CallbackData
+const KEY_PREFIX: char = '~';
+
+const DEFAULT_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+const DEFAULT_EXPIRED_TEXT: &str = "This button has expired.";
+
+/// Stores payloads of inline buttons on the server side.
+///
+/// See the [module-level documentation](crate::dispatching::callback_payloads).
+pub struct CallbackPayloads<P, S> {
+    storage: Arc<S>,
+    ttl: Duration,
+    expired_text: Option<String>,
+    hasher: RandomState,
+    counter: AtomicU64,
+    _payload: PhantomData<fn() -> P>,
+}
+
+impl<P, S> CallbackPayloads<P, S>
+where
+    S: PayloadStorage<P>,
+{
+    #[must_use]
+    pub fn new(storage: Arc<S>) -> Self {
+        Self {
+            storage,
+            ttl: DEFAULT_TTL,
+            expired_text: Some(DEFAULT_EXPIRED_TEXT.to_owned()),
+            hasher: RandomState::new(),
+            counter: AtomicU64::new(0),
+            _payload: PhantomData,
+        }
+    }
+
+    /// How long payloads are stored (the default is 1 day).
+    #[must_use]
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// A text shown to a user who pressed a button with an expired payload.
+    #[must_use]
+    pub fn expired_text<T>(mut self, text: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.expired_text = Some(text.into());
+        self
+    }
+
+    /// Answers buttons with expired payloads without any text.
+    #[must_use]
+    pub fn silent(mut self) -> Self {
+        self.expired_text = None;
+        self
+    }
+
+    /// The underlying storage.
+    pub fn storage(&self) -> &Arc<S> {
+        &self.storage
+    }
+
+    /// Saves `payload` and returns callback data referring to it.
+    pub async fn save(&self, payload: P) -> Result<String, S::Error>
+    where
+        P: Send + 'static,
+    {
+        let key = self.next_key();
+        Arc::clone(&self.storage).save_payload(key.clone(), payload, self.ttl).await?;
+        Ok(format!("{}{}", KEY_PREFIX, key))
+    }
+
+    /// Saves `payload` and returns a callback button referring to it.
+    pub async fn button<T>(&self, text: T, payload: P) -> Result<InlineKeyboardButton, S::Error>
+    where
+        T: Into<String>,
+        P: Send + 'static,
+    {
+        let data = self.save(payload).await?;
+        Ok(InlineKeyboardButton::callback(text.into(), data))
+    }
+
+    /// Restores a payload from callback data returned by
+    /// [`CallbackPayloads::save`].
+    ///
+    /// [`CallbackPayloads::save`]: crate::dispatching::callback_payloads::CallbackPayloads::save
+    pub async fn resolve(&self, data: &str) -> Result<P, PayloadError<S::Error>>
+    where
+        P: Send + 'static,
+        S::Error: Debug + Display,
+    {
+        let key = data.strip_prefix(KEY_PREFIX).ok_or(PayloadError::NotPayload)?;
+        match Arc::clone(&self.storage).get_payload(key.to_owned()).await {
+            Ok(Some(payload)) => Ok(payload),
+            Ok(None) => Err(PayloadError::Expired),
+            Err(error) => Err(PayloadError::Storage(error)),
+        }
+    }
+
+    /// Answers a callback query with an expired payload, if an expired text
+    /// is set.
+    pub async fn answer_expired(&self, cx: &UpdateWithCx<CallbackQuery>) {
+        let mut answer = cx.bot.answer_callback_query(cx.update.id.clone());
+        if let Some(text) = &self.expired_text {
+            answer = answer.text(text.as_str());
+        }
+
+        if let Err(error) = answer.send().await {
+            log::error!("Cannot answer a callback query with an expired payload: {}", error);
+        }
+    }
+
+    /// Returns a new random key.
+    ///
+    /// `RandomState` is seeded randomly in every process, so keys don't
+    /// repeat after restarts of a bot.
+    fn next_key(&self) -> String {
+        let mut hasher = self.hasher.build_hasher();
+        hasher.write_u64(self.counter.fetch_add(1, Ordering::Relaxed));
+        format!("{:x}", hasher.finish())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn save_and_resolve() {
+        let payloads = CallbackPayloads::new(InMemPayloadStorage::new());
+
+        let first = payloads.save("a".repeat(100)).await.unwrap();
+        let second = payloads.save("b".to_owned()).await.unwrap();
+
+        assert_ne!(first, second);
+        assert!(first.len() <= crate::utils::callback_data::MAX_CALLBACK_DATA_LENGTH);
+        assert_eq!(payloads.resolve(&first).await, Ok("a".repeat(100)));
+        assert_eq!(payloads.resolve(&second).await, Ok("b".to_owned()));
+        assert_eq!(payloads.resolve("~0").await, Err(PayloadError::Expired));
+        assert_eq!(payloads.resolve("0:1").await, Err(PayloadError::NotPayload));
+    }
+
+    #[tokio::test]
+    async fn expiry() {
+        let payloads =
+            CallbackPayloads::new(InMemPayloadStorage::new()).ttl(Duration::from_secs(0));
+
+        let data = payloads.save(1).await.unwrap();
+        assert_eq!(payloads.resolve(&data).await, Err(PayloadError::Expired));
+    }
+}
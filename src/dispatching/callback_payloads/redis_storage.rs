@@ -0,0 +1,95 @@
+use super::PayloadStorage;
+use crate::dispatching::dialogue::{RedisStorageError, Serializer};
+use futures::future::BoxFuture;
+use redis::{AsyncCommands, IntoConnectionInfo};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    convert::Infallible,
+    fmt::{Debug, Display},
+    ops::DerefMut,
+    sync::Arc,
+    time::Duration,
+};
+use tokio::sync::Mutex;
+
+/// A prefix of Redis keys, so that payloads don't clash with dialogues stored
+/// in the same database.
+const KEY_PREFIX: &str = "callback_payload:";
+
+/// A payload storage based on [Redis](https://redis.io/).
+///
+/// Payloads are stored with the `EX` option, so Redis removes them itself
+/// when they expire.
+pub struct RedisPayloadStorage<S> {
+    conn: Mutex<redis::aio::Connection>,
+    serializer: S,
+}
+
+impl<S> RedisPayloadStorage<S> {
+    pub async fn open(
+        url: impl IntoConnectionInfo,
+        serializer: S,
+    ) -> Result<Arc<Self>, RedisStorageError<Infallible>> {
+        Ok(Arc::new(Self {
+            conn: Mutex::new(redis::Client::open(url)?.get_async_connection().await?),
+            serializer,
+        }))
+    }
+}
+
+impl<S, P> PayloadStorage<P> for RedisPayloadStorage<S>
+where
+    S: Send + Sync + Serializer<P> + 'static,
+    P: Send + Serialize + DeserializeOwned + 'static,
+    <S as Serializer<P>>::Error: Debug + Display,
+{
+    type Error = RedisStorageError<<S as Serializer<P>>::Error>;
+
+    fn save_payload(
+        self: Arc<Self>,
+        key: String,
+        payload: P,
+        ttl: Duration,
+    ) -> BoxFuture<'static, Result<(), Self::Error>>
+    where
+        P: Send + 'static,
+    {
+        Box::pin(async move {
+            let payload =
+                self.serializer.serialize(&payload).map_err(RedisStorageError::SerdeError)?;
+            // `EX 0` is rejected by Redis.
+            let seconds = ttl.as_secs().max(1) as usize;
+            self.conn
+                .lock()
+                .await
+                .deref_mut()
+                .set_ex::<_, _, ()>(format!("{}{}", KEY_PREFIX, key), payload, seconds)
+                .await?;
+            Ok(())
+        })
+    }
+
+    fn get_payload(
+        self: Arc<Self>,
+        key: String,
+    ) -> BoxFuture<'static, Result<Option<P>, Self::Error>>
+    where
+        P: Send + 'static,
+    {
+        Box::pin(async move {
+            let payload = self
+                .conn
+                .lock()
+                .await
+                .deref_mut()
+                .get::<_, Option<Vec<u8>>>(format!("{}{}", KEY_PREFIX, key))
+                .await?;
+
+            payload
+                .map(|payload| {
+                    self.serializer.deserialize(&payload).map_err(RedisStorageError::SerdeError)
+                })
+                .transpose()
+        })
+    }
+}
@@ -0,0 +1,317 @@
+use crate::{
+    dispatching::UpdateWithCx,
+    requests::{Request, ResponseResult},
+    types::{ChatMember, ChatMemberStatus, Message},
+    Bot,
+};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::Mutex;
+
+/// A permission which a user must have to execute a command.
+///
+/// See [`RequiresPermission`] and [`CommandGuard`].
+///
+/// [`RequiresPermission`]: crate::dispatching::RequiresPermission
+/// [`CommandGuard`]: crate::dispatching::CommandGuard
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum Permission {
+    /// Everybody can execute a command.
+    Anyone,
+
+    /// Only the creator of a chat can execute a command.
+    Creator,
+
+    /// The creator or any administrator of a chat can execute a command.
+    Admin,
+
+    /// Only an administrator that can restrict, ban or unban chat members.
+    CanRestrictMembers,
+
+    /// Only an administrator that can delete messages of other users.
+    CanDeleteMessages,
+
+    /// Only an administrator that can pin messages.
+    CanPinMessages,
+
+    /// Only an administrator that can add new administrators.
+    CanPromoteMembers,
+
+    /// Only an administrator that can change the chat title, photo and other
+    /// settings.
+    CanChangeInfo,
+
+    /// Only an administrator that can invite new users to the chat.
+    CanInviteUsers,
+
+    /// Only users passed into [`CommandGuard::owners`] can execute a command.
+    ///
+    /// [`CommandGuard::owners`]: crate::dispatching::CommandGuard::owners
+    Owner,
+}
+
+impl Permission {
+    /// Checks whether `member` (an administrator of a chat) has this
+    /// permission.
+    ///
+    /// The creator of a chat has all the administrator permissions.
+    pub fn is_satisfied_by(&self, member: &ChatMember) -> bool {
+        if member.status == ChatMemberStatus::Creator {
+            return !matches!(self, Self::Owner);
+        }
+
+        if member.status != ChatMemberStatus::Administrator {
+            return matches!(self, Self::Anyone);
+        }
+
+        let flag = match self {
+            Self::Anyone | Self::Admin => return true,
+            Self::Creator | Self::Owner => return false,
+            Self::CanRestrictMembers => member.can_restrict_members,
+            Self::CanDeleteMessages => member.can_delete_messages,
+            Self::CanPinMessages => member.can_pin_messages,
+            Self::CanPromoteMembers => member.can_promote_members,
+            Self::CanChangeInfo => member.can_change_info,
+            Self::CanInviteUsers => member.can_invite_users,
+        };
+
+        flag.unwrap_or(false)
+    }
+}
+
+impl Default for Permission {
+    fn default() -> Self {
+        Self::Anyone
+    }
+}
+
+/// A command, which variants may require some permissions.
+///
+/// Used by [`DispatcherHandlerRxExt::guarded_commands`] to filter out
+/// commands sent by users without enough rights.
+///
+/// # Example
+/// ```
+/// use teloxide::{
+///     dispatching::{Permission, RequiresPermission},
+///     utils::command::BotCommand,
+/// };
+///
+/// #[derive(BotCommand)]
+/// #[command(rename = "lowercase")]
+/// enum Command {
+///     Help,
+///     Ban,
+///     Shutdown,
+/// }
+///
+/// impl RequiresPermission for Command {
+///     fn required_permission(&self) -> Permission {
+///         match self {
+///             Command::Help => Permission::Anyone,
+///             Command::Ban => Permission::CanRestrictMembers,
+///             Command::Shutdown => Permission::Owner,
+///         }
+///     }
+/// }
+/// ```
+///
+/// [`DispatcherHandlerRxExt::guarded_commands`]: crate::dispatching::DispatcherHandlerRxExt::guarded_commands
+pub trait RequiresPermission {
+    fn required_permission(&self) -> Permission;
+}
+
+/// A cache of chat administrators.
+///
+/// Results of [`Bot::get_chat_administrators`] are stored for `ttl` to avoid
+/// calling Telegram on every command.
+///
+/// [`Bot::get_chat_administrators`]: crate::Bot::get_chat_administrators
+#[derive(Debug)]
+pub struct AdminsCache {
+    ttl: Duration,
+    map: Mutex<HashMap<i64, (Instant, Arc<Vec<ChatMember>>)>>,
+}
+
+impl AdminsCache {
+    #[must_use]
+    pub fn new(ttl: Duration) -> Self {
+        Self { ttl, map: Mutex::new(HashMap::new()) }
+    }
+
+    /// Returns administrators of the chat with the specified `chat_id`.
+    ///
+    /// If there's no a fresh cached result, they are requested via
+    /// [`Bot::get_chat_administrators`].
+    ///
+    /// [`Bot::get_chat_administrators`]: crate::Bot::get_chat_administrators
+    pub async fn get(&self, bot: &Bot, chat_id: i64) -> ResponseResult<Arc<Vec<ChatMember>>> {
+        if let Some((fetched_at, admins)) = self.map.lock().await.get(&chat_id) {
+            if fetched_at.elapsed() < self.ttl {
+                return Ok(Arc::clone(admins));
+            }
+        }
+
+        let admins = Arc::new(bot.get_chat_administrators(chat_id).send().await?);
+        self.map.lock().await.insert(chat_id, (Instant::now(), Arc::clone(&admins)));
+        Ok(admins)
+    }
+
+    /// Forgets cached administrators of the chat with the specified `chat_id`.
+    pub async fn invalidate(&self, chat_id: i64) {
+        self.map.lock().await.remove(&chat_id);
+    }
+}
+
+/// Checks that users have enough rights to execute commands.
+///
+/// See [`DispatcherHandlerRxExt::guarded_commands`].
+///
+/// [`DispatcherHandlerRxExt::guarded_commands`]: crate::dispatching::DispatcherHandlerRxExt::guarded_commands
+#[derive(Debug)]
+pub struct CommandGuard {
+    admins: AdminsCache,
+    owners: Vec<i32>,
+    denial_text: Option<String>,
+}
+
+const DEFAULT_ADMINS_TTL: Duration = Duration::from_secs(5 * 60);
+const DEFAULT_DENIAL_TEXT: &str = "You don't have enough rights to use this command.";
+
+impl Default for CommandGuard {
+    fn default() -> Self {
+        Self {
+            admins: AdminsCache::new(DEFAULT_ADMINS_TTL),
+            owners: Vec::new(),
+            denial_text: Some(DEFAULT_DENIAL_TEXT.to_owned()),
+        }
+    }
+}
+
+impl CommandGuard {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How long administrators of a chat are cached (the default is 5
+    /// minutes).
+    #[must_use]
+    pub fn admins_ttl(mut self, ttl: Duration) -> Self {
+        self.admins = AdminsCache::new(ttl);
+        self
+    }
+
+    /// Identifiers of users satisfying [`Permission::Owner`].
+    ///
+    /// [`Permission::Owner`]: crate::dispatching::Permission::Owner
+    #[must_use]
+    pub fn owners<I>(mut self, owners: I) -> Self
+    where
+        I: IntoIterator<Item = i32>,
+    {
+        self.owners = owners.into_iter().collect();
+        self
+    }
+
+    /// A text replied to a user without enough rights.
+    #[must_use]
+    pub fn denial_text<T>(mut self, text: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.denial_text = Some(text.into());
+        self
+    }
+
+    /// Ignores users without enough rights instead of replying to them.
+    #[must_use]
+    pub fn silent(mut self) -> Self {
+        self.denial_text = None;
+        self
+    }
+
+    /// The underlying cache of chat administrators.
+    pub fn admins(&self) -> &AdminsCache {
+        &self.admins
+    }
+
+    /// Checks whether the sender of `cx.update` has `permission`.
+    pub async fn check(&self, cx: &UpdateWithCx<Message>, permission: &Permission) -> bool {
+        let user_id = match cx.update.from() {
+            Some(user) => user.id,
+            None => return *permission == Permission::Anyone,
+        };
+
+        match permission {
+            Permission::Anyone => true,
+            Permission::Owner => self.owners.contains(&user_id),
+            _ if cx.update.chat.is_private() => false,
+            _ => match self.admins.get(&cx.bot, cx.update.chat_id()).await {
+                Ok(admins) => admins
+                    .iter()
+                    .any(|member| member.user.id == user_id && permission.is_satisfied_by(member)),
+                Err(error) => {
+                    log::error!("Cannot get administrators of a chat: {}", error);
+                    false
+                }
+            },
+        }
+    }
+
+    /// Replies to a user without enough rights, if a denial text is set.
+    pub async fn deny(&self, cx: &UpdateWithCx<Message>) {
+        if let Some(text) = &self.denial_text {
+            if let Err(error) = cx.reply_to(text.as_str()).send().await {
+                log::error!("Cannot reply to a user without enough rights: {}", error);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::User;
+
+    fn member(status: ChatMemberStatus) -> ChatMember {
+        let user = User {
+            id: 0,
+            is_bot: false,
+            first_name: "".to_string(),
+            last_name: None,
+            username: None,
+            language_code: None,
+        };
+        ChatMember::new(user, status)
+    }
+
+    #[test]
+    fn creator_has_admin_rights() {
+        let creator = member(ChatMemberStatus::Creator);
+        assert!(Permission::Admin.is_satisfied_by(&creator));
+        assert!(Permission::Creator.is_satisfied_by(&creator));
+        assert!(Permission::CanRestrictMembers.is_satisfied_by(&creator));
+        assert!(!Permission::Owner.is_satisfied_by(&creator));
+    }
+
+    #[test]
+    fn admin_rights() {
+        let admin = member(ChatMemberStatus::Administrator).can_restrict_members(true);
+        assert!(Permission::Admin.is_satisfied_by(&admin));
+        assert!(Permission::CanRestrictMembers.is_satisfied_by(&admin));
+        assert!(!Permission::CanPinMessages.is_satisfied_by(&admin));
+        assert!(!Permission::Creator.is_satisfied_by(&admin));
+    }
+
+    #[test]
+    fn member_rights() {
+        let member = member(ChatMemberStatus::Member);
+        assert!(Permission::Anyone.is_satisfied_by(&member));
+        assert!(!Permission::Admin.is_satisfied_by(&member));
+    }
+}
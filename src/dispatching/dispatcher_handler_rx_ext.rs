@@ -1,5 +1,23 @@
-use crate::{prelude::UpdateWithCx, types::Message, utils::command::BotCommand};
+#[cfg(feature = "payments")]
+use crate::dispatching::payments::{OrderHandler, Payments};
+use crate::{
+    dispatching::{
+        callback_payloads::{CallbackPayloads, PayloadError, PayloadStorage},
+        inline_mode::{ChosenResult, InlineSessions},
+        media_groups::{self, GroupedMessage},
+        widgets::{Calendar, Date, Menu, Paginator, Selection, Time, TimePicker},
+        CommandGuard, RequiresPermission,
+    },
+    prelude::UpdateWithCx,
+    types::{CallbackQuery, ChosenInlineResult, Message},
+    utils::{callback_data::CallbackData, command::BotCommand},
+};
 use futures::{stream::BoxStream, Stream, StreamExt};
+use std::{
+    fmt::{Debug, Display},
+    sync::Arc,
+    time::Duration,
+};
 
 /// An extension trait to be used with [`DispatcherHandlerRx`].
 ///
@@ -20,6 +38,136 @@ pub trait DispatcherHandlerRxExt {
         Self: Stream<Item = UpdateWithCx<Message>>,
         C: BotCommand,
         N: Into<String> + Send;
+
+    /// Like [`DispatcherHandlerRxExt::commands`], but only yields commands
+    /// sent by users having [`RequiresPermission::required_permission`].
+    ///
+    /// Other users receive a reply configured in `guard`.
+    ///
+    /// [`DispatcherHandlerRxExt::commands`]: crate::dispatching::DispatcherHandlerRxExt::commands
+    /// [`RequiresPermission::required_permission`]: crate::dispatching::RequiresPermission::required_permission
+    fn guarded_commands<C, N>(
+        self,
+        bot_name: N,
+        guard: Arc<CommandGuard>,
+    ) -> BoxStream<'static, (UpdateWithCx<Message>, C)>
+    where
+        Self: Stream<Item = UpdateWithCx<Message>>,
+        C: BotCommand + RequiresPermission + Send + 'static,
+        N: Into<String> + Send;
+
+    /// Extracts only callback queries with data of type `D` from this stream
+    /// of arbitrary callback queries.
+    ///
+    /// Queries without data or with data that cannot be parsed into `D` (e.g.
+    /// of an outdated [`CallbackData::VERSION`]) are skipped.
+    ///
+    /// [`CallbackData::VERSION`]: crate::utils::callback_data::CallbackData::VERSION
+    fn data<D>(self) -> BoxStream<'static, (UpdateWithCx<CallbackQuery>, D)>
+    where
+        Self: Stream<Item = UpdateWithCx<CallbackQuery>>,
+        D: CallbackData + Send + 'static;
+
+    /// Extracts only callback queries with payloads saved by `payloads` from
+    /// this stream of arbitrary callback queries.
+    ///
+    /// Queries with expired payloads are answered by
+    /// [`CallbackPayloads::answer_expired`], errors of the storage are logged,
+    /// other queries are skipped.
+    ///
+    /// [`CallbackPayloads::answer_expired`]: crate::dispatching::callback_payloads::CallbackPayloads::answer_expired
+    fn payloads<P, S>(
+        self,
+        payloads: Arc<CallbackPayloads<P, S>>,
+    ) -> BoxStream<'static, (UpdateWithCx<CallbackQuery>, P)>
+    where
+        Self: Stream<Item = UpdateWithCx<CallbackQuery>>,
+        P: Send + 'static,
+        S: PayloadStorage<P> + Send + Sync + 'static,
+        S::Error: Debug + Display + Send;
+
+    /// Extracts items selected in keyboards of `paginator` from this stream
+    /// of arbitrary callback queries.
+    ///
+    /// Navigation between pages is handled by [`Paginator::handle`], other
+    /// queries are passed further as [`Selection::Other`].
+    ///
+    /// [`Paginator::handle`]: crate::dispatching::widgets::Paginator::handle
+    /// [`Selection::Other`]: crate::dispatching::widgets::Selection::Other
+    fn paginated<I>(self, paginator: Arc<Paginator<I>>) -> BoxStream<'static, Selection<I>>
+    where
+        Self: Stream<Item = UpdateWithCx<CallbackQuery>>,
+        I: Clone + Send + Sync + 'static;
+
+    /// Handles callback queries from `menu` and passes others further.
+    ///
+    /// See [`Menu::handle`].
+    ///
+    /// [`Menu::handle`]: crate::dispatching::widgets::Menu::handle
+    fn menu(self, menu: Arc<Menu>) -> BoxStream<'static, UpdateWithCx<CallbackQuery>>
+    where
+        Self: Stream<Item = UpdateWithCx<CallbackQuery>>;
+
+    /// Extracts dates selected in keyboards of `calendar` from this stream of
+    /// arbitrary callback queries.
+    ///
+    /// Switching months is handled by [`Calendar::handle`], other queries are
+    /// passed further as [`Selection::Other`].
+    ///
+    /// [`Calendar::handle`]: crate::dispatching::widgets::Calendar::handle
+    /// [`Selection::Other`]: crate::dispatching::widgets::Selection::Other
+    fn dates(self, calendar: Arc<Calendar>) -> BoxStream<'static, Selection<Date>>
+    where
+        Self: Stream<Item = UpdateWithCx<CallbackQuery>>;
+
+    /// Extracts times selected in keyboards of `picker` from this stream of
+    /// arbitrary callback queries.
+    ///
+    /// Switching between hours and minutes is handled by
+    /// [`TimePicker::handle`], other queries are passed further as
+    /// [`Selection::Other`].
+    ///
+    /// [`TimePicker::handle`]: crate::dispatching::widgets::TimePicker::handle
+    /// [`Selection::Other`]: crate::dispatching::widgets::Selection::Other
+    fn times(self, picker: Arc<TimePicker>) -> BoxStream<'static, Selection<Time>>
+    where
+        Self: Stream<Item = UpdateWithCx<CallbackQuery>>;
+
+    /// Aggregates messages of albums into [`GroupedMessage::MediaGroup`]s,
+    /// passing other messages as [`GroupedMessage::Message`]s.
+    ///
+    /// Messages with the same [`Message::media_group_id`] are buffered until
+    /// no more of them arrive during `window` (about a second is enough
+    /// usually), so other messages can be yielded before an album sent
+    /// earlier.
+    ///
+    /// [`GroupedMessage::MediaGroup`]: crate::dispatching::GroupedMessage::MediaGroup
+    /// [`GroupedMessage::Message`]: crate::dispatching::GroupedMessage::Message
+    /// [`Message::media_group_id`]: crate::types::Message::media_group_id
+    fn media_groups(self, window: Duration) -> BoxStream<'static, UpdateWithCx<GroupedMessage>>
+    where
+        Self: Stream<Item = UpdateWithCx<Message>>;
+
+    /// Passes messages with successful payments of `payments`' invoices to
+    /// its [`OrderHandler`] and returns other messages.
+    ///
+    /// [`OrderHandler`]: crate::dispatching::payments::OrderHandler
+    #[cfg(feature = "payments")]
+    fn payments<H>(self, payments: Arc<Payments<H>>) -> BoxStream<'static, UpdateWithCx<Message>>
+    where
+        Self: Stream<Item = UpdateWithCx<Message>>,
+        H: OrderHandler;
+
+    /// Resolves chosen inline results into results offered for inline
+    /// queries and remembered by `sessions`.
+    ///
+    /// Results, which weren't offered or have expired, are skipped.
+    fn chosen_results(
+        self,
+        sessions: Arc<InlineSessions>,
+    ) -> BoxStream<'static, UpdateWithCx<ChosenResult>>
+    where
+        Self: Stream<Item = UpdateWithCx<ChosenInlineResult>>;
 }
 
 impl<T> DispatcherHandlerRxExt for T
@@ -47,4 +195,158 @@ where
             async move { C::parse(&text, &bot_name).map(|command| (cx, command)).ok() }
         }))
     }
+
+    fn guarded_commands<C, N>(
+        self,
+        bot_name: N,
+        guard: Arc<CommandGuard>,
+    ) -> BoxStream<'static, (UpdateWithCx<Message>, C)>
+    where
+        Self: Stream<Item = UpdateWithCx<Message>>,
+        C: BotCommand + RequiresPermission + Send + 'static,
+        N: Into<String> + Send,
+    {
+        Box::pin(self.commands::<C, N>(bot_name).filter_map(move |(cx, command)| {
+            let guard = Arc::clone(&guard);
+
+            async move {
+                if guard.check(&cx, &command.required_permission()).await {
+                    Some((cx, command))
+                } else {
+                    guard.deny(&cx).await;
+                    None
+                }
+            }
+        }))
+    }
+
+    fn data<D>(self) -> BoxStream<'static, (UpdateWithCx<CallbackQuery>, D)>
+    where
+        Self: Stream<Item = UpdateWithCx<CallbackQuery>>,
+        D: CallbackData + Send + 'static,
+    {
+        Box::pin(self.filter_map(|cx| async move {
+            let data = cx.update.data.as_deref().and_then(|data| D::from_callback_data(data).ok());
+            data.map(|data| (cx, data))
+        }))
+    }
+
+    fn payloads<P, S>(
+        self,
+        payloads: Arc<CallbackPayloads<P, S>>,
+    ) -> BoxStream<'static, (UpdateWithCx<CallbackQuery>, P)>
+    where
+        Self: Stream<Item = UpdateWithCx<CallbackQuery>>,
+        P: Send + 'static,
+        S: PayloadStorage<P> + Send + Sync + 'static,
+        S::Error: Debug + Display + Send,
+    {
+        Box::pin(self.filter_map(move |cx| {
+            let payloads = Arc::clone(&payloads);
+
+            async move {
+                let data = cx.update.data.as_deref()?;
+                let payload = payloads.resolve(data).await;
+                match payload {
+                    Ok(payload) => Some((cx, payload)),
+                    Err(PayloadError::NotPayload) => None,
+                    Err(PayloadError::Expired) => {
+                        payloads.answer_expired(&cx).await;
+                        None
+                    }
+                    Err(PayloadError::Storage(error)) => {
+                        log::error!("Cannot get a callback payload: {}", error);
+                        None
+                    }
+                }
+            }
+        }))
+    }
+
+    fn paginated<I>(self, paginator: Arc<Paginator<I>>) -> BoxStream<'static, Selection<I>>
+    where
+        Self: Stream<Item = UpdateWithCx<CallbackQuery>>,
+        I: Clone + Send + Sync + 'static,
+    {
+        Box::pin(self.filter_map(move |cx| {
+            let paginator = Arc::clone(&paginator);
+
+            async move { paginator.handle(cx).await }
+        }))
+    }
+
+    fn menu(self, menu: Arc<Menu>) -> BoxStream<'static, UpdateWithCx<CallbackQuery>>
+    where
+        Self: Stream<Item = UpdateWithCx<CallbackQuery>>,
+    {
+        Box::pin(self.filter_map(move |cx| {
+            let menu = Arc::clone(&menu);
+
+            async move { menu.handle(cx).await }
+        }))
+    }
+
+    fn dates(self, calendar: Arc<Calendar>) -> BoxStream<'static, Selection<Date>>
+    where
+        Self: Stream<Item = UpdateWithCx<CallbackQuery>>,
+    {
+        Box::pin(self.filter_map(move |cx| {
+            let calendar = Arc::clone(&calendar);
+
+            async move { calendar.handle(cx).await }
+        }))
+    }
+
+    fn times(self, picker: Arc<TimePicker>) -> BoxStream<'static, Selection<Time>>
+    where
+        Self: Stream<Item = UpdateWithCx<CallbackQuery>>,
+    {
+        Box::pin(self.filter_map(move |cx| {
+            let picker = Arc::clone(&picker);
+
+            async move { picker.handle(cx).await }
+        }))
+    }
+
+    fn media_groups(self, window: Duration) -> BoxStream<'static, UpdateWithCx<GroupedMessage>>
+    where
+        Self: Stream<Item = UpdateWithCx<Message>>,
+    {
+        media_groups::media_groups(self, window)
+    }
+
+    #[cfg(feature = "payments")]
+    fn payments<H>(self, payments: Arc<Payments<H>>) -> BoxStream<'static, UpdateWithCx<Message>>
+    where
+        Self: Stream<Item = UpdateWithCx<Message>>,
+        H: OrderHandler,
+    {
+        Box::pin(self.filter_map(move |cx| {
+            let payments = Arc::clone(&payments);
+
+            async move { payments.handle_message(cx).await }
+        }))
+    }
+
+    fn chosen_results(
+        self,
+        sessions: Arc<InlineSessions>,
+    ) -> BoxStream<'static, UpdateWithCx<ChosenResult>>
+    where
+        Self: Stream<Item = UpdateWithCx<ChosenInlineResult>>,
+    {
+        Box::pin(self.filter_map(move |UpdateWithCx { bot, update }| {
+            let sessions = Arc::clone(&sessions);
+
+            async move {
+                match sessions.resolve(update) {
+                    Some(update) => Some(UpdateWithCx { bot, update }),
+                    None => {
+                        log::warn!("A chosen inline result wasn't offered or has expired");
+                        None
+                    }
+                }
+            }
+        }))
+    }
 }
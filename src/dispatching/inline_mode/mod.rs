@@ -0,0 +1,66 @@
+//! Answering inline queries.
+//!
+//! [`InlinePager`] answers inline queries with results of an
+//! [`InlineSearch`], splitting them into pages for infinite scrolling.
+//! [`InlineSessions`] remembers the sent results, so a
+//! [`ChosenInlineResult`] can be resolved into a [`ChosenResult`] with the
+//! original query and the chosen result (see
+//! [`DispatcherHandlerRxExt::chosen_results`]).
+//!
+//! # Example
+//! ```no_run
+//! use std::{sync::Arc, time::Duration};
+//!
+//! use teloxide::{
+//!     dispatching::inline_mode::{InlinePager, InlineSessions},
+//!     prelude::*,
+//!     types::{
+//!         ChosenInlineResult, InlineQuery, InlineQueryResult, InlineQueryResultArticle,
+//!         InputMessageContent, InputMessageContentText,
+//!     },
+//! };
+//!
+//! async fn search(query: InlineQuery) -> Vec<InlineQueryResult> {
+//!     // An expensive search.
+//!     (1..=200)
+//!         .map(|i| {
+//!             let text = format!("{} #{}", query.query, i);
+//!             let content = InputMessageContent::Text(InputMessageContentText::new(&text));
+//!             InlineQueryResultArticle::new(i.to_string(), text, content).into()
+//!         })
+//!         .collect()
+//! }
+//!
+//! # async fn run(bot: Bot) {
+//! let sessions = Arc::new(InlineSessions::new(Duration::from_secs(600)));
+//! let pager = Arc::new(
+//!     InlinePager::new(search)
+//!         .cache_ttl(Duration::from_secs(60))
+//!         .switch_pm("Nothing is found, ask me directly", "search")
+//!         .sessions(Arc::clone(&sessions)),
+//! );
+//!
+//! Dispatcher::new(bot)
+//!     .inline_queries_handler(pager.inline_queries_handler())
+//!     .chosen_inline_results_handler(move |rx: DispatcherHandlerRx<ChosenInlineResult>| {
+//!         rx.chosen_results(sessions).for_each(|cx| async move {
+//!             log::info!("{:?} is chosen for {:?}", cx.update.result, cx.update.query.query);
+//!         })
+//!     })
+//!     .dispatch()
+//!     .await;
+//! # }
+//! ```
+//!
+//! [`InlinePager`]: crate::dispatching::inline_mode::InlinePager
+//! [`InlineSearch`]: crate::dispatching::inline_mode::InlineSearch
+//! [`InlineSessions`]: crate::dispatching::inline_mode::InlineSessions
+//! [`ChosenInlineResult`]: crate::types::ChosenInlineResult
+//! [`ChosenResult`]: crate::dispatching::inline_mode::ChosenResult
+//! [`DispatcherHandlerRxExt::chosen_results`]: crate::dispatching::DispatcherHandlerRxExt::chosen_results
+
+mod pager;
+mod sessions;
+
+pub use pager::{InlinePager, InlineSearch, MAX_INLINE_RESULTS};
+pub use sessions::{ChosenResult, InlineSessions};
@@ -0,0 +1,312 @@
+use std::{
+    collections::{HashMap, HashSet},
+    future::Future,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use futures::{future::BoxFuture, StreamExt};
+use tokio::sync::Mutex;
+
+use crate::{
+    dispatching::{
+        inline_mode::InlineSessions, DispatcherHandler, DispatcherHandlerRx, UpdateWithCx,
+    },
+    error_handlers::OnError,
+    requests::Request,
+    types::{InlineQuery, InlineQueryResult},
+};
+
+/// The maximum number of results in an answer to an inline query.
+pub const MAX_INLINE_RESULTS: usize = 50;
+
+/// A search of results for an inline query, used by [`InlinePager`].
+///
+/// Implemented for async functions accepting an [`InlineQuery`].
+///
+/// [`InlinePager`]: crate::dispatching::inline_mode::InlinePager
+/// [`InlineQuery`]: crate::types::InlineQuery
+pub trait InlineSearch: Send + Sync + 'static {
+    /// Returns all the results for `query`, which are then split into pages.
+    fn search(self: Arc<Self>, query: InlineQuery) -> BoxFuture<'static, Vec<InlineQueryResult>>;
+}
+
+impl<F, Fut> InlineSearch for F
+where
+    F: Fn(InlineQuery) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Vec<InlineQueryResult>> + Send + 'static,
+{
+    fn search(self: Arc<Self>, query: InlineQuery) -> BoxFuture<'static, Vec<InlineQueryResult>> {
+        Box::pin(self(query))
+    }
+}
+
+type CacheKey = (Option<i32>, String);
+type CacheEntry = (Instant, Arc<Vec<InlineQueryResult>>);
+
+/// Answers inline queries with pages of results of an [`InlineSearch`].
+///
+/// Results are sent by pages of at most [`MAX_INLINE_RESULTS`] and the next
+/// page is requested by Telegram with [`InlineQuery::offset`], which
+/// `InlinePager` encodes by itself. Results with duplicate identifiers are
+/// dropped, since Telegram rejects such answers.
+///
+/// A search is run again for every page, unless its results are cached with
+/// [`InlinePager::cache_ttl`].
+///
+/// [`InlineSearch`]: crate::dispatching::inline_mode::InlineSearch
+/// [`MAX_INLINE_RESULTS`]: crate::dispatching::inline_mode::MAX_INLINE_RESULTS
+/// [`InlineQuery::offset`]: crate::types::InlineQuery::offset
+/// [`InlinePager::cache_ttl`]: crate::dispatching::inline_mode::InlinePager::cache_ttl
+pub struct InlinePager<S> {
+    search: Arc<S>,
+    page_size: usize,
+    cache_ttl: Option<Duration>,
+    cache: Mutex<HashMap<CacheKey, CacheEntry>>,
+    cache_time: Option<i32>,
+    is_personal: bool,
+    switch_pm: Option<(String, String)>,
+    sessions: Option<Arc<InlineSessions>>,
+}
+
+impl<S> InlinePager<S>
+where
+    S: InlineSearch,
+{
+    #[must_use]
+    pub fn new(search: S) -> Self {
+        Self {
+            search: Arc::new(search),
+            page_size: MAX_INLINE_RESULTS,
+            cache_ttl: None,
+            cache: Mutex::new(HashMap::new()),
+            cache_time: None,
+            is_personal: false,
+            switch_pm: None,
+            sessions: None,
+        }
+    }
+
+    /// The number of results in a page, [`MAX_INLINE_RESULTS`] by default.
+    ///
+    /// # Panics
+    /// If `val` is 0 or greater than [`MAX_INLINE_RESULTS`].
+    ///
+    /// [`MAX_INLINE_RESULTS`]: crate::dispatching::inline_mode::MAX_INLINE_RESULTS
+    #[must_use]
+    pub fn page_size(mut self, val: usize) -> Self {
+        assert!(
+            val > 0 && val <= MAX_INLINE_RESULTS,
+            "a page size must be between 1 and {}",
+            MAX_INLINE_RESULTS
+        );
+        self.page_size = val;
+        self
+    }
+
+    /// Caches results of a search for a query string for `val`, so next
+    /// pages and repeated queries don't run the search again.
+    #[must_use]
+    pub fn cache_ttl(mut self, val: Duration) -> Self {
+        self.cache_ttl = Some(val);
+        self
+    }
+
+    /// The maximum amount of time in seconds that answers may be cached by
+    /// Telegram (see [`AnswerInlineQuery::cache_time`]).
+    ///
+    /// [`AnswerInlineQuery::cache_time`]: crate::requests::AnswerInlineQuery::cache_time
+    #[must_use]
+    pub fn cache_time(mut self, val: i32) -> Self {
+        self.cache_time = Some(val);
+        self
+    }
+
+    /// Marks results as specific to a user (see
+    /// [`AnswerInlineQuery::is_personal`]). Cached results are then stored
+    /// per user as well.
+    ///
+    /// [`AnswerInlineQuery::is_personal`]: crate::requests::AnswerInlineQuery::is_personal
+    #[must_use]
+    pub fn is_personal(mut self, val: bool) -> Self {
+        self.is_personal = val;
+        self
+    }
+
+    /// A button, which is shown instead of results if a search has found
+    /// nothing (see [`AnswerInlineQuery::switch_pm_text`]).
+    ///
+    /// [`AnswerInlineQuery::switch_pm_text`]: crate::requests::AnswerInlineQuery::switch_pm_text
+    #[must_use]
+    pub fn switch_pm<T, P>(mut self, text: T, parameter: P) -> Self
+    where
+        T: Into<String>,
+        P: Into<String>,
+    {
+        self.switch_pm = Some((text.into(), parameter.into()));
+        self
+    }
+
+    /// Remembers sent results in `val`, so chosen results can be correlated
+    /// with them.
+    #[must_use]
+    pub fn sessions(mut self, val: Arc<InlineSessions>) -> Self {
+        self.sessions = Some(val);
+        self
+    }
+
+    /// Returns all the results for `query`, from the cache if possible.
+    pub async fn results(&self, query: &InlineQuery) -> Arc<Vec<InlineQueryResult>> {
+        let ttl = match self.cache_ttl {
+            Some(ttl) => ttl,
+            None => return Arc::new(self.search(query).await),
+        };
+
+        let key = (if self.is_personal { Some(query.from.id) } else { None }, query.query.clone());
+        if let Some((found_at, results)) = self.cache.lock().await.get(&key) {
+            if found_at.elapsed() < ttl {
+                return Arc::clone(results);
+            }
+        }
+
+        let results = Arc::new(self.search(query).await);
+        let mut cache = self.cache.lock().await;
+        cache.retain(|_, (found_at, _)| found_at.elapsed() < ttl);
+        cache.insert(key, (Instant::now(), Arc::clone(&results)));
+        results
+    }
+
+    async fn search(&self, query: &InlineQuery) -> Vec<InlineQueryResult> {
+        let mut results = Arc::clone(&self.search).search(query.clone()).await;
+        let mut ids = HashSet::new();
+        results.retain(|result| {
+            let unique = ids.insert(result.id().to_owned());
+            if !unique {
+                log::warn!("An inline result with a duplicate id {:?} is dropped", result.id());
+            }
+            unique
+        });
+        results
+    }
+
+    /// Answers an inline query with a page of results.
+    pub async fn handle_inline_query(&self, cx: UpdateWithCx<InlineQuery>) {
+        let results = self.results(&cx.update).await;
+        let (page, next_offset) = page(&results, &cx.update.offset, self.page_size);
+        if let Some(sessions) = &self.sessions {
+            sessions.offer(&cx.update, page, self.is_personal);
+        }
+
+        let mut request =
+            cx.bot.answer_inline_query(&cx.update.id, page.to_vec()).next_offset(next_offset);
+        if self.is_personal {
+            request = request.is_personal(true);
+        }
+        if let Some(cache_time) = self.cache_time {
+            request = request.cache_time(cache_time);
+        }
+        if let (true, Some((text, parameter))) = (results.is_empty(), &self.switch_pm) {
+            request = request.switch_pm_text(text).switch_pm_parameter(parameter);
+        }
+        request.send().await.log_on_error().await;
+    }
+
+    /// A handler of inline queries for [`Dispatcher::inline_queries_handler`].
+    ///
+    /// [`Dispatcher::inline_queries_handler`]: crate::dispatching::Dispatcher::inline_queries_handler
+    pub fn inline_queries_handler(
+        self: &Arc<Self>,
+    ) -> impl DispatcherHandler<InlineQuery> + Send + 'static {
+        let pager = Arc::clone(self);
+        move |rx: DispatcherHandlerRx<InlineQuery>| {
+            rx.for_each_concurrent(None, move |cx| {
+                let pager = Arc::clone(&pager);
+                async move { pager.handle_inline_query(cx).await }
+            })
+        }
+    }
+}
+
+/// Returns a page of `results` starting at `offset` and an offset of the next
+/// page, which is empty if there are no more results.
+///
+/// Unknown offsets are treated as the first page.
+fn page<'a>(
+    results: &'a [InlineQueryResult],
+    offset: &str,
+    page_size: usize,
+) -> (&'a [InlineQueryResult], String) {
+    let start = offset.parse().unwrap_or(0).min(results.len());
+    let end = (start + page_size).min(results.len());
+    let next_offset = if end < results.len() { end.to_string() } else { String::new() };
+    (&results[start..end], next_offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::types::{
+        InlineQueryResultArticle, InputMessageContent, InputMessageContentText, User,
+    };
+
+    fn article(id: &str) -> InlineQueryResult {
+        InlineQueryResultArticle::new(
+            id,
+            id,
+            InputMessageContent::Text(InputMessageContentText::new(id)),
+        )
+        .into()
+    }
+
+    fn ids(results: &[InlineQueryResult]) -> Vec<&str> {
+        results.iter().map(InlineQueryResult::id).collect()
+    }
+
+    #[test]
+    fn pages() {
+        let results: Vec<_> = (0..5).map(|i| article(&i.to_string())).collect();
+
+        let (first, next_offset) = page(&results, "", 2);
+        assert_eq!((ids(first), next_offset.as_str()), (vec!["0", "1"], "2"));
+        let (second, next_offset) = page(&results, "2", 2);
+        assert_eq!((ids(second), next_offset.as_str()), (vec!["2", "3"], "4"));
+        let (last, next_offset) = page(&results, "4", 2);
+        assert_eq!((ids(last), next_offset.as_str()), (vec!["4"], ""));
+        let (beyond, next_offset) = page(&results, "10", 2);
+        assert_eq!((ids(beyond), next_offset.as_str()), (vec![], ""));
+        let (invalid, _) = page(&results, "invalid", 2);
+        assert_eq!(ids(invalid), vec!["0", "1"]);
+    }
+
+    #[tokio::test]
+    async fn unique_cached_results() {
+        let searches = Arc::new(AtomicUsize::new(0));
+        let pager = InlinePager::new({
+            let searches = Arc::clone(&searches);
+            move |_: InlineQuery| {
+                searches.fetch_add(1, Ordering::SeqCst);
+                async { vec![article("a"), article("b"), article("a")] }
+            }
+        })
+        .cache_ttl(Duration::from_secs(60));
+        let query = InlineQuery::new(
+            "id",
+            User {
+                id: 1,
+                is_bot: false,
+                first_name: "Alice".to_owned(),
+                last_name: None,
+                username: None,
+                language_code: None,
+            },
+            "query",
+            "",
+        );
+
+        assert_eq!(ids(&pager.results(&query).await), vec!["a", "b"]);
+        assert_eq!(ids(&pager.results(&query).await), vec!["a", "b"]);
+        assert_eq!(searches.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,209 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    dispatching::UpdateWithCx,
+    requests::{EditMessageCaption, EditMessageReplyMarkup, EditMessageText},
+    types::{ChatOrInlineMessage, ChosenInlineResult, InlineQuery, InlineQueryResult},
+};
+
+struct Session {
+    offered_at: Instant,
+    query: InlineQuery,
+    results: HashMap<String, InlineQueryResult>,
+}
+
+/// Results offered for inline queries, which are correlated with
+/// [`ChosenInlineResult`]s.
+///
+/// Results are remembered for `ttl` per a query string, since a
+/// [`ChosenInlineResult`] doesn't contain an identifier of its inline query.
+/// Personal results are remembered per a user as well, but other results are
+/// shared by all users, because Telegram may show them to other users from
+/// its cache without sending inline queries. Chosen results are received only
+/// if inline feedback is enabled via [@Botfather].
+///
+/// [`ChosenInlineResult`]: crate::types::ChosenInlineResult
+/// [@Botfather]: https://t.me/botfather
+pub struct InlineSessions {
+    ttl: Duration,
+    sessions: Mutex<HashMap<(Option<i32>, String), Session>>,
+}
+
+impl InlineSessions {
+    #[must_use]
+    pub fn new(ttl: Duration) -> Self {
+        Self { ttl, sessions: Mutex::new(HashMap::new()) }
+    }
+
+    /// Remembers `results` offered for `query`, which are specific to its
+    /// user if `is_personal` is `true` (see
+    /// [`AnswerInlineQuery::is_personal`]).
+    ///
+    /// Results of next pages are added to the ones of the first page, and
+    /// the first page starts a new session.
+    ///
+    /// [`AnswerInlineQuery::is_personal`]: crate::requests::AnswerInlineQuery::is_personal
+    pub fn offer(&self, query: &InlineQuery, results: &[InlineQueryResult], is_personal: bool) {
+        let mut sessions = self.sessions.lock().unwrap();
+        let ttl = self.ttl;
+        sessions.retain(|_, session| session.offered_at.elapsed() < ttl);
+
+        let key = (if is_personal { Some(query.from.id) } else { None }, query.query.clone());
+        let new_session = || Session {
+            offered_at: Instant::now(),
+            query: query.clone(),
+            results: HashMap::new(),
+        };
+        let session = if query.offset.is_empty() {
+            sessions.insert(key.clone(), new_session());
+            sessions.get_mut(&key).expect("the session has just been inserted")
+        } else {
+            sessions.entry(key).or_insert_with(new_session)
+        };
+        session.offered_at = Instant::now();
+        session
+            .results
+            .extend(results.iter().map(|result| (result.id().to_owned(), result.clone())));
+    }
+
+    /// Returns the query and the result `chosen` was chosen from, or `None`
+    /// if they weren't offered or have expired.
+    ///
+    /// Personal results of the user are looked up first, then shared ones.
+    #[must_use]
+    pub fn resolve(&self, chosen: ChosenInlineResult) -> Option<ChosenResult> {
+        let sessions = self.sessions.lock().unwrap();
+        let (query, result) = [Some(chosen.from.id), None].iter().find_map(|&user_id| {
+            let session = sessions.get(&(user_id, chosen.query.clone()))?;
+            if session.offered_at.elapsed() >= self.ttl {
+                return None;
+            }
+            let result = session.results.get(&chosen.result_id)?;
+            Some((session.query.clone(), result.clone()))
+        })?;
+        Some(ChosenResult { query, result, chosen })
+    }
+}
+
+/// A result chosen by a user with the inline query it was offered for.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct ChosenResult {
+    /// The inline query the result was offered for.
+    ///
+    /// Results, which are not personal, are shared by users, so it may be a
+    /// query of another user with the same query string.
+    pub query: InlineQuery,
+
+    /// The chosen result.
+    pub result: InlineQueryResult,
+
+    /// The update about the chosen result.
+    pub chosen: ChosenInlineResult,
+}
+
+impl ChosenResult {
+    /// The sent inline message, which is available only if the result has an
+    /// inline keyboard attached.
+    #[must_use]
+    pub fn inline_message(&self) -> Option<ChatOrInlineMessage> {
+        let inline_message_id = self.chosen.inline_message_id.clone()?;
+        Some(ChatOrInlineMessage::Inline { inline_message_id })
+    }
+}
+
+impl UpdateWithCx<ChosenResult> {
+    /// Edits a text of the sent inline message, if it's available (see
+    /// [`ChosenResult::inline_message`]).
+    ///
+    /// [`ChosenResult::inline_message`]: crate::dispatching::inline_mode::ChosenResult::inline_message
+    pub fn edit_message_text<T>(&self, text: T) -> Option<EditMessageText>
+    where
+        T: Into<String>,
+    {
+        Some(self.bot.edit_message_text(self.update.inline_message()?, text))
+    }
+
+    /// Edits a caption of the sent inline message, if it's available (see
+    /// [`ChosenResult::inline_message`]).
+    ///
+    /// [`ChosenResult::inline_message`]: crate::dispatching::inline_mode::ChosenResult::inline_message
+    pub fn edit_message_caption(&self) -> Option<EditMessageCaption> {
+        Some(self.bot.edit_message_caption(self.update.inline_message()?))
+    }
+
+    /// Edits a reply markup of the sent inline message, if it's available
+    /// (see [`ChosenResult::inline_message`]).
+    ///
+    /// [`ChosenResult::inline_message`]: crate::dispatching::inline_mode::ChosenResult::inline_message
+    pub fn edit_message_reply_markup(&self) -> Option<EditMessageReplyMarkup> {
+        Some(self.bot.edit_message_reply_markup(self.update.inline_message()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{
+        InlineQueryResultArticle, InputMessageContent, InputMessageContentText, User,
+    };
+
+    fn article(id: &str) -> InlineQueryResult {
+        InlineQueryResultArticle::new(
+            id,
+            id,
+            InputMessageContent::Text(InputMessageContentText::new(id)),
+        )
+        .into()
+    }
+
+    fn user(id: i32) -> User {
+        User {
+            id,
+            is_bot: false,
+            first_name: "Alice".to_owned(),
+            last_name: None,
+            username: None,
+            language_code: None,
+        }
+    }
+
+    #[test]
+    fn resolve() {
+        let sessions = InlineSessions::new(Duration::from_secs(60));
+        let first_page = InlineQuery::new("1", user(1), "cats", "");
+        sessions.offer(&first_page, &[article("a")], true);
+        sessions.offer(&InlineQuery::new("2", user(1), "cats", "1"), &[article("b")], true);
+
+        let chosen = sessions.resolve(ChosenInlineResult::new("b", user(1), "cats")).unwrap();
+        assert_eq!(chosen.query, first_page);
+        assert_eq!(chosen.result, article("b"));
+        assert_eq!(chosen.inline_message(), None);
+
+        assert_eq!(sessions.resolve(ChosenInlineResult::new("c", user(1), "cats")), None);
+        assert_eq!(sessions.resolve(ChosenInlineResult::new("a", user(2), "cats")), None);
+        assert_eq!(sessions.resolve(ChosenInlineResult::new("a", user(1), "dogs")), None);
+
+        sessions.offer(&InlineQuery::new("3", user(1), "cats", ""), &[article("c")], true);
+        assert_eq!(sessions.resolve(ChosenInlineResult::new("a", user(1), "cats")), None);
+    }
+
+    #[test]
+    fn shared() {
+        let sessions = InlineSessions::new(Duration::from_secs(60));
+        let query = InlineQuery::new("1", user(1), "cats", "");
+        sessions.offer(&query, &[article("a")], false);
+        sessions.offer(&InlineQuery::new("2", user(1), "cats", ""), &[article("b")], true);
+
+        // Telegram shows the cached answer to another user.
+        let chosen = sessions.resolve(ChosenInlineResult::new("a", user(2), "cats")).unwrap();
+        assert_eq!((chosen.query, chosen.result), (query, article("a")));
+        assert_eq!(sessions.resolve(ChosenInlineResult::new("b", user(2), "cats")), None);
+        assert!(sessions.resolve(ChosenInlineResult::new("a", user(1), "cats")).is_some());
+        assert!(sessions.resolve(ChosenInlineResult::new("b", user(1), "cats")).is_some());
+    }
+}
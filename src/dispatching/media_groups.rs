@@ -0,0 +1,188 @@
+use crate::{dispatching::UpdateWithCx, types::Message};
+use futures::{
+    stream::{self, BoxStream},
+    Stream, StreamExt,
+};
+use std::time::Duration;
+use tokio::time::{self, Instant};
+
+/// Messages of an album, sent together.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MediaGroup {
+    /// The identifier of the album (see [`Message::media_group_id`]).
+    ///
+    /// [`Message::media_group_id`]: crate::types::Message::media_group_id
+    pub media_group_id: String,
+
+    /// Messages of the album in the order of arrival.
+    pub messages: Vec<Message>,
+}
+
+/// An item of [`DispatcherHandlerRxExt::media_groups`].
+///
+/// [`DispatcherHandlerRxExt::media_groups`]: crate::dispatching::DispatcherHandlerRxExt::media_groups
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug, PartialEq)]
+pub enum GroupedMessage {
+    /// A message not belonging to an album.
+    Message(Message),
+
+    /// All the messages of an album.
+    MediaGroup(MediaGroup),
+}
+
+/// Messages of an album, which are still being received.
+struct Pending {
+    media_group_id: String,
+    deadline: Instant,
+    messages: Vec<UpdateWithCx<Message>>,
+}
+
+impl Pending {
+    fn into_update(self) -> UpdateWithCx<GroupedMessage> {
+        let bot = self.messages[0].bot.clone();
+        let messages = self.messages.into_iter().map(|cx| cx.update).collect();
+        let group = MediaGroup { media_group_id: self.media_group_id, messages };
+        UpdateWithCx { bot, update: GroupedMessage::MediaGroup(group) }
+    }
+}
+
+struct State {
+    /// `None` after the input stream has ended.
+    messages: Option<BoxStream<'static, UpdateWithCx<Message>>>,
+    pending: Vec<Pending>,
+    window: Duration,
+}
+
+impl State {
+    async fn next(mut self) -> Option<(UpdateWithCx<GroupedMessage>, Self)> {
+        loop {
+            let messages = match &mut self.messages {
+                Some(messages) => messages,
+                None if self.pending.is_empty() => return None,
+                None => {
+                    let group = self.pending.remove(0);
+                    return Some((group.into_update(), self));
+                }
+            };
+
+            let next = match self.pending.iter().map(|group| group.deadline).min() {
+                Some(deadline) => time::timeout_at(deadline, messages.next()).await,
+                None => Ok(messages.next().await),
+            };
+
+            match next {
+                // An album hasn't got new messages during the window.
+                Err(_) => {
+                    let (index, _) =
+                        self.pending.iter().enumerate().min_by_key(|(_, group)| group.deadline)?;
+                    let group = self.pending.remove(index);
+                    return Some((group.into_update(), self));
+                }
+                Ok(None) => self.messages = None,
+                Ok(Some(cx)) => {
+                    let media_group_id = match cx.update.media_group_id() {
+                        Some(id) => id.to_owned(),
+                        None => {
+                            let UpdateWithCx { bot, update } = cx;
+                            return Some((
+                                UpdateWithCx { bot, update: GroupedMessage::Message(update) },
+                                self,
+                            ));
+                        }
+                    };
+
+                    let deadline = Instant::now() + self.window;
+                    match self
+                        .pending
+                        .iter_mut()
+                        .find(|group| group.media_group_id == media_group_id)
+                    {
+                        Some(group) => {
+                            group.deadline = deadline;
+                            group.messages.push(cx);
+                        }
+                        None => self.pending.push(Pending {
+                            media_group_id,
+                            deadline,
+                            messages: vec![cx],
+                        }),
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// See [`DispatcherHandlerRxExt::media_groups`].
+///
+/// [`DispatcherHandlerRxExt::media_groups`]: crate::dispatching::DispatcherHandlerRxExt::media_groups
+pub(crate) fn media_groups<S>(
+    messages: S,
+    window: Duration,
+) -> BoxStream<'static, UpdateWithCx<GroupedMessage>>
+where
+    S: Stream<Item = UpdateWithCx<Message>> + Send + 'static,
+{
+    let state = State { messages: Some(Box::pin(messages)), pending: Vec::new(), window };
+    Box::pin(stream::unfold(state, State::next))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Bot;
+    use tokio::sync::mpsc;
+
+    fn message(id: i32, media_group_id: Option<&str>) -> Message {
+        let media_group_id =
+            media_group_id.map(|id| format!(r#","media_group_id":"{}""#, id)).unwrap_or_default();
+        let json = format!(
+            r#"{{
+                "message_id": {},
+                "date": 1581448857,
+                "chat": {{ "id": 1, "type": "private", "first_name": "A" }},
+                "photo": [{{ "file_id": "f", "file_unique_id": "u", "width": 1, "height": 1 }}]
+                {}
+            }}"#,
+            id, media_group_id
+        );
+        serde_json::from_str(&json).unwrap()
+    }
+
+    fn ids(update: &GroupedMessage) -> Vec<i32> {
+        match update {
+            GroupedMessage::Message(message) => vec![message.id],
+            GroupedMessage::MediaGroup(group) => group.messages.iter().map(|m| m.id).collect(),
+        }
+    }
+
+    #[tokio::test]
+    #[allow(deprecated)]
+    async fn groups() {
+        time::pause();
+        let (tx, rx) = mpsc::unbounded_channel();
+        let mut groups = media_groups(rx, Duration::from_millis(100));
+        let send = |id, media_group_id| {
+            let update = message(id, media_group_id);
+            tx.send(UpdateWithCx { bot: Bot::new("Doesn't matter here"), update }).unwrap();
+        };
+
+        send(1, Some("a"));
+        send(2, Some("b"));
+        send(3, None);
+        assert_eq!(ids(&groups.next().await.unwrap().update), [3]);
+
+        // The window of "a" is extended by a new message, so "b" ends first.
+        time::advance(Duration::from_millis(50)).await;
+        send(4, Some("a"));
+        send(5, Some("a"));
+        assert_eq!(ids(&groups.next().await.unwrap().update), [2]);
+        assert_eq!(ids(&groups.next().await.unwrap().update), [1, 4, 5]);
+
+        send(6, Some("c"));
+        drop(tx);
+        assert_eq!(ids(&groups.next().await.unwrap().update), [6]);
+        assert!(groups.next().await.is_none());
+    }
+}
@@ -43,17 +43,26 @@
 //! [`tokio::sync::mpsc::UnboundedReceiver`]: https://docs.rs/tokio/0.2.11/tokio/sync/mpsc/struct.UnboundedReceiver.html
 //! [examples/dialogue_bot]: https://github.com/teloxide/teloxide/tree/master/examples/dialogue_bot
 
+pub mod callback_payloads;
+mod command_guard;
 pub mod dialogue;
 mod dispatcher;
 mod dispatcher_handler;
 mod dispatcher_handler_rx_ext;
+pub mod inline_mode;
+mod media_groups;
+#[cfg(feature = "payments")]
+pub mod payments;
 pub(crate) mod repls;
 pub mod update_listeners;
 mod update_with_cx;
+pub mod widgets;
 
+pub use command_guard::{AdminsCache, CommandGuard, Permission, RequiresPermission};
 pub use dispatcher::Dispatcher;
 pub use dispatcher_handler::DispatcherHandler;
 pub use dispatcher_handler_rx_ext::DispatcherHandlerRxExt;
+pub use media_groups::{GroupedMessage, MediaGroup};
 use tokio::sync::mpsc::UnboundedReceiver;
 pub use update_with_cx::UpdateWithCx;
 
@@ -0,0 +1,123 @@
+use std::fmt::{self, Display};
+
+use crate::types::{Currency, LabeledPrice};
+
+/// An amount of money in a [`Currency`], stored in its smallest units.
+///
+/// [`Currency`]: crate::types::Currency
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Amount {
+    currency: Currency,
+    minor_units: i32,
+}
+
+impl Amount {
+    /// An amount of `minor_units` (e.g. cents for [`Currency::USD`]).
+    ///
+    /// [`Currency::USD`]: crate::types::Currency::USD
+    #[must_use]
+    pub fn new(currency: Currency, minor_units: i32) -> Self {
+        Self { currency, minor_units }
+    }
+
+    /// Parses an amount written in major units, like `"14.99"`.
+    ///
+    /// Returns `None` if `amount` has more digits after the point than
+    /// [`Currency::exponent`] or doesn't fit into `i32` minor units.
+    ///
+    /// [`Currency::exponent`]: crate::types::Currency::exponent
+    #[must_use]
+    pub fn parse(currency: Currency, amount: &str) -> Option<Self> {
+        let exponent = currency.exponent() as usize;
+        let (major, fraction) = match amount.find('.') {
+            Some(point) => (&amount[..point], &amount[point + 1..]),
+            None => (amount, ""),
+        };
+        if major.is_empty()
+            || fraction.len() > exponent
+            || !major.bytes().chain(fraction.bytes()).all(|byte| byte.is_ascii_digit())
+        {
+            return None;
+        }
+
+        let digits = format!("{}{:0<width$}", major, fraction, width = exponent);
+        Some(Self::new(currency, digits.parse().ok()?))
+    }
+
+    #[must_use]
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    #[must_use]
+    pub fn minor_units(&self) -> i32 {
+        self.minor_units
+    }
+
+    /// A sum of amounts of the same currency, or `None` if the currencies
+    /// differ or the sum overflows.
+    #[must_use]
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        if self.currency != other.currency {
+            return None;
+        }
+        Some(Self::new(self.currency, self.minor_units.checked_add(other.minor_units)?))
+    }
+
+    /// A portion of a price, to be sent in an invoice or a shipping option.
+    #[must_use]
+    pub fn labeled_price<S>(self, label: S) -> LabeledPrice
+    where
+        S: Into<String>,
+    {
+        LabeledPrice::new(label, self.minor_units)
+    }
+}
+
+impl Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let exponent = self.currency.exponent();
+        let divisor = 10_i64.pow(exponent);
+        let minor_units = i64::from(self.minor_units);
+        let sign = if minor_units < 0 { "-" } else { "" };
+        let (major, fraction) = (minor_units.abs() / divisor, minor_units.abs() % divisor);
+
+        if exponent == 0 {
+            write!(f, "{}{} {}", sign, major, self.currency)
+        } else {
+            write!(
+                f,
+                "{}{}.{:0width$} {}",
+                sign,
+                major,
+                fraction,
+                self.currency,
+                width = exponent as usize
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn minor_units() {
+        assert_eq!(Amount::parse(Currency::USD, "14.99"), Some(Amount::new(Currency::USD, 1499)));
+        assert_eq!(Amount::parse(Currency::USD, "14.9"), Some(Amount::new(Currency::USD, 1490)));
+        assert_eq!(Amount::parse(Currency::USD, "14"), Some(Amount::new(Currency::USD, 1400)));
+        assert_eq!(Amount::parse(Currency::USD, "14.999"), None);
+        assert_eq!(Amount::parse(Currency::JPY, "1500"), Some(Amount::new(Currency::JPY, 1500)));
+        assert_eq!(Amount::parse(Currency::JPY, "1500.5"), None);
+        assert_eq!(Amount::parse(Currency::USD, "-1"), None);
+        assert_eq!(Amount::parse(Currency::USD, ".5"), None);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Amount::new(Currency::USD, 1405).to_string(), "14.05 USD");
+        assert_eq!(Amount::new(Currency::EUR, -5).to_string(), "-0.05 EUR");
+        assert_eq!(Amount::new(Currency::JPY, 1500).to_string(), "1500 JPY");
+    }
+}
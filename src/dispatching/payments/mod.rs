@@ -0,0 +1,510 @@
+//! Handling payments of invoices.
+//!
+//! Accepting a payment takes a shipping query (for invoices with flexible
+//! prices), a pre-checkout query, which must be answered within 10 seconds,
+//! and a message with [`SuccessfulPayment`]. [`Payments`] handles all of them
+//! with an [`OrderHandler`]:
+//!
+//!  - Invoices are sent with a payload built by [`Payments::invoice_payload`]
+//!    from an order identifier and signed with a secret key, so a user can't
+//!    pay for an order crafted by themselves. Queries and payments with invalid
+//!    payloads are rejected.
+//!  - Queries are answered by [`OrderHandler::shipping_options`] and
+//!    [`OrderHandler::pre_checkout`], or with an error if they don't answer in
+//!    time (see [`Payments::answer_timeout`]).
+//!  - Successful payments are passed to [`OrderHandler::successful_payment`].
+//!
+//! Prices are built from [`Amount`]s, which handle minor units of currencies.
+//!
+//! Requires the `payments` feature.
+//!
+//! # Example
+//! ```no_run
+//! use std::sync::Arc;
+//!
+//! use futures::future::BoxFuture;
+//! use teloxide::{
+//!     dispatching::payments::{Amount, OrderHandler, Payments},
+//!     prelude::*,
+//!     types::{Currency, PreCheckoutQuery, SuccessfulPayment},
+//! };
+//!
+//! struct Shop;
+//!
+//! impl OrderHandler for Shop {
+//!     fn pre_checkout(
+//!         self: Arc<Self>,
+//!         order_id: String,
+//!         query: PreCheckoutQuery,
+//!     ) -> BoxFuture<'static, Result<(), String>> {
+//!         Box::pin(async move {
+//!             // Check that the order is still in stock here.
+//!             Ok(())
+//!         })
+//!     }
+//!
+//!     fn successful_payment(
+//!         self: Arc<Self>,
+//!         order_id: String,
+//!         cx: UpdateWithCx<Message>,
+//!         payment: SuccessfulPayment,
+//!     ) -> BoxFuture<'static, ()> {
+//!         Box::pin(async move {
+//!             cx.answer_str(format!("Thank you! The order {} is paid.", order_id))
+//!                 .await
+//!                 .log_on_error()
+//!                 .await;
+//!         })
+//!     }
+//! }
+//!
+//! # async fn run(bot: Bot, chat_id: i32) -> Result<(), Box<dyn std::error::Error>> {
+//! let payments = Arc::new(Payments::new("a secret key", Shop));
+//!
+//! let price = Amount::parse(Currency::USD, "14.99").unwrap();
+//! bot.send_invoice(
+//!     chat_id,
+//!     "A book",
+//!     "A very good book",
+//!     payments.invoice_payload("book-42")?,
+//!     "PROVIDER_TOKEN",
+//!     "book",
+//!     price.currency().to_string(),
+//!     vec![price.labeled_price("The book")],
+//! )
+//! .await?;
+//!
+//! Dispatcher::new(bot)
+//!     .shipping_queries_handler(payments.shipping_queries_handler())
+//!     .pre_checkout_queries_handler(payments.pre_checkout_queries_handler())
+//!     .messages_handler(move |rx: DispatcherHandlerRx<Message>| {
+//!         rx.payments(payments).for_each(|message| async move {
+//!             // Other messages.
+//!         })
+//!     })
+//!     .dispatch()
+//!     .await;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`SuccessfulPayment`]: crate::types::SuccessfulPayment
+//! [`Payments`]: crate::dispatching::payments::Payments
+//! [`OrderHandler`]: crate::dispatching::payments::OrderHandler
+//! [`Payments::invoice_payload`]: crate::dispatching::payments::Payments::invoice_payload
+//! [`OrderHandler::shipping_options`]: crate::dispatching::payments::OrderHandler::shipping_options
+//! [`OrderHandler::pre_checkout`]: crate::dispatching::payments::OrderHandler::pre_checkout
+//! [`Payments::answer_timeout`]: crate::dispatching::payments::Payments::answer_timeout
+//! [`OrderHandler::successful_payment`]: crate::dispatching::payments::OrderHandler::successful_payment
+//! [`Amount`]: crate::dispatching::payments::Amount
+
+mod amount;
+mod signing;
+
+pub use amount::Amount;
+
+use std::{fmt::Debug, sync::Arc, time::Duration};
+
+use futures::{future::BoxFuture, StreamExt};
+use thiserror::Error;
+
+use crate::{
+    dispatching::{DispatcherHandler, DispatcherHandlerRx, UpdateWithCx},
+    error_handlers::OnError,
+    requests::Request,
+    types::{Message, PreCheckoutQuery, ShippingOption, ShippingQuery, SuccessfulPayment},
+};
+
+/// A handler of orders, paid with [`Payments`].
+///
+/// Identifiers of orders are extracted from verified invoice payloads.
+///
+/// [`Payments`]: crate::dispatching::payments::Payments
+pub trait OrderHandler: Send + Sync + 'static {
+    /// Validates a shipping address of `query` and returns shipping options
+    /// or an error message to be shown to a user.
+    ///
+    /// Called only for invoices with flexible prices. Rejects all addresses
+    /// by default.
+    fn shipping_options(
+        self: Arc<Self>,
+        order_id: String,
+        query: ShippingQuery,
+    ) -> BoxFuture<'static, Result<Vec<ShippingOption>, String>> {
+        let _ = (order_id, query);
+        Box::pin(async { Err("Shipping is not available".to_owned()) })
+    }
+
+    /// Approves a checkout or returns an error message to be shown to a
+    /// user.
+    fn pre_checkout(
+        self: Arc<Self>,
+        order_id: String,
+        query: PreCheckoutQuery,
+    ) -> BoxFuture<'static, Result<(), String>>;
+
+    /// Handles a successful payment of an order.
+    fn successful_payment(
+        self: Arc<Self>,
+        order_id: String,
+        cx: UpdateWithCx<Message>,
+        payment: SuccessfulPayment,
+    ) -> BoxFuture<'static, ()>;
+}
+
+/// An error returned from [`Payments::invoice_payload`].
+///
+/// [`Payments::invoice_payload`]: crate::dispatching::payments::Payments::invoice_payload
+#[derive(Debug, Error, PartialEq)]
+pub enum PaymentsError {
+    #[error("an order identifier is {len} bytes long, but must be at most {max_len} bytes")]
+    OrderIdTooLong { len: usize, max_len: usize },
+}
+
+/// Handles shipping queries, pre-checkout queries and successful payments
+/// with an [`OrderHandler`].
+///
+/// See the [module-level documentation] for an example.
+///
+/// [`OrderHandler`]: crate::dispatching::payments::OrderHandler
+/// [module-level documentation]: crate::dispatching::payments
+pub struct Payments<H> {
+    handler: Arc<H>,
+    secret: Vec<u8>,
+    answer_timeout: Duration,
+    timeout_error: String,
+    invalid_payload_error: String,
+}
+
+impl<H> Payments<H>
+where
+    H: OrderHandler,
+{
+    /// Creates payments, which sign payloads with `secret`.
+    ///
+    /// Changing `secret` invalidates all the sent invoices.
+    #[must_use]
+    pub fn new<S>(secret: S, handler: H) -> Self
+    where
+        S: Into<Vec<u8>>,
+    {
+        Self {
+            handler: Arc::new(handler),
+            secret: secret.into(),
+            answer_timeout: Duration::from_secs(8),
+            timeout_error: "Cannot process the order now, please try again later".to_owned(),
+            invalid_payload_error: "The invoice is invalid".to_owned(),
+        }
+    }
+
+    /// How long [`OrderHandler::shipping_options`] and
+    /// [`OrderHandler::pre_checkout`] may run before a query is answered with
+    /// the [timeout error]. 8 seconds by default, since Telegram cancels a
+    /// payment if a pre-checkout query isn't answered within 10 seconds.
+    ///
+    /// [`OrderHandler::shipping_options`]: crate::dispatching::payments::OrderHandler::shipping_options
+    /// [`OrderHandler::pre_checkout`]: crate::dispatching::payments::OrderHandler::pre_checkout
+    /// [timeout error]: crate::dispatching::payments::Payments::timeout_error
+    #[must_use]
+    pub fn answer_timeout(mut self, val: Duration) -> Self {
+        self.answer_timeout = val;
+        self
+    }
+
+    /// An error message shown to a user if a query isn't handled in time.
+    #[must_use]
+    pub fn timeout_error<S>(mut self, val: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.timeout_error = val.into();
+        self
+    }
+
+    /// An error message shown to a user if an invoice payload is invalid.
+    #[must_use]
+    pub fn invalid_payload_error<S>(mut self, val: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.invalid_payload_error = val.into();
+        self
+    }
+
+    /// Builds a signed payload of an invoice for the order `order_id`.
+    ///
+    /// `order_id` may be at most 103 bytes long, since a payload is limited
+    /// to 128 bytes.
+    pub fn invoice_payload(&self, order_id: &str) -> Result<String, PaymentsError> {
+        if order_id.len() > signing::MAX_ORDER_ID_LEN {
+            return Err(PaymentsError::OrderIdTooLong {
+                len: order_id.len(),
+                max_len: signing::MAX_ORDER_ID_LEN,
+            });
+        }
+        Ok(signing::sign(&self.secret, order_id))
+    }
+
+    /// Returns an order identifier from a signed invoice payload.
+    #[must_use]
+    pub fn order_id<'a>(&self, invoice_payload: &'a str) -> Option<&'a str> {
+        signing::verify(&self.secret, invoice_payload)
+    }
+
+    /// Answers a shipping query with [`OrderHandler::shipping_options`].
+    ///
+    /// [`OrderHandler::shipping_options`]: crate::dispatching::payments::OrderHandler::shipping_options
+    pub async fn handle_shipping_query(&self, cx: UpdateWithCx<ShippingQuery>) {
+        let answer = self.shipping_answer(&cx.update).await;
+        let request = match answer {
+            Ok(options) => {
+                cx.bot.answer_shipping_query(&cx.update.id, true).shipping_options(options)
+            }
+            Err(error) => cx.bot.answer_shipping_query(&cx.update.id, false).error_message(error),
+        };
+        request.send().await.log_on_error().await;
+    }
+
+    /// Answers a pre-checkout query with [`OrderHandler::pre_checkout`].
+    ///
+    /// [`OrderHandler::pre_checkout`]: crate::dispatching::payments::OrderHandler::pre_checkout
+    pub async fn handle_pre_checkout_query(&self, cx: UpdateWithCx<PreCheckoutQuery>) {
+        let answer = self.pre_checkout_answer(&cx.update).await;
+        let request = match answer {
+            Ok(()) => cx.bot.answer_pre_checkout_query(&cx.update.id, true),
+            Err(error) => {
+                cx.bot.answer_pre_checkout_query(&cx.update.id, false).error_message(error)
+            }
+        };
+        request.send().await.log_on_error().await;
+    }
+
+    /// Returns shipping options or an error message for `query`.
+    async fn shipping_answer(&self, query: &ShippingQuery) -> Result<Vec<ShippingOption>, String> {
+        let order_id = match self.order_id(&query.invoice_payload) {
+            Some(order_id) => order_id,
+            None => {
+                log::warn!("A shipping query with an invalid payload: {:?}", query);
+                return Err(self.invalid_payload_error.clone());
+            }
+        };
+
+        let options =
+            Arc::clone(&self.handler).shipping_options(order_id.to_owned(), query.clone());
+        match tokio::time::timeout(self.answer_timeout, options).await {
+            Ok(options) => options,
+            Err(_) => {
+                log::warn!("A shipping query for the order {} has timed out", order_id);
+                Err(self.timeout_error.clone())
+            }
+        }
+    }
+
+    /// Returns an approval or an error message for `query`.
+    async fn pre_checkout_answer(&self, query: &PreCheckoutQuery) -> Result<(), String> {
+        let order_id = match self.order_id(&query.invoice_payload) {
+            Some(order_id) => order_id,
+            None => {
+                log::warn!("A pre-checkout query with an invalid payload: {:?}", query);
+                return Err(self.invalid_payload_error.clone());
+            }
+        };
+
+        let answer = Arc::clone(&self.handler).pre_checkout(order_id.to_owned(), query.clone());
+        match tokio::time::timeout(self.answer_timeout, answer).await {
+            Ok(answer) => answer,
+            Err(_) => {
+                log::warn!("A pre-checkout query for the order {} has timed out", order_id);
+                Err(self.timeout_error.clone())
+            }
+        }
+    }
+
+    /// Passes a message with a successful payment to
+    /// [`OrderHandler::successful_payment`] and returns other messages back.
+    ///
+    /// [`OrderHandler::successful_payment`]: crate::dispatching::payments::OrderHandler::successful_payment
+    pub async fn handle_message(&self, cx: UpdateWithCx<Message>) -> Option<UpdateWithCx<Message>> {
+        let payment = match cx.update.successful_payment() {
+            Some(payment) => payment.clone(),
+            None => return Some(cx),
+        };
+        match self.order_id(&payment.invoice_payload) {
+            Some(order_id) => {
+                let order_id = order_id.to_owned();
+                Arc::clone(&self.handler).successful_payment(order_id, cx, payment).await;
+                None
+            }
+            None => {
+                log::error!("A successful payment with an invalid payload: {:?}", payment);
+                Some(cx)
+            }
+        }
+    }
+
+    /// A handler of shipping queries for
+    /// [`Dispatcher::shipping_queries_handler`].
+    ///
+    /// [`Dispatcher::shipping_queries_handler`]: crate::dispatching::Dispatcher::shipping_queries_handler
+    pub fn shipping_queries_handler(
+        self: &Arc<Self>,
+    ) -> impl DispatcherHandler<ShippingQuery> + Send + 'static {
+        let payments = Arc::clone(self);
+        move |rx: DispatcherHandlerRx<ShippingQuery>| {
+            rx.for_each_concurrent(None, move |cx| {
+                let payments = Arc::clone(&payments);
+                async move { payments.handle_shipping_query(cx).await }
+            })
+        }
+    }
+
+    /// A handler of pre-checkout queries for
+    /// [`Dispatcher::pre_checkout_queries_handler`].
+    ///
+    /// [`Dispatcher::pre_checkout_queries_handler`]: crate::dispatching::Dispatcher::pre_checkout_queries_handler
+    pub fn pre_checkout_queries_handler(
+        self: &Arc<Self>,
+    ) -> impl DispatcherHandler<PreCheckoutQuery> + Send + 'static {
+        let payments = Arc::clone(self);
+        move |rx: DispatcherHandlerRx<PreCheckoutQuery>| {
+            rx.for_each_concurrent(None, move |cx| {
+                let payments = Arc::clone(&payments);
+                async move { payments.handle_pre_checkout_query(cx).await }
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{CountryCode, Currency, ShippingAddress, User};
+
+    struct Shop;
+
+    impl OrderHandler for Shop {
+        fn pre_checkout(
+            self: Arc<Self>,
+            _: String,
+            _: PreCheckoutQuery,
+        ) -> BoxFuture<'static, Result<(), String>> {
+            Box::pin(async { Ok(()) })
+        }
+
+        fn successful_payment(
+            self: Arc<Self>,
+            _: String,
+            _: UpdateWithCx<Message>,
+            _: SuccessfulPayment,
+        ) -> BoxFuture<'static, ()> {
+            Box::pin(async {})
+        }
+    }
+
+    /// A handler, which never answers queries.
+    struct Stuck;
+
+    impl OrderHandler for Stuck {
+        fn shipping_options(
+            self: Arc<Self>,
+            _: String,
+            _: ShippingQuery,
+        ) -> BoxFuture<'static, Result<Vec<ShippingOption>, String>> {
+            Box::pin(futures::future::pending())
+        }
+
+        fn pre_checkout(
+            self: Arc<Self>,
+            _: String,
+            _: PreCheckoutQuery,
+        ) -> BoxFuture<'static, Result<(), String>> {
+            Box::pin(futures::future::pending())
+        }
+
+        fn successful_payment(
+            self: Arc<Self>,
+            _: String,
+            _: UpdateWithCx<Message>,
+            _: SuccessfulPayment,
+        ) -> BoxFuture<'static, ()> {
+            Box::pin(async {})
+        }
+    }
+
+    fn user() -> User {
+        User {
+            id: 1,
+            is_bot: false,
+            first_name: "Alice".to_owned(),
+            last_name: None,
+            username: None,
+            language_code: None,
+        }
+    }
+
+    fn pre_checkout_query(payload: &str) -> PreCheckoutQuery {
+        PreCheckoutQuery::new("id", user(), Currency::USD, 1499, payload)
+    }
+
+    fn shipping_query(payload: &str) -> ShippingQuery {
+        let address = ShippingAddress::new(CountryCode::US, "", "City", "Street", "", "12345");
+        ShippingQuery::new("id", user(), payload, address)
+    }
+
+    #[tokio::test]
+    async fn pre_checkout_answers() {
+        let payments = Payments::new("secret", Shop).invalid_payload_error("invalid");
+        let payload = payments.invoice_payload("42").unwrap();
+
+        assert_eq!(payments.pre_checkout_answer(&pre_checkout_query(&payload)).await, Ok(()));
+        assert_eq!(
+            payments.pre_checkout_answer(&pre_checkout_query("42:0123")).await,
+            Err("invalid".to_owned())
+        );
+
+        let stuck = Payments::new("secret", Stuck)
+            .answer_timeout(Duration::from_millis(10))
+            .timeout_error("timeout");
+        assert_eq!(
+            stuck.pre_checkout_answer(&pre_checkout_query(&payload)).await,
+            Err("timeout".to_owned())
+        );
+    }
+
+    #[tokio::test]
+    async fn shipping_answers() {
+        let payments = Payments::new("secret", Shop).invalid_payload_error("invalid");
+        let payload = payments.invoice_payload("42").unwrap();
+
+        assert_eq!(
+            payments.shipping_answer(&shipping_query(&payload)).await,
+            Err("Shipping is not available".to_owned())
+        );
+        assert_eq!(
+            payments.shipping_answer(&shipping_query("42:0123")).await,
+            Err("invalid".to_owned())
+        );
+
+        let stuck = Payments::new("secret", Stuck)
+            .answer_timeout(Duration::from_millis(10))
+            .timeout_error("timeout");
+        assert_eq!(
+            stuck.shipping_answer(&shipping_query(&payload)).await,
+            Err("timeout".to_owned())
+        );
+    }
+
+    #[test]
+    fn invoice_payload() {
+        let payments = Payments::new("secret", Shop);
+        let payload = payments.invoice_payload("42").unwrap();
+
+        assert_eq!(payments.order_id(&payload), Some("42"));
+        assert_eq!(Payments::new("another secret", Shop).order_id(&payload), None);
+        assert_eq!(
+            payments.invoice_payload(&"1".repeat(104)),
+            Err(PaymentsError::OrderIdTooLong { len: 104, max_len: 103 })
+        );
+        assert_eq!(payments.invoice_payload(&"1".repeat(103)).unwrap().len(), 128);
+    }
+}
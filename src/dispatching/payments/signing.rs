@@ -0,0 +1,62 @@
+use hmac::{Hmac, Mac, NewMac};
+use sha2::Sha256;
+
+/// The number of bytes of a signature kept in a payload.
+const SIGNATURE_LEN: usize = 12;
+
+/// The maximum length of an invoice payload in bytes.
+const MAX_PAYLOAD_LEN: usize = 128;
+
+/// The maximum length of an order identifier in bytes.
+pub(crate) const MAX_ORDER_ID_LEN: usize = MAX_PAYLOAD_LEN - 1 - SIGNATURE_LEN * 2;
+
+fn signature(secret: &[u8], order_id: &str) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_varkey(secret).expect("HMAC accepts keys of any length");
+    mac.update(order_id.as_bytes());
+    mac.finalize().into_bytes()[..SIGNATURE_LEN].to_vec()
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Builds an invoice payload of the form `<order_id>:<signature>`.
+///
+/// `order_id` must be at most [`MAX_ORDER_ID_LEN`] bytes long.
+pub(crate) fn sign(secret: &[u8], order_id: &str) -> String {
+    format!("{}:{}", order_id, to_hex(&signature(secret, order_id)))
+}
+
+/// Returns an order identifier from `payload` if it was signed with `secret`.
+pub(crate) fn verify<'a>(secret: &[u8], payload: &'a str) -> Option<&'a str> {
+    let separator = payload.rfind(':')?;
+    let (order_id, hex) = (&payload[..separator], &payload[separator + 1..]);
+
+    let expected = to_hex(&signature(secret, order_id));
+    // Compares in constant time, so a signature can't be guessed byte by byte.
+    let difference = expected
+        .bytes()
+        .zip(hex.bytes())
+        .fold(expected.len() ^ hex.len(), |difference, (a, b)| difference | usize::from(a ^ b));
+    if difference == 0 {
+        Some(order_id)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign_and_verify() {
+        let payload = sign(b"secret", "order:42");
+
+        assert_eq!(payload.len(), "order:42".len() + 1 + SIGNATURE_LEN * 2);
+        assert_eq!(verify(b"secret", &payload), Some("order:42"));
+        assert_eq!(verify(b"another secret", &payload), None);
+        assert_eq!(verify(b"secret", &payload.replace("42", "43")), None);
+        assert_eq!(verify(b"secret", "order:42"), None);
+    }
+}
@@ -0,0 +1,541 @@
+use super::{query_message, strip_id, Selection};
+use crate::{
+    dispatching::UpdateWithCx,
+    requests::Request,
+    types::{CallbackQuery, InlineKeyboardButton, InlineKeyboardMarkup},
+};
+use std::{
+    fmt::{self, Display},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// A date of the Gregorian calendar, selected in [`Calendar`].
+///
+/// [`Calendar`]: crate::dispatching::widgets::Calendar
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Date {
+    year: i32,
+    month: u32,
+    day: u32,
+}
+
+impl Date {
+    /// Returns `None`, if there is no such a date.
+    pub fn new(year: i32, month: u32, day: u32) -> Option<Self> {
+        if (1..=12).contains(&month) && day >= 1 && day <= days_in_month(year, month) {
+            Some(Self { year, month, day })
+        } else {
+            None
+        }
+    }
+
+    /// The date of a unix timestamp (in UTC), e.g. of [`Message::date`].
+    ///
+    /// [`Message::date`]: crate::types::Message::date
+    pub fn from_timestamp(timestamp: i64) -> Self {
+        // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        let days = timestamp.div_euclid(24 * 60 * 60) + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+        let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 } as u32;
+        let year = (year_of_era + era * 400 + if month <= 2 { 1 } else { 0 }) as i32;
+
+        Self { year, month, day }
+    }
+
+    /// The current date in UTC.
+    pub fn today() -> Self {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |now| now.as_secs());
+        Self::from_timestamp(now as i64)
+    }
+
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// A month from 1 to 12.
+    pub fn month(&self) -> u32 {
+        self.month
+    }
+
+    /// A day of a month from 1.
+    pub fn day(&self) -> u32 {
+        self.day
+    }
+
+    /// A day of a week from 0 (Monday) to 6 (Sunday).
+    pub fn weekday(&self) -> u32 {
+        // http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+        let year = i64::from(self.year) - if self.month <= 2 { 1 } else { 0 };
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let shifted_month = i64::from((self.month + 9) % 12);
+        let day_of_year = (153 * shifted_month + 2) / 5 + i64::from(self.day) - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        let days = era * 146_097 + day_of_era - 719_468;
+
+        // 1970-01-01 was Thursday.
+        (days + 3).rem_euclid(7) as u32
+    }
+}
+
+impl Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+fn is_leap_year(year: i32) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Names of months and days of a week shown in [`Calendar`].
+///
+/// [`Calendar`]: crate::dispatching::widgets::Calendar
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Locale {
+    months: Vec<String>,
+    weekdays: Vec<String>,
+    sunday_first: bool,
+}
+
+impl Locale {
+    /// Creates a locale with names of 12 `months` (from January) and 7
+    /// `weekdays` (from Monday).
+    ///
+    /// # Panics
+    /// If there are not 12 months or 7 weekdays.
+    #[must_use]
+    pub fn new<M, W>(months: M, weekdays: W) -> Self
+    where
+        M: IntoIterator,
+        M::Item: Into<String>,
+        W: IntoIterator,
+        W::Item: Into<String>,
+    {
+        let months: Vec<_> = months.into_iter().map(Into::into).collect();
+        let weekdays: Vec<_> = weekdays.into_iter().map(Into::into).collect();
+        assert_eq!(months.len(), 12, "there must be 12 months");
+        assert_eq!(weekdays.len(), 7, "there must be 7 weekdays");
+
+        Self { months, weekdays, sunday_first: false }
+    }
+
+    /// Weeks start on Sunday instead of Monday.
+    #[must_use]
+    pub fn sunday_first(mut self) -> Self {
+        self.sunday_first = true;
+        self
+    }
+
+    /// English names, weeks start on Sunday.
+    #[must_use]
+    pub fn english() -> Self {
+        Self::new(
+            [
+                "January",
+                "February",
+                "March",
+                "April",
+                "May",
+                "June",
+                "July",
+                "August",
+                "September",
+                "October",
+                "November",
+                "December",
+            ]
+            .iter()
+            .copied(),
+            ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"].iter().copied(),
+        )
+        .sunday_first()
+    }
+
+    #[must_use]
+    pub fn german() -> Self {
+        Self::new(
+            [
+                "Januar",
+                "Februar",
+                "März",
+                "April",
+                "Mai",
+                "Juni",
+                "Juli",
+                "August",
+                "September",
+                "Oktober",
+                "November",
+                "Dezember",
+            ]
+            .iter()
+            .copied(),
+            ["Mo", "Di", "Mi", "Do", "Fr", "Sa", "So"].iter().copied(),
+        )
+    }
+
+    #[must_use]
+    pub fn french() -> Self {
+        Self::new(
+            [
+                "Janvier",
+                "Février",
+                "Mars",
+                "Avril",
+                "Mai",
+                "Juin",
+                "Juillet",
+                "Août",
+                "Septembre",
+                "Octobre",
+                "Novembre",
+                "Décembre",
+            ]
+            .iter()
+            .copied(),
+            ["Lu", "Ma", "Me", "Je", "Ve", "Sa", "Di"].iter().copied(),
+        )
+    }
+
+    #[must_use]
+    pub fn spanish() -> Self {
+        Self::new(
+            [
+                "Enero",
+                "Febrero",
+                "Marzo",
+                "Abril",
+                "Mayo",
+                "Junio",
+                "Julio",
+                "Agosto",
+                "Septiembre",
+                "Octubre",
+                "Noviembre",
+                "Diciembre",
+            ]
+            .iter()
+            .copied(),
+            ["Lu", "Ma", "Mi", "Ju", "Vi", "Sá", "Do"].iter().copied(),
+        )
+    }
+
+    #[must_use]
+    pub fn russian() -> Self {
+        Self::new(
+            [
+                "Январь",
+                "Февраль",
+                "Март",
+                "Апрель",
+                "Май",
+                "Июнь",
+                "Июль",
+                "Август",
+                "Сентябрь",
+                "Октябрь",
+                "Ноябрь",
+                "Декабрь",
+            ]
+            .iter()
+            .copied(),
+            ["Пн", "Вт", "Ср", "Чт", "Пт", "Сб", "Вс"].iter().copied(),
+        )
+    }
+
+    /// The weekday shown in the `column` of a calendar (0 is Monday).
+    fn weekday_of_column(&self, column: u32) -> u32 {
+        if self.sunday_first {
+            (column + 6) % 7
+        } else {
+            column
+        }
+    }
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Self::english()
+    }
+}
+
+/// An inline keyboard showing a month to choose a date.
+///
+/// The keyboard consists of a header with the month and buttons switching
+/// months, names of weekdays and days of the month. Switching is handled by
+/// [`Calendar::handle`] (or [`DispatcherHandlerRxExt::dates`]), which edits
+/// the keyboard in place, so your code receives only selected dates and
+/// queries of other keyboards.
+///
+/// # Example
+/// ```no_run
+/// use std::sync::Arc;
+/// use teloxide::{
+///     dispatching::widgets::{Calendar, Date, Selection},
+///     prelude::*,
+///     types::CallbackQuery,
+/// };
+///
+/// # async fn run(cx: UpdateWithCx<Message>) -> ResponseResult<()> {
+/// let today = Date::today();
+/// let calendar = Arc::new(Calendar::new("booking").min_date(today));
+///
+/// cx.answer("When would you like to come?")
+///     .reply_markup(calendar.keyboard(today.year(), today.month()))
+///     .send()
+///     .await?;
+///
+/// Dispatcher::new(cx.bot.clone())
+///     .callback_queries_handler(move |rx: DispatcherHandlerRx<CallbackQuery>| {
+///         rx.dates(Arc::clone(&calendar)).for_each(|selection| async move {
+///             match selection {
+///                 Selection::Selected(cx, date) => {
+///                     cx.bot
+///                         .answer_callback_query(cx.update.id)
+///                         .text(format!("Booked for {}", date))
+///                         .send()
+///                         .await
+///                         .log_on_error()
+///                         .await;
+///                 }
+///                 // Queries of other keyboards, e.g. of a `TimePicker`, which
+///                 // can be passed to its `handle`.
+///                 Selection::Other(_) => {}
+///             }
+///         })
+///     })
+///     .dispatch()
+///     .await;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`Calendar::handle`]: crate::dispatching::widgets::Calendar::handle
+/// [`DispatcherHandlerRxExt::dates`]: crate::dispatching::DispatcherHandlerRxExt::dates
+#[derive(Clone, Debug)]
+pub struct Calendar {
+    id: String,
+    min_date: Option<Date>,
+    max_date: Option<Date>,
+    locale: Locale,
+}
+
+impl Calendar {
+    /// Creates a calendar.
+    ///
+    /// `id` is a prefix of callback data of this calendar, so it must be
+    /// unique among your keyboards and short (callback data is limited to 64
+    /// bytes).
+    #[must_use]
+    pub fn new<N>(id: N) -> Self
+    where
+        N: Into<String>,
+    {
+        Self { id: id.into(), min_date: None, max_date: None, locale: Locale::default() }
+    }
+
+    /// The earliest date, which can be selected.
+    #[must_use]
+    pub fn min_date(mut self, date: Date) -> Self {
+        self.min_date = Some(date);
+        self
+    }
+
+    /// The latest date, which can be selected.
+    #[must_use]
+    pub fn max_date(mut self, date: Date) -> Self {
+        self.max_date = Some(date);
+        self
+    }
+
+    /// Names of months and weekdays (the default is [`Locale::english`]).
+    ///
+    /// [`Locale::english`]: crate::dispatching::widgets::Locale::english
+    #[must_use]
+    pub fn locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    fn is_allowed(&self, date: Date) -> bool {
+        !matches!(self.min_date, Some(min) if date < min)
+            && !matches!(self.max_date, Some(max) if date > max)
+    }
+
+    /// Renders the specified month (from 1 to 12) of the `year`.
+    ///
+    /// # Panics
+    /// If `month` is not from 1 to 12.
+    pub fn keyboard(&self, year: i32, month: u32) -> InlineKeyboardMarkup {
+        assert!((1..=12).contains(&month), "month must be from 1 to 12");
+
+        let noop = || format!("{}:n", self.id);
+        let button = |text: String, data: String| InlineKeyboardButton::callback(text, data);
+
+        let (prev_year, prev_month) = if month == 1 { (year - 1, 12) } else { (year, month - 1) };
+        let (next_year, next_month) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+        let prev_allowed =
+            !matches!(self.min_date, Some(min) if (prev_year, prev_month) < (min.year, min.month));
+        let next_allowed =
+            !matches!(self.max_date, Some(max) if (next_year, next_month) > (max.year, max.month));
+
+        let mut header = Vec::with_capacity(3);
+        header.push(if prev_allowed {
+            button("‹".to_owned(), format!("{}:m{}-{}", self.id, prev_year, prev_month))
+        } else {
+            button(" ".to_owned(), noop())
+        });
+        header.push(button(format!("{} {}", self.locale.months[month as usize - 1], year), noop()));
+        header.push(if next_allowed {
+            button("›".to_owned(), format!("{}:m{}-{}", self.id, next_year, next_month))
+        } else {
+            button(" ".to_owned(), noop())
+        });
+
+        let weekdays = (0..7)
+            .map(|column| {
+                let weekday = self.locale.weekday_of_column(column);
+                button(self.locale.weekdays[weekday as usize].clone(), noop())
+            })
+            .collect();
+
+        let mut keyboard = InlineKeyboardMarkup::default().append_row(header).append_row(weekdays);
+
+        let first = Date { year, month, day: 1 };
+        let offset =
+            (0..7).find(|&column| self.locale.weekday_of_column(column) == first.weekday());
+        let mut week = vec![button(" ".to_owned(), noop()); offset.unwrap_or(0) as usize];
+        for day in 1..=days_in_month(year, month) {
+            let date = Date { year, month, day };
+            week.push(if self.is_allowed(date) {
+                button(day.to_string(), format!("{}:d{}-{}-{}", self.id, year, month, day))
+            } else {
+                button(" ".to_owned(), noop())
+            });
+
+            if week.len() == 7 {
+                keyboard = keyboard.append_row(std::mem::take(&mut week));
+            }
+        }
+        if !week.is_empty() {
+            week.resize(7, button(" ".to_owned(), noop()));
+            keyboard = keyboard.append_row(week);
+        }
+
+        keyboard
+    }
+
+    /// Handles a callback query.
+    ///
+    /// Returns a selected date or a query, which doesn't belong to this
+    /// calendar. Buttons switching months edit the keyboard in place and are
+    /// answered.
+    pub async fn handle(&self, cx: UpdateWithCx<CallbackQuery>) -> Option<Selection<Date>> {
+        let data = match cx.update.data.as_deref().and_then(|data| strip_id(data, &self.id)) {
+            Some(data) => data.to_owned(),
+            None => return Some(Selection::Other(cx)),
+        };
+
+        if let Some(date) = data.strip_prefix('d') {
+            let mut parts = date.splitn(3, '-');
+            let date = match (parts.next(), parts.next(), parts.next()) {
+                (Some(year), Some(month), Some(day)) => {
+                    match (year.parse(), month.parse(), day.parse()) {
+                        (Ok(year), Ok(month), Ok(day)) => Date::new(year, month, day),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            };
+            if let Some(date) = date.filter(|&date| self.is_allowed(date)) {
+                return Some(Selection::Selected(cx, date));
+            }
+        } else if let Some(month) = data.strip_prefix('m') {
+            let mut parts = month.splitn(2, '-').map(str::parse::<i64>);
+            if let (Some(Ok(year)), Some(Ok(month))) = (parts.next(), parts.next()) {
+                if (1..=12).contains(&month) && year.abs() < 1_000_000 {
+                    self.show_month(&cx, year as i32, month as u32).await;
+                }
+            }
+        }
+
+        if let Err(error) = cx.bot.answer_callback_query(cx.update.id.clone()).send().await {
+            log::error!("Cannot answer a callback query of a calendar: {}", error);
+        }
+        None
+    }
+
+    async fn show_month(&self, cx: &UpdateWithCx<CallbackQuery>, year: i32, month: u32) {
+        let message = match query_message(&cx.update) {
+            Some(message) => message,
+            None => return,
+        };
+
+        let keyboard = self.keyboard(year, month);
+        if let Err(error) =
+            cx.bot.edit_message_reply_markup(message).reply_markup(keyboard).send().await
+        {
+            log::error!("Cannot show a month of a calendar: {}", error);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dispatching::widgets::tests::texts;
+
+    #[test]
+    fn dates() {
+        assert_eq!(Date::from_timestamp(0), Date::new(1970, 1, 1).unwrap());
+        assert_eq!(Date::from_timestamp(951_782_400), Date::new(2000, 2, 29).unwrap());
+        assert_eq!(Date::from_timestamp(-1), Date::new(1969, 12, 31).unwrap());
+        assert_eq!(Date::new(2000, 2, 29).unwrap().weekday(), 1);
+        assert_eq!(Date::new(2021, 1, 1).unwrap().weekday(), 4);
+        assert_eq!(Date::new(2021, 2, 29), None);
+        assert_eq!(Date::new(2021, 13, 1), None);
+        assert_eq!(Date::new(1900, 2, 28).unwrap().to_string(), "1900-02-28");
+    }
+
+    #[test]
+    fn month() {
+        let calendar = Calendar::new("c")
+            .min_date(Date::new(2021, 2, 10).unwrap())
+            .max_date(Date::new(2021, 3, 20).unwrap());
+
+        assert_eq!(
+            texts(&calendar.keyboard(2021, 2)),
+            vec![
+                " |February 2021|›",
+                "Su|Mo|Tu|We|Th|Fr|Sa",
+                " | | | | | | ",
+                " | | |10|11|12|13",
+                "14|15|16|17|18|19|20",
+                "21|22|23|24|25|26|27",
+                "28| | | | | | ",
+            ]
+        );
+
+        let calendar = calendar.locale(Locale::german());
+        assert_eq!(
+            &texts(&calendar.keyboard(2021, 3))[..3],
+            ["‹|März 2021| ", "Mo|Di|Mi|Do|Fr|Sa|So", "1|2|3|4|5|6|7"]
+        );
+    }
+}
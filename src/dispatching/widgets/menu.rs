@@ -0,0 +1,539 @@
+use super::{query_message, strip_id};
+use crate::{
+    dispatching::UpdateWithCx,
+    requests::{Request, ResponseResult},
+    types::{
+        CallbackQuery, ChatOrInlineMessage, InlineKeyboardButton, InlineKeyboardMarkup, Message,
+        ParseMode,
+    },
+    ApiErrorKind, Bot, KnownApiErrorKind, RequestError,
+};
+use futures::future::{BoxFuture, Future};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::Mutex;
+
+type Builder =
+    Box<dyn Fn(UpdateWithCx<CallbackQuery>) -> BoxFuture<'static, MenuNode> + Send + Sync>;
+type Action =
+    Box<dyn Fn(UpdateWithCx<CallbackQuery>) -> BoxFuture<'static, Option<String>> + Send + Sync>;
+/// A navigation stack of a message and when it was used last time.
+type Stack = (Instant, Arc<Mutex<Vec<String>>>);
+
+enum EntryKind {
+    Submenu(Arc<MenuNode>),
+    Dynamic(Builder),
+    Action(Action),
+}
+
+struct Entry {
+    key: String,
+    label: String,
+    kind: EntryKind,
+}
+
+/// A screen of [`Menu`]: a text and buttons below it.
+///
+/// Buttons open submenus (static or built on demand) or execute actions. Every
+/// button has a key, which must be unique among buttons of the node and short
+/// (it's a part of callback data, which is limited to 64 bytes).
+///
+/// [`Menu`]: crate::dispatching::widgets::Menu
+pub struct MenuNode {
+    text: String,
+    parse_mode: Option<ParseMode>,
+    columns: usize,
+    entries: Vec<Entry>,
+}
+
+impl MenuNode {
+    #[must_use]
+    pub fn new<T>(text: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Self { text: text.into(), parse_mode: None, columns: 1, entries: Vec::new() }
+    }
+
+    /// Send [Markdown] or [HTML], if you want Telegram apps to show [bold,
+    /// italic, fixed-width text or inline URLs] in the text of this node.
+    ///
+    /// [Markdown]: https://core.telegram.org/bots/api#markdown-style
+    /// [HTML]: https://core.telegram.org/bots/api#html-style
+    /// [bold, italic, fixed-width text or inline URLs]: https://core.telegram.org/bots/api#formatting-options
+    #[must_use]
+    pub fn parse_mode(mut self, val: ParseMode) -> Self {
+        self.parse_mode = Some(val);
+        self
+    }
+
+    /// The number of buttons in a row (the default is 1).
+    ///
+    /// # Panics
+    /// If `columns` is 0.
+    #[must_use]
+    pub fn columns(mut self, columns: usize) -> Self {
+        assert!(columns > 0, "number of columns must be positive");
+        self.columns = columns;
+        self
+    }
+
+    /// Adds a button opening the `node` submenu.
+    #[must_use]
+    pub fn submenu<K, L>(self, key: K, label: L, node: MenuNode) -> Self
+    where
+        K: Into<String>,
+        L: Into<String>,
+    {
+        self.entry(key.into(), label.into(), EntryKind::Submenu(Arc::new(node)))
+    }
+
+    /// Adds a button opening a submenu built by `builder`.
+    ///
+    /// `builder` is called every time the submenu is shown, so its text and
+    /// buttons may depend on the user or your data. Actions inside of it
+    /// rebuild it, so changes made by them are shown immediately.
+    #[must_use]
+    pub fn dynamic<K, L, F, Fut>(self, key: K, label: L, builder: F) -> Self
+    where
+        K: Into<String>,
+        L: Into<String>,
+        F: Fn(UpdateWithCx<CallbackQuery>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = MenuNode> + Send + 'static,
+    {
+        let builder: Builder = Box::new(move |cx| Box::pin(builder(cx)));
+        self.entry(key.into(), label.into(), EntryKind::Dynamic(builder))
+    }
+
+    /// Adds a button executing `action`.
+    ///
+    /// A text returned by `action` is shown to the user as a notification.
+    #[must_use]
+    pub fn action<K, L, F, Fut>(self, key: K, label: L, action: F) -> Self
+    where
+        K: Into<String>,
+        L: Into<String>,
+        F: Fn(UpdateWithCx<CallbackQuery>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Option<String>> + Send + 'static,
+    {
+        let action: Action = Box::new(move |cx| Box::pin(action(cx)));
+        self.entry(key.into(), label.into(), EntryKind::Action(action))
+    }
+
+    fn entry(mut self, key: String, label: String, kind: EntryKind) -> Self {
+        self.entries.push(Entry { key, label, kind });
+        self
+    }
+
+    fn keyboard(&self, id: &str, back_text: Option<&str>) -> InlineKeyboardMarkup {
+        let buttons: Vec<_> = self
+            .entries
+            .iter()
+            .map(|entry| {
+                InlineKeyboardButton::callback(
+                    entry.label.clone(),
+                    format!("{}:>{}", id, entry.key),
+                )
+            })
+            .collect();
+        let keyboard = InlineKeyboardMarkup::new(
+            buttons.chunks(self.columns).map(<[_]>::to_vec).collect::<Vec<_>>(),
+        );
+
+        match back_text {
+            Some(text) => keyboard.append_row(vec![InlineKeyboardButton::callback(
+                text.to_owned(),
+                format!("{}:<", id),
+            )]),
+            None => keyboard,
+        }
+    }
+}
+
+const DEFAULT_BACK_TEXT: &str = "« Back";
+const DEFAULT_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// A hierarchical inline menu editing its message in place.
+///
+/// A menu is a tree of [`MenuNode`]s. Pressing a button of a node shows its
+/// submenu in the same message or executes an action, and a "« Back" button
+/// returns to the parent node. Callback queries are answered by the menu
+/// itself.
+///
+/// The navigation stack of every message is kept in memory for a day since
+/// the last press (see [`Menu::ttl`]), so after that or after you restart
+/// your bot, old menus start from the root. Presses in the same message are
+/// handled one by one.
+///
+/// # Example
+/// ```no_run
+/// use std::sync::{
+///     atomic::{AtomicBool, Ordering},
+///     Arc,
+/// };
+/// use teloxide::{
+///     dispatching::widgets::{Menu, MenuNode},
+///     prelude::*,
+///     types::CallbackQuery,
+/// };
+///
+/// # async fn run(cx: UpdateWithCx<Message>) -> ResponseResult<()> {
+/// static NOTIFICATIONS: AtomicBool = AtomicBool::new(true);
+///
+/// let menu = Arc::new(Menu::new(
+///     "settings",
+///     MenuNode::new("Settings")
+///         .dynamic("n", "Notifications", |_| async {
+///             let enabled = NOTIFICATIONS.load(Ordering::Relaxed);
+///             let label = if enabled { "Disable" } else { "Enable" };
+///             MenuNode::new(format!("Notifications are {}", if enabled { "on" } else { "off" }))
+///                 .action("t", label, |_| async {
+///                     NOTIFICATIONS.fetch_xor(true, Ordering::Relaxed);
+///                     None
+///                 })
+///         })
+///         .submenu("a", "About", MenuNode::new("A bot with settings")),
+/// ));
+///
+/// menu.send(&cx.bot, cx.chat_id()).await?;
+///
+/// Dispatcher::new(cx.bot.clone())
+///     .callback_queries_handler(move |rx: DispatcherHandlerRx<CallbackQuery>| {
+///         rx.menu(Arc::clone(&menu)).for_each(|_other_queries| async {})
+///     })
+///     .dispatch()
+///     .await;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`Menu::ttl`]: crate::dispatching::widgets::Menu::ttl
+/// [`MenuNode`]: crate::dispatching::widgets::MenuNode
+pub struct Menu {
+    id: String,
+    root: Arc<MenuNode>,
+    back_text: String,
+    ttl: Duration,
+    stacks: std::sync::Mutex<HashMap<ChatOrInlineMessage, Stack>>,
+}
+
+impl Menu {
+    /// Creates a menu starting from the `root` node.
+    ///
+    /// `id` is a prefix of callback data of this menu, so it must be unique
+    /// among your keyboards and short (callback data is limited to 64 bytes).
+    #[must_use]
+    pub fn new<N>(id: N, root: MenuNode) -> Self
+    where
+        N: Into<String>,
+    {
+        Self {
+            id: id.into(),
+            root: Arc::new(root),
+            back_text: DEFAULT_BACK_TEXT.to_owned(),
+            ttl: DEFAULT_TTL,
+            stacks: std::sync::Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// A text of the button returning to the parent node.
+    #[must_use]
+    pub fn back_text<T>(mut self, text: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.back_text = text.into();
+        self
+    }
+
+    /// How long a navigation stack of a message is kept since the last press
+    /// (the default is a day).
+    #[must_use]
+    pub fn ttl(mut self, val: Duration) -> Self {
+        self.ttl = val;
+        self
+    }
+
+    /// Sends the root node of this menu to the chat with the specified
+    /// `chat_id`.
+    pub async fn send(&self, bot: &Bot, chat_id: i64) -> ResponseResult<Message> {
+        let mut request = bot
+            .send_message(chat_id, self.root.text.clone())
+            .reply_markup(self.root.keyboard(&self.id, None));
+        if let Some(parse_mode) = self.root.parse_mode {
+            request = request.parse_mode(parse_mode);
+        }
+
+        request.send().await
+    }
+
+    /// Handles a callback query from this menu.
+    ///
+    /// Returns the query back, if it doesn't belong to this menu.
+    pub async fn handle(
+        &self,
+        cx: UpdateWithCx<CallbackQuery>,
+    ) -> Option<UpdateWithCx<CallbackQuery>> {
+        let data = match cx.update.data.as_deref().and_then(|data| strip_id(data, &self.id)) {
+            Some(data) => data.to_owned(),
+            None => return Some(cx),
+        };
+
+        let answer_text = match query_message(&cx.update) {
+            Some(message) => self.navigate(&cx, message, &data).await,
+            None => None,
+        };
+
+        let mut answer = cx.bot.answer_callback_query(cx.update.id.clone());
+        if let Some(text) = answer_text {
+            answer = answer.text(text);
+        }
+        if let Err(error) = answer.send().await {
+            log::error!("Cannot answer a callback query of a menu: {}", error);
+        }
+
+        None
+    }
+
+    /// Returns the navigation stack of `message`, removing expired ones.
+    fn stack(&self, message: &ChatOrInlineMessage) -> Arc<Mutex<Vec<String>>> {
+        let mut stacks = self.stacks.lock().unwrap();
+        let ttl = self.ttl;
+        stacks.retain(|_, (used_at, _)| used_at.elapsed() < ttl);
+
+        let (used_at, stack) =
+            stacks.entry(message.clone()).or_insert_with(|| (Instant::now(), Arc::default()));
+        *used_at = Instant::now();
+        Arc::clone(stack)
+    }
+
+    /// Executes a pressed button, shows the resulting node and returns a
+    /// notification text.
+    async fn navigate(
+        &self,
+        cx: &UpdateWithCx<CallbackQuery>,
+        message: ChatOrInlineMessage,
+        data: &str,
+    ) -> Option<String> {
+        // The stack is locked until the node is shown, so quick presses don't
+        // overwrite each other.
+        let stack = self.stack(&message);
+        let mut stack = stack.lock().await;
+        let (node, text) = self.step(cx, &mut stack, data).await;
+        let node = match node {
+            Some(node) => node,
+            None => return text,
+        };
+
+        let back_text = if stack.is_empty() { None } else { Some(self.back_text.as_str()) };
+        let mut request = cx
+            .bot
+            .edit_message_text(message, node.text.clone())
+            .reply_markup(node.keyboard(&self.id, back_text));
+        if let Some(parse_mode) = node.parse_mode {
+            request = request.parse_mode(parse_mode);
+        }
+
+        match request.send().await {
+            Ok(_)
+            | Err(RequestError::ApiError {
+                kind: ApiErrorKind::Known(KnownApiErrorKind::MessageNotModified),
+                ..
+            }) => {}
+            Err(error) => log::error!("Cannot show a node of a menu: {}", error),
+        }
+
+        text
+    }
+
+    /// Executes a pressed button, updating `stack`, and returns a node to be
+    /// shown (`None` if nothing has changed) and a notification text.
+    async fn step(
+        &self,
+        cx: &UpdateWithCx<CallbackQuery>,
+        stack: &mut Vec<String>,
+        data: &str,
+    ) -> (Option<Arc<MenuNode>>, Option<String>) {
+        let (mut node, dynamic) = self.resolve(cx, stack).await;
+
+        let mut text = None;
+        if data == "<" {
+            if stack.pop().is_some() {
+                node = self.resolve(cx, stack).await.0;
+            }
+        } else if let Some(key) = data.strip_prefix('>') {
+            match node.entries.iter().find(|entry| entry.key == key).map(|entry| &entry.kind) {
+                Some(EntryKind::Submenu(child)) => {
+                    stack.push(key.to_owned());
+                    node = Arc::clone(child);
+                }
+                Some(EntryKind::Dynamic(builder)) => {
+                    stack.push(key.to_owned());
+                    node = Arc::new(builder(copy(cx)).await);
+                }
+                Some(EntryKind::Action(action)) => {
+                    text = action(copy(cx)).await;
+                    // Nothing has changed in static nodes.
+                    if !dynamic {
+                        return (None, text);
+                    }
+                    node = self.resolve(cx, stack).await.0;
+                }
+                // The keyboard is outdated, so the current node is shown again.
+                None => {}
+            }
+        }
+
+        (Some(node), text)
+    }
+
+    /// Returns the node at the end of `path` and whether it was built
+    /// dynamically.
+    ///
+    /// Keys, which aren't found (e.g. if a dynamic node has changed), are
+    /// removed from `path`.
+    async fn resolve(
+        &self,
+        cx: &UpdateWithCx<CallbackQuery>,
+        path: &mut Vec<String>,
+    ) -> (Arc<MenuNode>, bool) {
+        let mut node = Arc::clone(&self.root);
+        let mut dynamic = false;
+
+        for (depth, key) in path.iter().enumerate() {
+            let child = match node.entries.iter().find(|entry| &entry.key == key) {
+                Some(Entry { kind: EntryKind::Submenu(child), .. }) => Arc::clone(child),
+                Some(Entry { kind: EntryKind::Dynamic(builder), .. }) => {
+                    dynamic = true;
+                    Arc::new(builder(copy(cx)).await)
+                }
+                _ => {
+                    path.truncate(depth);
+                    break;
+                }
+            };
+            node = child;
+        }
+
+        (node, dynamic)
+    }
+}
+
+fn copy(cx: &UpdateWithCx<CallbackQuery>) -> UpdateWithCx<CallbackQuery> {
+    UpdateWithCx { bot: cx.bot.clone(), update: cx.update.clone() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{dispatching::widgets::tests::query, types::InlineKeyboardButtonKind};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn data(keyboard: &InlineKeyboardMarkup) -> Vec<Vec<(&str, &str)>> {
+        keyboard
+            .inline_keyboard
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|button| match &button.kind {
+                        InlineKeyboardButtonKind::CallbackData(data) => {
+                            (button.text.as_str(), data.as_str())
+                        }
+                        _ => unreachable!(),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn keyboard() {
+        let node = MenuNode::new("Settings")
+            .columns(2)
+            .submenu("l", "Language", MenuNode::new("Language"))
+            .dynamic("n", "Notifications", |_| async { MenuNode::new("Notifications") })
+            .action("r", "Reset", |_| async { None });
+
+        assert_eq!(
+            data(&node.keyboard("m", None)),
+            vec![vec![("Language", "m:>l"), ("Notifications", "m:>n")], vec![("Reset", "m:>r")]]
+        );
+        assert_eq!(data(&node.keyboard("m", Some("Back")))[2], vec![("Back", "m:<")]);
+    }
+
+    /// Presses a button with `data` and returns a text of the shown node and
+    /// a notification.
+    async fn press(
+        menu: &Menu,
+        stack: &mut Vec<String>,
+        data: &str,
+    ) -> (Option<String>, Option<String>) {
+        let (node, text) = menu.step(&query(data), stack, data).await;
+        (node.map(|node| node.text.clone()), text)
+    }
+
+    fn some(text: &str) -> Option<String> {
+        Some(text.to_owned())
+    }
+
+    #[tokio::test]
+    async fn navigation() {
+        static PRESSES: AtomicUsize = AtomicUsize::new(0);
+        let menu = Menu::new(
+            "m",
+            MenuNode::new("Root")
+                .submenu(
+                    "s",
+                    "Settings",
+                    MenuNode::new("Settings").submenu("l", "Language", MenuNode::new("Language")),
+                )
+                .dynamic("c", "Counter", |_| async {
+                    MenuNode::new(PRESSES.load(Ordering::SeqCst).to_string()).action(
+                        "i",
+                        "Increment",
+                        |_| async {
+                            PRESSES.fetch_add(1, Ordering::SeqCst);
+                            some("Incremented")
+                        },
+                    )
+                })
+                .action("r", "Reset", |_| async { some("Reset") }),
+        );
+        let mut stack = Vec::new();
+
+        assert_eq!(press(&menu, &mut stack, ">s").await, (some("Settings"), None));
+        assert_eq!(press(&menu, &mut stack, ">l").await, (some("Language"), None));
+        assert_eq!(stack, vec!["s", "l"]);
+        // The keyboard is outdated.
+        assert_eq!(press(&menu, &mut stack, ">x").await, (some("Language"), None));
+        assert_eq!(press(&menu, &mut stack, "<").await, (some("Settings"), None));
+        assert_eq!(press(&menu, &mut stack, "<").await, (some("Root"), None));
+        assert_eq!(press(&menu, &mut stack, "<").await, (some("Root"), None));
+        assert!(stack.is_empty());
+
+        assert_eq!(press(&menu, &mut stack, ">r").await, (None, some("Reset")));
+        assert_eq!(press(&menu, &mut stack, ">c").await, (some("0"), None));
+        assert_eq!(press(&menu, &mut stack, ">i").await, (some("1"), some("Incremented")));
+        assert_eq!(stack, vec!["c"]);
+
+        // Unknown keys are removed from the stack.
+        let mut stack = vec!["s".to_owned(), "x".to_owned()];
+        assert_eq!(press(&menu, &mut stack, "<").await, (some("Root"), None));
+        assert!(stack.is_empty());
+    }
+
+    #[tokio::test]
+    async fn expired_stacks() {
+        let message = ChatOrInlineMessage::Inline { inline_message_id: "id".to_owned() };
+        let menu = Menu::new("m", MenuNode::new("Root"));
+        menu.stack(&message).lock().await.push("s".to_owned());
+        assert_eq!(*menu.stack(&message).lock().await, vec!["s"]);
+
+        let menu = menu.ttl(Duration::from_secs(0));
+        assert!(menu.stack(&message).lock().await.is_empty());
+        assert_eq!(menu.stacks.lock().unwrap().len(), 1);
+    }
+}
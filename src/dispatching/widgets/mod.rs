@@ -0,0 +1,116 @@
+//! Reusable inline keyboards handling their callback queries themselves.
+//!
+//! A widget renders an [`InlineKeyboardMarkup`], which you send with a
+//! message, and then processes callback queries from it: navigation buttons
+//! edit the message in place, while meaningful choices are yielded to your
+//! code.
+//!
+//! Callback data of every widget starts with an identifier passed to its
+//! constructor, so several widgets can process the same stream of callback
+//! queries: queries, which don't belong to a widget, are passed further as
+//! [`Selection::Other`].
+//!
+//! [`InlineKeyboardMarkup`]: crate::types::InlineKeyboardMarkup
+//! [`Selection::Other`]: crate::dispatching::widgets::Selection::Other
+
+mod calendar;
+mod menu;
+mod pagination;
+mod time_picker;
+
+pub use calendar::{Calendar, Date, Locale};
+pub use menu::{Menu, MenuNode};
+pub use pagination::{Page, Paginator};
+pub use time_picker::{Time, TimePicker};
+
+use crate::{
+    dispatching::UpdateWithCx,
+    types::{CallbackQuery, ChatOrInlineMessage},
+};
+
+/// A callback query processed by a widget.
+#[derive(Debug)]
+pub enum Selection<T> {
+    /// A value selected in a keyboard of the widget.
+    Selected(UpdateWithCx<CallbackQuery>, T),
+
+    /// A query, which doesn't belong to the widget.
+    Other(UpdateWithCx<CallbackQuery>),
+}
+
+impl<T> Selection<T> {
+    /// Returns the selected value, if any.
+    #[must_use]
+    pub fn selected(self) -> Option<(UpdateWithCx<CallbackQuery>, T)> {
+        match self {
+            Self::Selected(cx, value) => Some((cx, value)),
+            Self::Other(_) => None,
+        }
+    }
+
+    /// Returns the query, if it doesn't belong to the widget.
+    #[must_use]
+    pub fn other(self) -> Option<UpdateWithCx<CallbackQuery>> {
+        match self {
+            Self::Selected(..) => None,
+            Self::Other(cx) => Some(cx),
+        }
+    }
+}
+
+/// Returns the message with the pressed keyboard.
+///
+/// Returns `None`, if the query has neither a message nor an identifier of an
+/// inline message.
+pub(crate) fn query_message(query: &CallbackQuery) -> Option<ChatOrInlineMessage> {
+    match (&query.message, &query.inline_message_id) {
+        (Some(message), _) => Some(ChatOrInlineMessage::Chat {
+            chat_id: message.chat_id().into(),
+            message_id: message.id,
+        }),
+        (None, Some(inline_message_id)) => {
+            Some(ChatOrInlineMessage::Inline { inline_message_id: inline_message_id.clone() })
+        }
+        (None, None) => None,
+    }
+}
+
+/// Returns the part of callback data following a widget identifier `id`.
+pub(crate) fn strip_id<'a>(data: &'a str, id: &str) -> Option<&'a str> {
+    data.strip_prefix(id)?.strip_prefix(':')
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::{
+        dispatching::UpdateWithCx,
+        types::{CallbackQuery, InlineKeyboardMarkup, User},
+        Bot,
+    };
+
+    /// Texts of buttons, joined by `|` in every row.
+    pub(crate) fn texts(keyboard: &InlineKeyboardMarkup) -> Vec<String> {
+        keyboard
+            .inline_keyboard
+            .iter()
+            .map(|row| row.iter().map(|button| button.text.as_str()).collect::<Vec<_>>().join("|"))
+            .collect()
+    }
+
+    /// A callback query with `data`.
+    #[allow(deprecated)]
+    pub(crate) fn query(data: &str) -> UpdateWithCx<CallbackQuery> {
+        let user = User {
+            id: 1,
+            is_bot: false,
+            first_name: "Alice".to_owned(),
+            last_name: None,
+            username: None,
+            language_code: None,
+        };
+        UpdateWithCx {
+            bot: Bot::new("token"),
+            update: CallbackQuery::new("id", user, "instance").data(data),
+        }
+    }
+}
@@ -0,0 +1,403 @@
+use super::{query_message, strip_id, Selection};
+use crate::{
+    dispatching::UpdateWithCx,
+    requests::Request,
+    types::{CallbackQuery, InlineKeyboardButton, InlineKeyboardMarkup},
+};
+use futures::future::{BoxFuture, Future};
+
+/// A page of items returned by a loader of [`Paginator`].
+///
+/// [`Paginator`]: crate::dispatching::widgets::Paginator
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Page<T> {
+    /// Items of the requested page.
+    pub items: Vec<T>,
+
+    /// The total number of items on all the pages.
+    pub total: usize,
+}
+
+type Label<T> = Box<dyn Fn(&T) -> String + Send + Sync>;
+type Loader<T> = Box<dyn Fn(usize, usize) -> BoxFuture<'static, Page<T>> + Send + Sync>;
+
+enum Source<T> {
+    Items(Vec<T>),
+    Loader(Loader<T>),
+}
+
+const DEFAULT_PAGE_SIZE: usize = 10;
+const DEFAULT_PREV_TEXT: &str = "‹ Prev";
+const DEFAULT_NEXT_TEXT: &str = "Next ›";
+
+/// An inline keyboard listing items page by page.
+///
+/// Items are shown as buttons followed by a row with "‹ Prev" and "Next ›"
+/// buttons and the number of the current page. Navigation is handled by
+/// [`Paginator::handle`] (or [`DispatcherHandlerRxExt::paginated`]), which
+/// edits the keyboard in place, so your code receives only selected items
+/// and queries of other keyboards.
+///
+/// # Example
+/// ```no_run
+/// use std::sync::Arc;
+/// use teloxide::{
+///     dispatching::widgets::{Paginator, Selection},
+///     prelude::*,
+///     types::CallbackQuery,
+/// };
+///
+/// # async fn run(cx: UpdateWithCx<Message>) -> ResponseResult<()> {
+/// let fruits = vec!["apple", "banana", "cherry", "durian", "elderberry"];
+/// let paginator =
+///     Arc::new(Paginator::new("fruits", fruits, |fruit| fruit.to_string()).page_size(2));
+///
+/// cx.answer("Choose a fruit:").reply_markup(paginator.keyboard(0).await).send().await?;
+///
+/// Dispatcher::new(cx.bot.clone())
+///     .callback_queries_handler(move |rx: DispatcherHandlerRx<CallbackQuery>| {
+///         rx.paginated(Arc::clone(&paginator)).for_each(|selection| async move {
+///             match selection {
+///                 Selection::Selected(cx, fruit) => {
+///                     cx.bot
+///                         .answer_callback_query(cx.update.id)
+///                         .text(format!("You chose {}", fruit))
+///                         .send()
+///                         .await
+///                         .log_on_error()
+///                         .await;
+///                 }
+///                 // Queries of other keyboards.
+///                 Selection::Other(_) => {}
+///             }
+///         })
+///     })
+///     .dispatch()
+///     .await;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`Paginator::handle`]: crate::dispatching::widgets::Paginator::handle
+/// [`DispatcherHandlerRxExt::paginated`]: crate::dispatching::DispatcherHandlerRxExt::paginated
+pub struct Paginator<T> {
+    id: String,
+    source: Source<T>,
+    label: Label<T>,
+    key: Option<Label<T>>,
+    page_size: usize,
+    columns: usize,
+    prev_text: String,
+    next_text: String,
+}
+
+impl<T> Paginator<T>
+where
+    T: Clone + Send + 'static,
+{
+    /// Creates a paginator of `items` labelled by `label`.
+    ///
+    /// `id` is a prefix of callback data of this paginator, so it must be
+    /// unique among your keyboards and short (callback data is limited to 64
+    /// bytes).
+    #[must_use]
+    pub fn new<N, I, F>(id: N, items: I, label: F) -> Self
+    where
+        N: Into<String>,
+        I: IntoIterator<Item = T>,
+        F: Fn(&T) -> String + Send + Sync + 'static,
+    {
+        Self::with_source(id.into(), Source::Items(items.into_iter().collect()), Box::new(label))
+    }
+
+    /// Creates a paginator of items returned by `loader`, labelled by `label`.
+    ///
+    /// `loader` is called with the number of a page (starting from 0) and the
+    /// page size every time a page is shown or an item is selected, so it can
+    /// query a database instead of keeping all the items in memory.
+    #[must_use]
+    pub fn with_loader<N, L, Fut, F>(id: N, loader: L, label: F) -> Self
+    where
+        N: Into<String>,
+        L: Fn(usize, usize) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Page<T>> + Send + 'static,
+        F: Fn(&T) -> String + Send + Sync + 'static,
+    {
+        let loader: Loader<T> = Box::new(move |page, page_size| Box::pin(loader(page, page_size)));
+        Self::with_source(id.into(), Source::Loader(loader), Box::new(label))
+    }
+
+    fn with_source(id: String, source: Source<T>, label: Label<T>) -> Self {
+        Self {
+            id,
+            source,
+            label,
+            key: None,
+            page_size: DEFAULT_PAGE_SIZE,
+            columns: 1,
+            prev_text: DEFAULT_PREV_TEXT.to_owned(),
+            next_text: DEFAULT_NEXT_TEXT.to_owned(),
+        }
+    }
+
+    /// The number of items on a page (the default is 10).
+    ///
+    /// # Panics
+    /// If `page_size` is 0.
+    #[must_use]
+    pub fn page_size(mut self, page_size: usize) -> Self {
+        assert!(page_size > 0, "page size must be positive");
+        self.page_size = page_size;
+        self
+    }
+
+    /// The number of item buttons in a row (the default is 1).
+    ///
+    /// # Panics
+    /// If `columns` is 0.
+    #[must_use]
+    pub fn columns(mut self, columns: usize) -> Self {
+        assert!(columns > 0, "number of columns must be positive");
+        self.columns = columns;
+        self
+    }
+
+    /// Identifies items in callback data by `key` instead of their positions
+    /// on a page.
+    ///
+    /// By default, a selected item is found by its position on a freshly
+    /// loaded page, so if items are added or removed between showing a
+    /// keyboard and pressing its button, a neighbouring item is selected.
+    /// With `key`, a button selects only the item it was shown for, or shows
+    /// the page again if the item has gone. Keys must be unique and short
+    /// (callback data is limited to 64 bytes).
+    #[must_use]
+    pub fn key<F>(mut self, key: F) -> Self
+    where
+        F: Fn(&T) -> String + Send + Sync + 'static,
+    {
+        self.key = Some(Box::new(key));
+        self
+    }
+
+    /// A text of the button showing the previous page.
+    #[must_use]
+    pub fn prev_text<S>(mut self, text: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.prev_text = text.into();
+        self
+    }
+
+    /// A text of the button showing the next page.
+    #[must_use]
+    pub fn next_text<S>(mut self, text: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.next_text = text.into();
+        self
+    }
+
+    async fn load(&self, page: usize) -> Page<T> {
+        match &self.source {
+            Source::Items(items) => {
+                let start = page.saturating_mul(self.page_size).min(items.len());
+                let end = (start + self.page_size).min(items.len());
+                Page { items: items[start..end].to_vec(), total: items.len() }
+            }
+            Source::Loader(loader) => loader(page, self.page_size).await,
+        }
+    }
+
+    /// Renders the page with the specified number (starting from 0).
+    ///
+    /// If there are less pages (e.g. some items were removed), the last page
+    /// is rendered.
+    pub async fn keyboard(&self, page: usize) -> InlineKeyboardMarkup {
+        // Offsets of items on the page must fit into `usize`.
+        let mut page_number = page.min(usize::MAX / self.page_size);
+        let mut page = self.load(page_number).await;
+
+        let pages = (page.total.max(1) - 1) / self.page_size + 1;
+        if page_number >= pages {
+            page_number = pages - 1;
+            page = self.load(page_number).await;
+        }
+
+        let buttons: Vec<_> = page
+            .items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                let key = match &self.key {
+                    Some(key) => key(item),
+                    None => index.to_string(),
+                };
+                let data = format!("{}:s{}.{}", self.id, page_number, key);
+                InlineKeyboardButton::callback((self.label)(item), data)
+            })
+            .collect();
+        let mut keyboard = InlineKeyboardMarkup::new(
+            buttons.chunks(self.columns).map(<[_]>::to_vec).collect::<Vec<_>>(),
+        );
+
+        if pages > 1 {
+            let mut navigation = Vec::with_capacity(3);
+            if page_number > 0 {
+                let data = format!("{}:p{}", self.id, page_number - 1);
+                navigation.push(InlineKeyboardButton::callback(self.prev_text.clone(), data));
+            }
+            navigation.push(InlineKeyboardButton::callback(
+                format!("{}/{}", page_number + 1, pages),
+                format!("{}:c", self.id),
+            ));
+            if page_number + 1 < pages {
+                let data = format!("{}:p{}", self.id, page_number + 1);
+                navigation.push(InlineKeyboardButton::callback(self.next_text.clone(), data));
+            }
+            keyboard = keyboard.append_row(navigation);
+        }
+
+        keyboard
+    }
+
+    /// Handles a callback query.
+    ///
+    /// Returns a selected item or a query, which doesn't belong to this
+    /// paginator. Navigation buttons edit the keyboard in place and are
+    /// answered.
+    pub async fn handle(&self, cx: UpdateWithCx<CallbackQuery>) -> Option<Selection<T>> {
+        let data = match cx.update.data.as_deref().and_then(|data| strip_id(data, &self.id)) {
+            Some(data) => data.to_owned(),
+            None => return Some(Selection::Other(cx)),
+        };
+
+        if let Some(selected) = data.strip_prefix('s') {
+            let mut parts = selected.splitn(2, '.');
+            let page = parts.next().and_then(|page| self.parse_page(page));
+            if let (Some(page), Some(key)) = (page, parts.next()) {
+                if let Some(item) = self.find(page, key).await {
+                    return Some(Selection::Selected(cx, item));
+                }
+            }
+
+            // The item has gone, so the keyboard is outdated.
+            self.show_page(&cx, page).await;
+        } else if let Some(page) = data.strip_prefix('p') {
+            self.show_page(&cx, self.parse_page(page)).await;
+        }
+
+        if let Err(error) = cx.bot.answer_callback_query(cx.update.id.clone()).send().await {
+            log::error!("Cannot answer a callback query of a paginator: {}", error);
+        }
+        None
+    }
+
+    /// Parses a number of a page from callback data, which may be crafted, so
+    /// numbers overflowing offsets of items are rejected.
+    fn parse_page(&self, page: &str) -> Option<usize> {
+        page.parse::<usize>().ok().filter(|page| page.checked_mul(self.page_size).is_some())
+    }
+
+    /// Finds an item by a key from callback data on the specified page.
+    async fn find(&self, page: usize, key: &str) -> Option<T> {
+        let items = self.load(page).await.items;
+        match &self.key {
+            Some(item_key) => items.into_iter().find(|item| item_key(item) == key),
+            None => items.into_iter().nth(key.parse().ok()?),
+        }
+    }
+
+    async fn show_page(&self, cx: &UpdateWithCx<CallbackQuery>, page: Option<usize>) {
+        let (page, message) = match (page, query_message(&cx.update)) {
+            (Some(page), Some(message)) => (page, message),
+            _ => return,
+        };
+
+        let keyboard = self.keyboard(page).await;
+        if let Err(error) =
+            cx.bot.edit_message_reply_markup(message).reply_markup(keyboard).send().await
+        {
+            log::error!("Cannot show a page of a paginator: {}", error);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dispatching::widgets::tests::{query, texts},
+        types::InlineKeyboardButtonKind,
+    };
+
+    #[tokio::test]
+    async fn pages() {
+        let paginator = Paginator::new("n", 1..=5, ToString::to_string).page_size(2).columns(2);
+
+        assert_eq!(texts(&paginator.keyboard(0).await), vec!["1|2", "1/3|Next ›"]);
+        assert_eq!(texts(&paginator.keyboard(1).await), vec!["3|4", "‹ Prev|2/3|Next ›"]);
+        assert_eq!(texts(&paginator.keyboard(7).await), vec!["5", "‹ Prev|3/3"]);
+
+        let keyboard = paginator.keyboard(1).await;
+        let data: Vec<_> = keyboard.inline_keyboard.concat().into_iter().map(|b| b.kind).collect();
+        assert_eq!(
+            data,
+            ["n:s1.0", "n:s1.1", "n:p0", "n:c", "n:p2"]
+                .iter()
+                .map(|data| InlineKeyboardButtonKind::CallbackData(data.to_string()))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn single_page() {
+        let paginator = Paginator::new("n", vec!["a"], ToString::to_string);
+        assert_eq!(texts(&paginator.keyboard(0).await), vec!["a"]);
+    }
+
+    #[tokio::test]
+    async fn loader() {
+        let paginator = Paginator::with_loader(
+            "n",
+            |page, page_size| async move {
+                let start = page * page_size;
+                Page { items: (start..(start + page_size).min(25)).collect(), total: 25 }
+            },
+            |n: &usize| n.to_string(),
+        );
+
+        assert_eq!(texts(&paginator.keyboard(2).await)[4..], ["24", "‹ Prev|3/3"]);
+    }
+
+    #[tokio::test]
+    async fn selection() {
+        let paginator = Paginator::new("n", vec!["a", "b", "c"], ToString::to_string).page_size(2);
+        let keyed = Paginator::new("k", vec!["a", "b", "c"], ToString::to_string)
+            .page_size(2)
+            .key(ToString::to_string);
+
+        let selected = paginator.handle(query("n:s1.0")).await.and_then(Selection::selected);
+        assert_eq!(selected.map(|(_, item)| item), Some("c"));
+        let selected = keyed.handle(query("k:s1.c")).await.and_then(Selection::selected);
+        assert_eq!(selected.map(|(_, item)| item), Some("c"));
+        assert_eq!(
+            keyed.keyboard(1).await.inline_keyboard[0][0].kind,
+            InlineKeyboardButtonKind::CallbackData("k:s1.c".to_owned())
+        );
+
+        let other = paginator.handle(query("k:s1.c")).await.and_then(Selection::other);
+        assert_eq!(other.and_then(|cx| cx.update.data).as_deref(), Some("k:s1.c"));
+    }
+
+    #[tokio::test]
+    async fn overflow() {
+        let paginator = Paginator::new("n", 1..=5, ToString::to_string).page_size(2);
+
+        assert_eq!(paginator.parse_page(&usize::MAX.to_string()), None);
+        assert_eq!(paginator.parse_page("2"), Some(2));
+        assert_eq!(texts(&paginator.keyboard(usize::MAX).await)[0], "5");
+    }
+}
@@ -0,0 +1,261 @@
+use super::{query_message, strip_id, Selection};
+use crate::{
+    dispatching::UpdateWithCx,
+    requests::Request,
+    types::{CallbackQuery, InlineKeyboardButton, InlineKeyboardMarkup},
+    utils::keyboard,
+};
+use std::fmt::{self, Display};
+
+/// A time of a day, selected in [`TimePicker`].
+///
+/// [`TimePicker`]: crate::dispatching::widgets::TimePicker
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Time {
+    hour: u32,
+    minute: u32,
+}
+
+impl Time {
+    /// Returns `None`, if there is no such a time.
+    pub fn new(hour: u32, minute: u32) -> Option<Self> {
+        if hour < 24 && minute < 60 {
+            Some(Self { hour, minute })
+        } else {
+            None
+        }
+    }
+
+    /// An hour from 0 to 23.
+    pub fn hour(&self) -> u32 {
+        self.hour
+    }
+
+    /// A minute from 0 to 59.
+    pub fn minute(&self) -> u32 {
+        self.minute
+    }
+}
+
+impl Display for Time {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}", self.hour, self.minute)
+    }
+}
+
+const DEFAULT_STEP: u32 = 15;
+const DEFAULT_BACK_TEXT: &str = "« Back";
+const HOURS_IN_ROW: usize = 6;
+const MINUTES_IN_ROW: usize = 4;
+
+/// An inline keyboard to choose a time of a day.
+///
+/// At first, hours are shown and, after one is pressed, minutes of this hour
+/// with a configurable step. Switching is handled by [`TimePicker::handle`]
+/// (or [`DispatcherHandlerRxExt::times`]), which edits the keyboard in place,
+/// so your code receives only selected times and queries of other keyboards.
+///
+/// See [`Calendar`] for an example of a similar widget.
+///
+/// [`TimePicker::handle`]: crate::dispatching::widgets::TimePicker::handle
+/// [`DispatcherHandlerRxExt::times`]: crate::dispatching::DispatcherHandlerRxExt::times
+/// [`Calendar`]: crate::dispatching::widgets::Calendar
+#[derive(Clone, Debug)]
+pub struct TimePicker {
+    id: String,
+    step: u32,
+    min_time: Option<Time>,
+    max_time: Option<Time>,
+    back_text: String,
+}
+
+impl TimePicker {
+    /// Creates a time picker.
+    ///
+    /// `id` is a prefix of callback data of this time picker, so it must be
+    /// unique among your keyboards and short (callback data is limited to 64
+    /// bytes).
+    #[must_use]
+    pub fn new<N>(id: N) -> Self
+    where
+        N: Into<String>,
+    {
+        Self {
+            id: id.into(),
+            step: DEFAULT_STEP,
+            min_time: None,
+            max_time: None,
+            back_text: DEFAULT_BACK_TEXT.to_owned(),
+        }
+    }
+
+    /// An interval between shown minutes (the default is 15).
+    ///
+    /// # Panics
+    /// If `minutes` is not from 1 to 60.
+    #[must_use]
+    pub fn step(mut self, minutes: u32) -> Self {
+        assert!((1..=60).contains(&minutes), "step must be from 1 to 60 minutes");
+        self.step = minutes;
+        self
+    }
+
+    /// The earliest time, which can be selected.
+    #[must_use]
+    pub fn min_time(mut self, time: Time) -> Self {
+        self.min_time = Some(time);
+        self
+    }
+
+    /// The latest time, which can be selected.
+    #[must_use]
+    pub fn max_time(mut self, time: Time) -> Self {
+        self.max_time = Some(time);
+        self
+    }
+
+    /// A text of the button returning from minutes to hours.
+    #[must_use]
+    pub fn back_text<T>(mut self, text: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.back_text = text.into();
+        self
+    }
+
+    fn is_allowed(&self, time: Time) -> bool {
+        !matches!(self.min_time, Some(min) if time < min)
+            && !matches!(self.max_time, Some(max) if time > max)
+    }
+
+    /// Times of the `hour`, which can be selected.
+    fn times(&self, hour: u32) -> impl Iterator<Item = Time> + '_ {
+        (0..60)
+            .step_by(self.step as usize)
+            .map(move |minute| Time { hour, minute })
+            .filter(move |&time| self.is_allowed(time))
+    }
+
+    /// Renders hours.
+    pub fn keyboard(&self) -> InlineKeyboardMarkup {
+        let buttons = (0..24).map(|hour| {
+            if self.times(hour).next().is_some() {
+                InlineKeyboardButton::callback(
+                    format!("{:02}", hour),
+                    format!("{}:h{}", self.id, hour),
+                )
+            } else {
+                InlineKeyboardButton::callback(" ".to_owned(), format!("{}:n", self.id))
+            }
+        });
+
+        InlineKeyboardMarkup::new(keyboard::rows(buttons, HOURS_IN_ROW))
+    }
+
+    /// Renders minutes of the `hour`.
+    ///
+    /// # Panics
+    /// If `hour` is not from 0 to 23.
+    pub fn minutes_keyboard(&self, hour: u32) -> InlineKeyboardMarkup {
+        assert!(hour < 24, "hour must be from 0 to 23");
+
+        let buttons = self.times(hour).map(|time| {
+            InlineKeyboardButton::callback(
+                time.to_string(),
+                format!("{}:t{}-{}", self.id, time.hour, time.minute),
+            )
+        });
+        let back = InlineKeyboardButton::callback(self.back_text.clone(), format!("{}:b", self.id));
+
+        InlineKeyboardMarkup::new(keyboard::rows(buttons, MINUTES_IN_ROW)).append_row(vec![back])
+    }
+
+    /// Handles a callback query.
+    ///
+    /// Returns a selected time or a query, which doesn't belong to this time
+    /// picker. Buttons switching between hours and minutes edit the keyboard
+    /// in place and are answered.
+    pub async fn handle(&self, cx: UpdateWithCx<CallbackQuery>) -> Option<Selection<Time>> {
+        let data = match cx.update.data.as_deref().and_then(|data| strip_id(data, &self.id)) {
+            Some(data) => data.to_owned(),
+            None => return Some(Selection::Other(cx)),
+        };
+
+        if let Some(time) = data.strip_prefix('t') {
+            let mut parts = time.splitn(2, '-').map(str::parse::<u32>);
+            if let (Some(Ok(hour)), Some(Ok(minute))) = (parts.next(), parts.next()) {
+                if let Some(time) = Time::new(hour, minute).filter(|&time| self.is_allowed(time)) {
+                    return Some(Selection::Selected(cx, time));
+                }
+            }
+        } else if let Some(hour) = data.strip_prefix('h') {
+            if let Some(hour) = hour.parse().ok().filter(|&hour| hour < 24) {
+                self.show(&cx, self.minutes_keyboard(hour)).await;
+            }
+        } else if data == "b" {
+            self.show(&cx, self.keyboard()).await;
+        }
+
+        if let Err(error) = cx.bot.answer_callback_query(cx.update.id.clone()).send().await {
+            log::error!("Cannot answer a callback query of a time picker: {}", error);
+        }
+        None
+    }
+
+    async fn show(&self, cx: &UpdateWithCx<CallbackQuery>, keyboard: InlineKeyboardMarkup) {
+        let message = match query_message(&cx.update) {
+            Some(message) => message,
+            None => return,
+        };
+
+        if let Err(error) =
+            cx.bot.edit_message_reply_markup(message).reply_markup(keyboard).send().await
+        {
+            log::error!("Cannot switch a time picker: {}", error);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dispatching::widgets::{
+        tests::{query, texts},
+        Calendar, Date,
+    };
+
+    #[test]
+    fn hours() {
+        let picker = TimePicker::new("t")
+            .min_time(Time::new(8, 50).unwrap())
+            .max_time(Time::new(17, 0).unwrap());
+
+        assert_eq!(
+            texts(&picker.keyboard()),
+            vec![" | | | | | ", " | | |09|10|11", "12|13|14|15|16|17", " | | | | | "]
+        );
+    }
+
+    #[test]
+    fn minutes() {
+        let picker = TimePicker::new("t").step(20).min_time(Time::new(9, 10).unwrap());
+
+        assert_eq!(texts(&picker.minutes_keyboard(9)), vec!["09:20|09:40", "« Back"]);
+        assert_eq!(texts(&picker.minutes_keyboard(10)), vec!["10:00|10:20|10:40", "« Back"]);
+    }
+
+    #[tokio::test]
+    async fn shared_queries() {
+        let calendar = Calendar::new("c");
+        let picker = TimePicker::new("t");
+
+        let cx = calendar.handle(query("t:t9-30")).await.and_then(Selection::other).unwrap();
+        let time = picker.handle(cx).await.and_then(Selection::selected);
+        assert_eq!(time.map(|(_, time)| time), Time::new(9, 30));
+
+        let cx = picker.handle(query("c:d2021-2-15")).await.and_then(Selection::other).unwrap();
+        let date = calendar.handle(cx).await.and_then(Selection::selected);
+        assert_eq!(date.map(|(_, date)| date), Date::new(2021, 2, 15));
+    }
+}
@@ -3,6 +3,8 @@ use reqwest::StatusCode;
 use serde::Deserialize;
 use thiserror::Error;
 
+use crate::types::MimeWrapper;
+
 /// An error caused by downloading a file.
 #[derive(Debug, Error, From)]
 pub enum DownloadError {
@@ -11,6 +13,22 @@ pub enum DownloadError {
 
     #[error("An I/O error: {0}")]
     Io(#[source] std::io::Error),
+
+    /// Getting information about a file via [`Bot::get_file`] has failed.
+    ///
+    /// [`Bot::get_file`]: crate::Bot::get_file
+    #[error("Cannot get a file: {0}")]
+    RequestError(#[source] RequestError),
+
+    /// A file is larger than the maximum size of a download.
+    #[from(ignore)]
+    #[error("A file of {size} bytes is larger than the maximum of {max_size} bytes")]
+    TooLarge { size: u64, max_size: u64 },
+
+    /// The number of downloaded bytes differs from the size of a file.
+    #[from(ignore)]
+    #[error("Downloaded {downloaded} bytes instead of {expected}")]
+    SizeMismatch { expected: u64, downloaded: u64 },
 }
 
 /// An error caused by sending a request to Telegram.
@@ -34,6 +52,35 @@ pub enum RequestError {
 
     #[error("An error while parsing JSON: {0}")]
     InvalidJson(#[source] serde_json::Error),
+
+    /// Reading a file to be uploaded has failed.
+    #[error("An I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// A file to be uploaded would be rejected by Telegram.
+    ///
+    /// See [`UploadLimits`].
+    ///
+    /// [`UploadLimits`]: crate::requests::UploadLimits
+    #[error("An invalid file: {0}")]
+    InvalidFile(#[from] InvalidFileError),
+}
+
+/// A reason to reject a file before uploading it.
+///
+/// See [`UploadLimits`].
+///
+/// [`UploadLimits`]: crate::requests::UploadLimits
+#[derive(Debug, Error)]
+pub enum InvalidFileError {
+    #[error("The file of {size} bytes is larger than the maximum of {max_size} bytes")]
+    TooLarge { size: u64, max_size: u64 },
+
+    #[error("The file of the type {} is not one of {expected}", mime.0)]
+    WrongType { mime: MimeWrapper, expected: String },
+
+    #[error("The image is {width}x{height}, but must have {expected}")]
+    WrongDimensions { width: u32, height: u32, expected: &'static str },
 }
 
 /// A kind of an API error.
@@ -41,12 +41,12 @@
 #![allow(clippy::match_bool)]
 #![forbid(unsafe_code)]
 
-pub use bot::{Bot, BotBuilder};
+pub use bot::{Bot, BotBuilder, FileDownload};
 pub use dispatching::repls::{
     commands_repl, commands_repl_with_listener, dialogues_repl, dialogues_repl_with_listener, repl,
     repl_with_listener,
 };
-pub use errors::{ApiErrorKind, DownloadError, KnownApiErrorKind, RequestError};
+pub use errors::{ApiErrorKind, DownloadError, InvalidFileError, KnownApiErrorKind, RequestError};
 
 mod errors;
 mod net;
@@ -55,6 +55,8 @@ mod bot;
 pub mod dispatching;
 pub mod error_handlers;
 mod logging;
+#[cfg(feature = "passport")]
+pub mod passport;
 pub mod prelude;
 pub mod requests;
 pub mod types;
@@ -1,3 +1,5 @@
+use bytes::Bytes;
+use futures::Stream;
 use reqwest::Client;
 use tokio::io::{AsyncWrite, AsyncWriteExt};
 
@@ -27,7 +29,6 @@ where
     Ok(())
 }
 
-#[cfg(feature = "unstable-stream")]
 pub async fn download_file_stream(
     client: &Client,
     token: &str,
@@ -1,8 +1,5 @@
-#[cfg(feature = "unstable-stream")]
-pub use download::download_file_stream;
-
 pub use self::{
-    download::download_file,
+    download::{download_file, download_file_stream},
     request::{request_json, request_multipart},
     telegram_response::TelegramResponse,
 };
@@ -0,0 +1,143 @@
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    passport::{crypto, PassportError},
+    types::PassportFile,
+};
+
+/// Decrypted [`EncryptedCredentials`].
+///
+/// [The official docs](https://core.telegram.org/passport#credentials).
+///
+/// [`EncryptedCredentials`]: crate::types::EncryptedCredentials
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Credentials {
+    /// Credentials for encrypted data.
+    pub secure_data: SecureData,
+
+    /// Bot-specified nonce.
+    ///
+    /// **Important:** for security purposes it should be checked that the
+    /// nonce is the same as the one passed to the Telegram Passport request.
+    pub nonce: String,
+}
+
+/// Credentials for all the requested elements.
+///
+/// [The official docs](https://core.telegram.org/passport#securedata).
+#[serde_with_macros::skip_serializing_none]
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct SecureData {
+    pub personal_details: Option<SecureValue>,
+    pub passport: Option<SecureValue>,
+    pub internal_passport: Option<SecureValue>,
+    pub driver_license: Option<SecureValue>,
+    pub identity_card: Option<SecureValue>,
+    pub address: Option<SecureValue>,
+    pub utility_bill: Option<SecureValue>,
+    pub bank_statement: Option<SecureValue>,
+    pub rental_agreement: Option<SecureValue>,
+    pub passport_registration: Option<SecureValue>,
+    pub temporary_registration: Option<SecureValue>,
+}
+
+/// Credentials for an element.
+///
+/// [The official docs](https://core.telegram.org/passport#securevalue).
+#[serde_with_macros::skip_serializing_none]
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct SecureValue {
+    /// Credentials for the encrypted data of an element.
+    pub data: Option<DataCredentials>,
+
+    /// Credentials for the encrypted document's front side.
+    pub front_side: Option<FileCredentials>,
+
+    /// Credentials for the encrypted document's reverse side.
+    pub reverse_side: Option<FileCredentials>,
+
+    /// Credentials for the encrypted selfie of the user with a document.
+    pub selfie: Option<FileCredentials>,
+
+    /// Credentials for the encrypted translation of a document.
+    pub translation: Option<Vec<FileCredentials>>,
+
+    /// Credentials for the encrypted files.
+    pub files: Option<Vec<FileCredentials>>,
+}
+
+/// Credentials for decrypting the `data` field of an element.
+///
+/// [The official docs](https://core.telegram.org/passport#datacredentials).
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct DataCredentials {
+    /// A base64-encoded checksum of the encrypted data.
+    pub data_hash: String,
+
+    /// A base64-encoded secret of the encrypted data.
+    pub secret: String,
+}
+
+impl DataCredentials {
+    /// Decrypts the base64-encoded `data` field of an element and
+    /// deserializes it from JSON.
+    pub fn decrypt<T>(&self, data: &str) -> Result<T, PassportError>
+    where
+        T: DeserializeOwned,
+    {
+        let decrypted = crypto::decrypt_base64(data, &self.secret, &self.data_hash)?;
+        Ok(serde_json::from_slice(&decrypted)?)
+    }
+}
+
+/// Credentials for decrypting a [`PassportFile`].
+///
+/// [The official docs](https://core.telegram.org/passport#filecredentials).
+///
+/// [`PassportFile`]: crate::types::PassportFile
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct FileCredentials {
+    /// A base64-encoded checksum of the encrypted file.
+    pub file_hash: String,
+
+    /// A base64-encoded secret of the encrypted file.
+    pub secret: String,
+}
+
+impl FileCredentials {
+    /// Decrypts the contents of a downloaded file.
+    pub fn decrypt(&self, encrypted: &[u8]) -> Result<Vec<u8>, PassportError> {
+        crypto::decrypt(
+            encrypted,
+            &base64::decode(&self.secret)?,
+            &base64::decode(&self.file_hash)?,
+        )
+    }
+}
+
+/// A [`PassportFile`] with credentials for decrypting it.
+///
+/// [`PassportFile`]: crate::types::PassportFile
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct SecureFile {
+    /// The file to be downloaded.
+    pub file: PassportFile,
+
+    /// Credentials for the file.
+    pub credentials: FileCredentials,
+}
+
+impl SecureFile {
+    /// Decrypts the contents of the downloaded file.
+    ///
+    /// Currently all Telegram Passport files are in JPEG format when
+    /// decrypted.
+    pub fn decrypt(&self, encrypted: &[u8]) -> Result<Vec<u8>, PassportError> {
+        self.credentials.decrypt(encrypted)
+    }
+}
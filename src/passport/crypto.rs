@@ -0,0 +1,104 @@
+use openssl::{
+    hash::{hash, MessageDigest},
+    symm::{Cipher, Crypter, Mode},
+};
+
+use crate::passport::PassportError;
+
+/// Decrypts and authenticates `data` of Telegram Passport.
+///
+/// The padding (its length is the first byte of decrypted data) is removed,
+/// see the [documentation].
+///
+/// [documentation]: https://core.telegram.org/passport#decrypting-data
+pub(crate) fn decrypt(
+    data: &[u8],
+    secret: &[u8],
+    data_hash: &[u8],
+) -> Result<Vec<u8>, PassportError> {
+    let secret_hash = hash(MessageDigest::sha512(), &[secret, data_hash].concat())?;
+    let (key, iv) = (&secret_hash[..32], &secret_hash[32..48]);
+
+    let cipher = Cipher::aes_256_cbc();
+    let mut crypter = Crypter::new(cipher, Mode::Decrypt, key, Some(iv))?;
+    crypter.pad(false);
+    let mut decrypted = vec![0; data.len() + cipher.block_size()];
+    let mut len = crypter.update(data, &mut decrypted)?;
+    len += crypter.finalize(&mut decrypted[len..])?;
+    decrypted.truncate(len);
+
+    if *hash(MessageDigest::sha256(), &decrypted)? != *data_hash {
+        return Err(PassportError::HashMismatch);
+    }
+
+    let padding = usize::from(*decrypted.first().ok_or(PassportError::InvalidPadding)?);
+    if padding < 32 || padding > decrypted.len() {
+        return Err(PassportError::InvalidPadding);
+    }
+    Ok(decrypted.split_off(padding))
+}
+
+/// Decrypts base64-encoded `data` with base64-encoded `secret` and `hash`.
+pub(crate) fn decrypt_base64(
+    data: &str,
+    secret: &str,
+    hash: &str,
+) -> Result<Vec<u8>, PassportError> {
+    decrypt(&base64::decode(data)?, &base64::decode(secret)?, &base64::decode(hash)?)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use openssl::rand::rand_bytes;
+
+    /// Encrypts `data` as Telegram does, returning encrypted data, a secret
+    /// and a hash.
+    pub(crate) fn encrypt(data: &[u8]) -> (Vec<u8>, Vec<u8>, Vec<u8>) {
+        let padding = 32 + (16 - (data.len() + 32) % 16) % 16;
+        let mut padded = vec![0; padding];
+        rand_bytes(&mut padded).unwrap();
+        padded[0] = padding as u8;
+        padded.extend_from_slice(data);
+
+        let data_hash = hash(MessageDigest::sha256(), &padded).unwrap().to_vec();
+        let mut secret = vec![0; 32];
+        rand_bytes(&mut secret).unwrap();
+        let secret_hash =
+            hash(MessageDigest::sha512(), &[&secret[..], &data_hash].concat()).unwrap();
+
+        let cipher = Cipher::aes_256_cbc();
+        let mut crypter =
+            Crypter::new(cipher, Mode::Encrypt, &secret_hash[..32], Some(&secret_hash[32..48]))
+                .unwrap();
+        crypter.pad(false);
+        let mut encrypted = vec![0; padded.len() + cipher.block_size()];
+        let mut len = crypter.update(&padded, &mut encrypted).unwrap();
+        len += crypter.finalize(&mut encrypted[len..]).unwrap();
+        encrypted.truncate(len);
+
+        (encrypted, secret, data_hash)
+    }
+
+    #[test]
+    fn known_vector() {
+        let data = "1G6/KDppRUtRz56K2ilCJ7kVklAexlFedeNLDje8PG5jn3B9LXgGuSw+s3MH+J+kE7EubqJfaz5Dl2QgC7r2IrUe+4KKpqFuO8Sv0CAjelhMF2Wa2uxc/GJcCL97mZO5";
+        let secret = "AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8=";
+        let hash = "SNf8PkzFA/86O9ljTxQI7bROIxTY5C9i6UXuPhd+UJE=";
+
+        assert_eq!(
+            decrypt_base64(data, secret, hash).unwrap(),
+            br#"{"document_no":"AB123","expiry_date":"01.01.2030"}"#
+        );
+        assert!(matches!(
+            decrypt_base64(data, secret, "AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8="),
+            Err(PassportError::HashMismatch)
+        ));
+    }
+
+    #[test]
+    fn round_trip() {
+        let (encrypted, secret, data_hash) = encrypt(b"file contents");
+        assert_eq!(decrypt(&encrypted, &secret, &data_hash).unwrap(), b"file contents");
+    }
+}
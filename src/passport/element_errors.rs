@@ -0,0 +1,375 @@
+use crate::{
+    passport::{
+        DecryptedPassportElement, DecryptedPassportElementKind, PassportElementType, SecureFile,
+    },
+    types::{
+        PassportElementError, PassportElementErrorDataField, PassportElementErrorDataFieldType,
+        PassportElementErrorFile, PassportElementErrorFileType, PassportElementErrorFiles,
+        PassportElementErrorFilesType, PassportElementErrorFrontSide,
+        PassportElementErrorFrontSideType, PassportElementErrorKind,
+        PassportElementErrorReverseSide, PassportElementErrorReverseSideType,
+        PassportElementErrorSelfie, PassportElementErrorSelfieType,
+        PassportElementErrorTranslationFile, PassportElementErrorTranslationFileType,
+        PassportElementErrorTranslationFiles, PassportElementErrorTranslationFilesType,
+        PassportElementErrorUnspecified, PassportElementErrorUnspecifiedType,
+    },
+};
+
+/// Building [`PassportElementError`]s of an element, to be sent with
+/// [`Bot::set_passport_data_errors`].
+///
+/// Methods return `None` if an element has no such part.
+///
+/// [`PassportElementError`]: crate::types::PassportElementError
+/// [`Bot::set_passport_data_errors`]: crate::Bot::set_passport_data_errors
+impl DecryptedPassportElement {
+    /// An error in the field `field_name` of the element's data (e.g.
+    /// `"birth_date"` of [`PersonalDetails`]).
+    ///
+    /// [`PersonalDetails`]: crate::passport::PersonalDetails
+    pub fn data_error<F, M>(&self, field_name: F, message: M) -> Option<PassportElementError>
+    where
+        F: Into<String>,
+        M: Into<String>,
+    {
+        use PassportElementErrorDataFieldType as Type;
+
+        let r#type = match self.kind.element_type() {
+            PassportElementType::PersonalDetails => Type::PersonalDetails,
+            PassportElementType::Passport => Type::Passport,
+            PassportElementType::DriverLicense => Type::DriverLicense,
+            PassportElementType::IdentityCard => Type::IdentityCard,
+            PassportElementType::InternalPassport => Type::InternalPassport,
+            PassportElementType::Address => Type::Address,
+            _ => return None,
+        };
+        let data_hash = self.data_hash.clone()?;
+        Some(PassportElementError::new(
+            message,
+            PassportElementErrorKind::DataField(PassportElementErrorDataField::new(
+                r#type, field_name, data_hash,
+            )),
+        ))
+    }
+
+    /// An error in `file` of the element: its front side, reverse side,
+    /// selfie, one of its files or translation files.
+    pub fn file_error<M>(&self, file: &SecureFile, message: M) -> Option<PassportElementError>
+    where
+        M: Into<String>,
+    {
+        let element_type = self.kind.element_type();
+        let file_hash = file.credentials.file_hash.clone();
+        let kind = match &self.kind {
+            DecryptedPassportElementKind::Passport(document)
+            | DecryptedPassportElementKind::DriverLicense(document)
+            | DecryptedPassportElementKind::IdentityCard(document)
+            | DecryptedPassportElementKind::InternalPassport(document) => {
+                if document.front_side == *file {
+                    PassportElementErrorKind::FrontSide(PassportElementErrorFrontSide::new(
+                        front_side_type(element_type)?,
+                        file_hash,
+                    ))
+                } else if document.reverse_side.as_ref() == Some(file) {
+                    PassportElementErrorKind::ReverseSide(PassportElementErrorReverseSide::new(
+                        reverse_side_type(element_type)?,
+                        file_hash,
+                    ))
+                } else if document.selfie.as_ref() == Some(file) {
+                    PassportElementErrorKind::Selfie(PassportElementErrorSelfie::new(
+                        selfie_type(element_type)?,
+                        file_hash,
+                    ))
+                } else if document.translation.contains(file) {
+                    translation_file(element_type, file_hash)?
+                } else {
+                    return None;
+                }
+            }
+            DecryptedPassportElementKind::UtilityBill(documents)
+            | DecryptedPassportElementKind::BankStatement(documents)
+            | DecryptedPassportElementKind::RentalAgreement(documents)
+            | DecryptedPassportElementKind::PassportRegistration(documents)
+            | DecryptedPassportElementKind::TemporaryRegistration(documents) => {
+                if documents.files.contains(file) {
+                    PassportElementErrorKind::File(PassportElementErrorFile::new(
+                        file_type(element_type)?,
+                        file_hash,
+                    ))
+                } else if documents.translation.contains(file) {
+                    translation_file(element_type, file_hash)?
+                } else {
+                    return None;
+                }
+            }
+            _ => return None,
+        };
+        Some(PassportElementError::new(message, kind))
+    }
+
+    /// An error in all the files of the element (e.g. a bill is not
+    /// recent).
+    pub fn files_error<M>(&self, message: M) -> Option<PassportElementError>
+    where
+        M: Into<String>,
+    {
+        let files = match &self.kind {
+            DecryptedPassportElementKind::UtilityBill(documents)
+            | DecryptedPassportElementKind::BankStatement(documents)
+            | DecryptedPassportElementKind::RentalAgreement(documents)
+            | DecryptedPassportElementKind::PassportRegistration(documents)
+            | DecryptedPassportElementKind::TemporaryRegistration(documents) => &documents.files,
+            _ => return None,
+        };
+        Some(PassportElementError::new(
+            message,
+            PassportElementErrorKind::Files(PassportElementErrorFiles::new(
+                file_type(self.kind.element_type()).map(files_type)?,
+                file_hashes(files),
+            )),
+        ))
+    }
+
+    /// An error in all the translation files of the element.
+    pub fn translation_error<M>(&self, message: M) -> Option<PassportElementError>
+    where
+        M: Into<String>,
+    {
+        let translation = match &self.kind {
+            DecryptedPassportElementKind::Passport(document)
+            | DecryptedPassportElementKind::DriverLicense(document)
+            | DecryptedPassportElementKind::IdentityCard(document)
+            | DecryptedPassportElementKind::InternalPassport(document) => &document.translation,
+            DecryptedPassportElementKind::UtilityBill(documents)
+            | DecryptedPassportElementKind::BankStatement(documents)
+            | DecryptedPassportElementKind::RentalAgreement(documents)
+            | DecryptedPassportElementKind::PassportRegistration(documents)
+            | DecryptedPassportElementKind::TemporaryRegistration(documents) => {
+                &documents.translation
+            }
+            _ => return None,
+        };
+        if translation.is_empty() {
+            return None;
+        }
+        Some(PassportElementError::new(
+            message,
+            PassportElementErrorKind::TranslationFiles(PassportElementErrorTranslationFiles::new(
+                translation_files_type(self.kind.element_type())?,
+                file_hashes(translation),
+            )),
+        ))
+    }
+
+    /// An error in the element, which doesn't fit into other methods.
+    pub fn unspecified_error<M>(&self, message: M) -> PassportElementError
+    where
+        M: Into<String>,
+    {
+        use PassportElementErrorUnspecifiedType as Type;
+
+        let r#type = match self.kind.element_type() {
+            PassportElementType::PersonalDetails => Type::PersonalDetails,
+            PassportElementType::Passport => Type::Passport,
+            PassportElementType::DriverLicense => Type::DriverLicense,
+            PassportElementType::IdentityCard => Type::IdentityCard,
+            PassportElementType::InternalPassport => Type::InternalPassport,
+            PassportElementType::Address => Type::Address,
+            PassportElementType::UtilityBill => Type::UtilityBill,
+            PassportElementType::BankStatement => Type::BankStatement,
+            PassportElementType::RentalAgreement => Type::RentalAgreement,
+            PassportElementType::PassportRegistration => Type::PassportRegistration,
+            PassportElementType::TemporaryRegistration => Type::TemporaryRegistration,
+            PassportElementType::PhoneNumber => Type::PhoneNumber,
+            PassportElementType::Email => Type::Email,
+        };
+        PassportElementError::new(
+            message,
+            PassportElementErrorKind::Unspecified(PassportElementErrorUnspecified::new(
+                r#type,
+                self.hash.clone(),
+            )),
+        )
+    }
+}
+
+fn file_hashes(files: &[SecureFile]) -> Vec<String> {
+    files.iter().map(|file| file.credentials.file_hash.clone()).collect()
+}
+
+fn front_side_type(r#type: PassportElementType) -> Option<PassportElementErrorFrontSideType> {
+    use PassportElementErrorFrontSideType as Type;
+
+    match r#type {
+        PassportElementType::Passport => Some(Type::Passport),
+        PassportElementType::DriverLicense => Some(Type::DriverLicense),
+        PassportElementType::IdentityCard => Some(Type::IdentityCard),
+        PassportElementType::InternalPassport => Some(Type::InternalPassport),
+        _ => None,
+    }
+}
+
+fn reverse_side_type(r#type: PassportElementType) -> Option<PassportElementErrorReverseSideType> {
+    use PassportElementErrorReverseSideType as Type;
+
+    match r#type {
+        PassportElementType::DriverLicense => Some(Type::DriverLicense),
+        PassportElementType::IdentityCard => Some(Type::IdentityCard),
+        _ => None,
+    }
+}
+
+fn selfie_type(r#type: PassportElementType) -> Option<PassportElementErrorSelfieType> {
+    use PassportElementErrorSelfieType as Type;
+
+    match r#type {
+        PassportElementType::Passport => Some(Type::Passport),
+        PassportElementType::DriverLicense => Some(Type::DriverLicense),
+        PassportElementType::IdentityCard => Some(Type::IdentityCard),
+        PassportElementType::InternalPassport => Some(Type::InternalPassport),
+        _ => None,
+    }
+}
+
+fn file_type(r#type: PassportElementType) -> Option<PassportElementErrorFileType> {
+    use PassportElementErrorFileType as Type;
+
+    match r#type {
+        PassportElementType::UtilityBill => Some(Type::UtilityBill),
+        PassportElementType::BankStatement => Some(Type::BankStatement),
+        PassportElementType::RentalAgreement => Some(Type::RentalAgreement),
+        PassportElementType::PassportRegistration => Some(Type::PassportRegistration),
+        PassportElementType::TemporaryRegistration => Some(Type::TemporaryRegistration),
+        _ => None,
+    }
+}
+
+fn files_type(r#type: PassportElementErrorFileType) -> PassportElementErrorFilesType {
+    use PassportElementErrorFilesType as Type;
+
+    match r#type {
+        PassportElementErrorFileType::UtilityBill => Type::UtilityBill,
+        PassportElementErrorFileType::BankStatement => Type::BankStatement,
+        PassportElementErrorFileType::RentalAgreement => Type::RentalAgreement,
+        PassportElementErrorFileType::PassportRegistration => Type::PassportRegistration,
+        PassportElementErrorFileType::TemporaryRegistration => Type::TemporaryRegistration,
+    }
+}
+
+fn translation_files_type(
+    r#type: PassportElementType,
+) -> Option<PassportElementErrorTranslationFilesType> {
+    use PassportElementErrorTranslationFilesType as Type;
+
+    match r#type {
+        PassportElementType::Passport => Some(Type::Passport),
+        PassportElementType::DriverLicense => Some(Type::DriverLicense),
+        PassportElementType::IdentityCard => Some(Type::IdentityCard),
+        PassportElementType::InternalPassport => Some(Type::InternalPassport),
+        PassportElementType::UtilityBill => Some(Type::UtilityBill),
+        PassportElementType::BankStatement => Some(Type::BankStatement),
+        PassportElementType::RentalAgreement => Some(Type::RentalAgreement),
+        PassportElementType::PassportRegistration => Some(Type::PassportRegistration),
+        PassportElementType::TemporaryRegistration => Some(Type::TemporaryRegistration),
+        _ => None,
+    }
+}
+
+fn translation_file(
+    r#type: PassportElementType,
+    file_hash: String,
+) -> Option<PassportElementErrorKind> {
+    use PassportElementErrorTranslationFileType as Type;
+
+    let r#type = match translation_files_type(r#type)? {
+        PassportElementErrorTranslationFilesType::Passport => Type::Passport,
+        PassportElementErrorTranslationFilesType::DriverLicense => Type::DriverLicense,
+        PassportElementErrorTranslationFilesType::IdentityCard => Type::IdentityCard,
+        PassportElementErrorTranslationFilesType::InternalPassport => Type::InternalPassport,
+        PassportElementErrorTranslationFilesType::UtilityBill => Type::UtilityBill,
+        PassportElementErrorTranslationFilesType::BankStatement => Type::BankStatement,
+        PassportElementErrorTranslationFilesType::RentalAgreement => Type::RentalAgreement,
+        PassportElementErrorTranslationFilesType::PassportRegistration => {
+            Type::PassportRegistration
+        }
+        PassportElementErrorTranslationFilesType::TemporaryRegistration => {
+            Type::TemporaryRegistration
+        }
+    };
+    Some(PassportElementErrorKind::TranslationFile(PassportElementErrorTranslationFile::new(
+        r#type, file_hash,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        passport::{FileCredentials, IdDocument, IdDocumentData},
+        types::PassportFile,
+    };
+
+    fn secure_file(hash: &str) -> SecureFile {
+        let credentials: FileCredentials =
+            serde_json::from_str(&format!(r#"{{"file_hash":"{}","secret":"s"}}"#, hash)).unwrap();
+        SecureFile { file: PassportFile::new(hash, hash, 1, 0), credentials }
+    }
+
+    #[test]
+    fn errors() {
+        let data: IdDocumentData = serde_json::from_str(r#"{"document_no":"1"}"#).unwrap();
+        let element = DecryptedPassportElement {
+            hash: "element".to_owned(),
+            data_hash: Some("data".to_owned()),
+            kind: DecryptedPassportElementKind::DriverLicense(IdDocument {
+                data,
+                front_side: secure_file("front"),
+                reverse_side: Some(secure_file("reverse")),
+                selfie: Some(secure_file("selfie")),
+                translation: vec![secure_file("translation")],
+            }),
+        };
+
+        let to_json =
+            |error: Option<PassportElementError>| serde_json::to_value(error.unwrap()).unwrap();
+        assert_eq!(
+            to_json(element.data_error("document_no", "Invalid")),
+            serde_json::json!({
+                "message": "Invalid",
+                "source": "data",
+                "type": "driver_license",
+                "field_name": "document_no",
+                "data_hash": "data"
+            })
+        );
+        assert_eq!(
+            to_json(element.file_error(&secure_file("reverse"), "Blurry")),
+            serde_json::json!({
+                "message": "Blurry",
+                "source": "reverse_side",
+                "type": "driver_license",
+                "file_hash": "reverse"
+            })
+        );
+        assert_eq!(
+            to_json(element.file_error(&secure_file("translation"), "Blurry")),
+            serde_json::json!({
+                "message": "Blurry",
+                "source": "translation_file",
+                "type": "driver_license",
+                "file_hash": "translation"
+            })
+        );
+        assert_eq!(
+            to_json(element.translation_error("Wrong")),
+            serde_json::json!({
+                "message": "Wrong",
+                "source": "translation_files",
+                "type": "driver_license",
+                "file_hashes": ["translation"]
+            })
+        );
+        assert_eq!(element.file_error(&secure_file("other"), "Blurry"), None);
+        assert_eq!(element.files_error("Old"), None);
+    }
+}
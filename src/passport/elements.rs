@@ -0,0 +1,407 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    passport::{
+        DataCredentials, FileCredentials, PassportElementType, PassportError, SecureData,
+        SecureFile, SecureValue,
+    },
+    types::{EncryptedPassportElement, EncryptedPassportElementKind, PassportFile},
+};
+
+/// Decrypted [`PassportData`].
+///
+/// [`PassportData`]: crate::types::PassportData
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct DecryptedPassportData {
+    /// Decrypted elements in the order of [`PassportData::data`].
+    ///
+    /// [`PassportData::data`]: crate::types::PassportData::data
+    pub elements: Vec<DecryptedPassportElement>,
+
+    /// Bot-specified nonce.
+    ///
+    /// **Important:** for security purposes it should be checked that the
+    /// nonce is the same as the one passed to the Telegram Passport request.
+    pub nonce: String,
+}
+
+/// A decrypted [`EncryptedPassportElement`].
+///
+/// [`EncryptedPassportElement`]: crate::types::EncryptedPassportElement
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct DecryptedPassportElement {
+    /// Base64-encoded element hash for using in
+    /// [`PassportElementErrorKind::Unspecified`].
+    ///
+    /// [`PassportElementErrorKind::Unspecified`]:
+    /// crate::types::PassportElementErrorKind::Unspecified
+    pub hash: String,
+
+    /// Base64-encoded hash of the element's `data` for using in
+    /// [`PassportElementErrorKind::DataField`].
+    ///
+    /// [`PassportElementErrorKind::DataField`]:
+    /// crate::types::PassportElementErrorKind::DataField
+    pub data_hash: Option<String>,
+
+    pub kind: DecryptedPassportElementKind,
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum DecryptedPassportElementKind {
+    PersonalDetails(PersonalDetails),
+    Passport(IdDocument),
+    DriverLicense(IdDocument),
+    IdentityCard(IdDocument),
+    InternalPassport(IdDocument),
+    Address(ResidentialAddress),
+    UtilityBill(Documents),
+    BankStatement(Documents),
+    RentalAgreement(Documents),
+    PassportRegistration(Documents),
+    TemporaryRegistration(Documents),
+    PhoneNumber(String),
+    Email(String),
+}
+
+impl DecryptedPassportElementKind {
+    #[must_use]
+    pub fn element_type(&self) -> PassportElementType {
+        match self {
+            Self::PersonalDetails(_) => PassportElementType::PersonalDetails,
+            Self::Passport(_) => PassportElementType::Passport,
+            Self::DriverLicense(_) => PassportElementType::DriverLicense,
+            Self::IdentityCard(_) => PassportElementType::IdentityCard,
+            Self::InternalPassport(_) => PassportElementType::InternalPassport,
+            Self::Address(_) => PassportElementType::Address,
+            Self::UtilityBill(_) => PassportElementType::UtilityBill,
+            Self::BankStatement(_) => PassportElementType::BankStatement,
+            Self::RentalAgreement(_) => PassportElementType::RentalAgreement,
+            Self::PassportRegistration(_) => PassportElementType::PassportRegistration,
+            Self::TemporaryRegistration(_) => PassportElementType::TemporaryRegistration,
+            Self::PhoneNumber(_) => PassportElementType::PhoneNumber,
+            Self::Email(_) => PassportElementType::Email,
+        }
+    }
+}
+
+/// Personal details of a user.
+///
+/// [The official docs](https://core.telegram.org/passport#personaldetails).
+#[serde_with_macros::skip_serializing_none]
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct PersonalDetails {
+    /// First Name.
+    pub first_name: String,
+
+    /// Last Name.
+    pub last_name: String,
+
+    /// Middle Name.
+    pub middle_name: Option<String>,
+
+    /// Date of birth in the DD.MM.YYYY format.
+    pub birth_date: String,
+
+    /// Gender, `male` or `female`.
+    pub gender: String,
+
+    /// Citizenship (ISO 3166-1 alpha-2 country code).
+    pub country_code: String,
+
+    /// Country of residence (ISO 3166-1 alpha-2 country code).
+    pub residence_country_code: String,
+
+    /// First Name in the language of the user's country of residence.
+    pub first_name_native: Option<String>,
+
+    /// Last Name in the language of the user's country of residence.
+    pub last_name_native: Option<String>,
+
+    /// Middle Name in the language of the user's country of residence.
+    pub middle_name_native: Option<String>,
+}
+
+/// Data of an identity document.
+///
+/// [The official docs](https://core.telegram.org/passport#iddocumentdata).
+#[serde_with_macros::skip_serializing_none]
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct IdDocumentData {
+    /// Document number.
+    pub document_no: String,
+
+    /// Date of expiry, in DD.MM.YYYY format.
+    pub expiry_date: Option<String>,
+}
+
+/// A residential address.
+///
+/// [The official docs](https://core.telegram.org/passport#residentialaddress).
+#[serde_with_macros::skip_serializing_none]
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct ResidentialAddress {
+    /// First line for the address.
+    pub street_line1: String,
+
+    /// Second line for the address.
+    pub street_line2: Option<String>,
+
+    /// City.
+    pub city: String,
+
+    /// State.
+    pub state: Option<String>,
+
+    /// ISO 3166-1 alpha-2 country code.
+    pub country_code: String,
+
+    /// Address post code.
+    pub post_code: String,
+}
+
+/// A decrypted identity document with its scans.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct IdDocument {
+    pub data: IdDocumentData,
+    pub front_side: SecureFile,
+
+    /// Present only for [`DecryptedPassportElementKind::DriverLicense`] and
+    /// [`DecryptedPassportElementKind::IdentityCard`].
+    ///
+    /// [`DecryptedPassportElementKind::DriverLicense`]:
+    /// crate::passport::DecryptedPassportElementKind::DriverLicense
+    /// [`DecryptedPassportElementKind::IdentityCard`]:
+    /// crate::passport::DecryptedPassportElementKind::IdentityCard
+    pub reverse_side: Option<SecureFile>,
+
+    /// Present only if a selfie was requested.
+    pub selfie: Option<SecureFile>,
+    pub translation: Vec<SecureFile>,
+}
+
+/// Scans of a document confirming an address.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Documents {
+    pub files: Vec<SecureFile>,
+    pub translation: Vec<SecureFile>,
+}
+
+/// Decrypts `element` with credentials from `secure_data`.
+pub(crate) fn decrypt_element(
+    secure_data: &SecureData,
+    element: &EncryptedPassportElement,
+) -> Result<DecryptedPassportElement, PassportError> {
+    use DecryptedPassportElementKind as Decrypted;
+    use EncryptedPassportElementKind as Encrypted;
+
+    let (data_hash, kind) = match &element.kind {
+        Encrypted::PersonalDetails(e) => {
+            let value = value(&secure_data.personal_details, "personal_details")?;
+            let credentials = data(value, "personal_details")?;
+            (
+                Some(credentials.data_hash.clone()),
+                Decrypted::PersonalDetails(credentials.decrypt(&e.data)?),
+            )
+        }
+        Encrypted::Passport(e) => {
+            let value = value(&secure_data.passport, "passport")?;
+            let credentials = data(value, "passport")?;
+            let document = IdDocument {
+                data: credentials.decrypt(&e.data)?,
+                front_side: file(&value.front_side, &e.front_side, "passport.front_side")?,
+                reverse_side: None,
+                selfie: optional_file(&value.selfie, e.selfie.as_ref(), "passport.selfie")?,
+                translation: files(
+                    &value.translation,
+                    e.translation.as_deref().unwrap_or_default(),
+                    "passport.translation",
+                )?,
+            };
+            (Some(credentials.data_hash.clone()), Decrypted::Passport(document))
+        }
+        Encrypted::DriverLicense(e) => {
+            let value = value(&secure_data.driver_license, "driver_license")?;
+            let credentials = data(value, "driver_license")?;
+            let document = IdDocument {
+                data: credentials.decrypt(&e.data)?,
+                front_side: file(&value.front_side, &e.front_side, "driver_license.front_side")?,
+                reverse_side: Some(file(
+                    &value.reverse_side,
+                    &e.reverse_side,
+                    "driver_license.reverse_side",
+                )?),
+                selfie: optional_file(&value.selfie, e.selfie.as_ref(), "driver_license.selfie")?,
+                translation: files(
+                    &value.translation,
+                    e.translation.as_deref().unwrap_or_default(),
+                    "driver_license.translation",
+                )?,
+            };
+            (Some(credentials.data_hash.clone()), Decrypted::DriverLicense(document))
+        }
+        Encrypted::IdentityCard(e) => {
+            let value = value(&secure_data.identity_card, "identity_card")?;
+            let credentials = data(value, "identity_card")?;
+            let document = IdDocument {
+                data: credentials.decrypt(&e.data)?,
+                front_side: file(&value.front_side, &e.front_side, "identity_card.front_side")?,
+                reverse_side: Some(file(
+                    &value.reverse_side,
+                    &e.reverse_side,
+                    "identity_card.reverse_side",
+                )?),
+                selfie: optional_file(&value.selfie, e.selfie.as_ref(), "identity_card.selfie")?,
+                translation: files(
+                    &value.translation,
+                    e.translation.as_deref().unwrap_or_default(),
+                    "identity_card.translation",
+                )?,
+            };
+            (Some(credentials.data_hash.clone()), Decrypted::IdentityCard(document))
+        }
+        Encrypted::InternalPassport(e) => {
+            let value = value(&secure_data.internal_passport, "internal_passport")?;
+            let credentials = data(value, "internal_passport")?;
+            let document = IdDocument {
+                data: credentials.decrypt(&e.data)?,
+                front_side: file(&value.front_side, &e.front_side, "internal_passport.front_side")?,
+                reverse_side: None,
+                selfie: optional_file(
+                    &value.selfie,
+                    e.selfie.as_ref(),
+                    "internal_passport.selfie",
+                )?,
+                translation: files(
+                    &value.translation,
+                    e.translation.as_deref().unwrap_or_default(),
+                    "internal_passport.translation",
+                )?,
+            };
+            (Some(credentials.data_hash.clone()), Decrypted::InternalPassport(document))
+        }
+        Encrypted::Address(e) => {
+            let value = value(&secure_data.address, "address")?;
+            let credentials = data(value, "address")?;
+            (Some(credentials.data_hash.clone()), Decrypted::Address(credentials.decrypt(&e.data)?))
+        }
+        Encrypted::UtilityBill(e) => (
+            None,
+            Decrypted::UtilityBill(documents(
+                &secure_data.utility_bill,
+                &e.files,
+                e.translation.as_deref().unwrap_or_default(),
+                "utility_bill",
+            )?),
+        ),
+        Encrypted::BankStatement(e) => (
+            None,
+            Decrypted::BankStatement(documents(
+                &secure_data.bank_statement,
+                &e.files,
+                e.translation.as_deref().unwrap_or_default(),
+                "bank_statement",
+            )?),
+        ),
+        Encrypted::RentalAgreement(e) => (
+            None,
+            Decrypted::RentalAgreement(documents(
+                &secure_data.rental_agreement,
+                &e.files,
+                e.translation.as_deref().unwrap_or_default(),
+                "rental_agreement",
+            )?),
+        ),
+        Encrypted::PassportRegistration(e) => (
+            None,
+            Decrypted::PassportRegistration(documents(
+                &secure_data.passport_registration,
+                &e.files,
+                e.translation.as_deref().unwrap_or_default(),
+                "passport_registration",
+            )?),
+        ),
+        Encrypted::EncryptedPassportElement(e) => (
+            None,
+            Decrypted::TemporaryRegistration(documents(
+                &secure_data.temporary_registration,
+                &e.files,
+                e.translation.as_deref().unwrap_or_default(),
+                "temporary_registration",
+            )?),
+        ),
+        Encrypted::PhoneNumber(e) => (None, Decrypted::PhoneNumber(e.phone_number.clone())),
+        Encrypted::Email(e) => (None, Decrypted::Email(e.email.clone())),
+    };
+
+    Ok(DecryptedPassportElement { hash: element.hash.clone(), data_hash, kind })
+}
+
+fn value<'a>(
+    value: &'a Option<SecureValue>,
+    name: &'static str,
+) -> Result<&'a SecureValue, PassportError> {
+    value.as_ref().ok_or(PassportError::MissingCredentials(name))
+}
+
+fn data<'a>(
+    value: &'a SecureValue,
+    name: &'static str,
+) -> Result<&'a DataCredentials, PassportError> {
+    value.data.as_ref().ok_or(PassportError::MissingCredentials(name))
+}
+
+fn file(
+    credentials: &Option<FileCredentials>,
+    file: &PassportFile,
+    name: &'static str,
+) -> Result<SecureFile, PassportError> {
+    let credentials = credentials.clone().ok_or(PassportError::MissingCredentials(name))?;
+    Ok(SecureFile { file: file.clone(), credentials })
+}
+
+fn optional_file(
+    credentials: &Option<FileCredentials>,
+    file: Option<&PassportFile>,
+    name: &'static str,
+) -> Result<Option<SecureFile>, PassportError> {
+    file.map(|file| self::file(credentials, file, name)).transpose()
+}
+
+fn files(
+    credentials: &Option<Vec<FileCredentials>>,
+    files: &[PassportFile],
+    name: &'static str,
+) -> Result<Vec<SecureFile>, PassportError> {
+    if files.is_empty() {
+        return Ok(Vec::new());
+    }
+    match credentials {
+        Some(credentials) if credentials.len() == files.len() => Ok(files
+            .iter()
+            .zip(credentials)
+            .map(|(file, credentials)| SecureFile {
+                file: file.clone(),
+                credentials: credentials.clone(),
+            })
+            .collect()),
+        _ => Err(PassportError::MissingCredentials(name)),
+    }
+}
+
+fn documents(
+    value: &Option<SecureValue>,
+    scans: &[PassportFile],
+    translation: &[PassportFile],
+    name: &'static str,
+) -> Result<Documents, PassportError> {
+    let value = self::value(value, name)?;
+    Ok(Documents {
+        files: files(&value.files, scans, name)?,
+        translation: files(&value.translation, translation, name)?,
+    })
+}
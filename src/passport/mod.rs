@@ -0,0 +1,319 @@
+//! Requesting and decrypting [Telegram Passport] data.
+//!
+//! A bot requests data with a link built by [`PassportRequest`], which
+//! contains a [`PassportScope`] and a nonce generated by [`PassportNonces`].
+//!
+//! Telegram Passport data shared with a bot ([`PassportData`]) is encrypted
+//! with the bot's public RSA key. Create a [`PassportKey`] from the private
+//! key and decrypt the whole data with [`PassportKey::decrypt`] or only its
+//! credentials with [`PassportKey::decrypt_credentials`]. Files are
+//! downloaded encrypted and are decrypted with [`SecureFile::decrypt`].
+//!
+//! The data is checked by the bot and problems are reported with
+//! [`Bot::set_passport_data_errors`], which errors are built by methods of
+//! [`DecryptedPassportElement`] like [`DecryptedPassportElement::data_error`].
+//!
+//! Requires the `passport` feature.
+//!
+//! # Example
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use teloxide::{
+//!     passport::{
+//!         DecryptedPassportElementKind, PassportElementType, PassportKey, PassportNonces,
+//!         PassportRequest, PassportScope,
+//!     },
+//!     prelude::*,
+//! };
+//!
+//! # async fn run(
+//! #     bot: Bot,
+//! #     message: Message,
+//! # ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+//! let key = PassportKey::from_pem(&std::fs::read("private.key")?)?;
+//! let nonces = PassportNonces::new(Duration::from_secs(60 * 60));
+//!
+//! let scope = PassportScope::new()
+//!     .element(PassportElementType::PersonalDetails)
+//!     .element(PassportElementType::Passport);
+//! let request = PassportRequest::new(123_456, scope, key.public_key_pem()?, nonces.generate()?);
+//! bot.send_message(message.chat_id(), format!("Please share your passport: {}", request.url()))
+//!     .await?;
+//!
+//! // ...
+//!
+//! if let Some(passport_data) = message.passport_data() {
+//!     let passport = key.decrypt(passport_data)?;
+//!     nonces.verify(&passport.nonce)?;
+//!
+//!     let mut errors = Vec::new();
+//!     for element in &passport.elements {
+//!         match &element.kind {
+//!             DecryptedPassportElementKind::PersonalDetails(details) => {
+//!                 if details.birth_date.ends_with("2020") {
+//!                     errors.extend(element.data_error("birth_date", "You are too young"));
+//!                 }
+//!             }
+//!             DecryptedPassportElementKind::Passport(passport) => {
+//!                 let mut encrypted = Vec::new();
+//!                 bot.download_file_by_id(&passport.front_side.file.file_id, &mut encrypted)
+//!                     .send()
+//!                     .await?;
+//!                 let jpeg = passport.front_side.decrypt(&encrypted)?;
+//!                 tokio::fs::write("front_side.jpg", jpeg).await?;
+//!             }
+//!             _ => {}
+//!         }
+//!     }
+//!     if let (Some(user), false) = (message.from(), errors.is_empty()) {
+//!         bot.set_passport_data_errors(user.id, errors).await?;
+//!     }
+//! }
+//! # Ok(()) }
+//! ```
+//!
+//! [Telegram Passport]: https://core.telegram.org/passport
+//! [`PassportData`]: crate::types::PassportData
+//! [`PassportKey`]: crate::passport::PassportKey
+//! [`PassportKey::decrypt`]: crate::passport::PassportKey::decrypt
+//! [`PassportKey::decrypt_credentials`]: crate::passport::PassportKey::decrypt_credentials
+//! [`SecureFile::decrypt`]: crate::passport::SecureFile::decrypt
+//! [`PassportRequest`]: crate::passport::PassportRequest
+//! [`PassportScope`]: crate::passport::PassportScope
+//! [`PassportNonces`]: crate::passport::PassportNonces
+//! [`Bot::set_passport_data_errors`]: crate::Bot::set_passport_data_errors
+//! [`DecryptedPassportElement`]: crate::passport::DecryptedPassportElement
+//! [`DecryptedPassportElement::data_error`]: crate::passport::DecryptedPassportElement::data_error
+
+mod credentials;
+mod crypto;
+mod element_errors;
+mod elements;
+mod nonces;
+mod request;
+
+pub use credentials::{
+    Credentials, DataCredentials, FileCredentials, SecureData, SecureFile, SecureValue,
+};
+pub use elements::{
+    DecryptedPassportData, DecryptedPassportElement, DecryptedPassportElementKind, Documents,
+    IdDocument, IdDocumentData, PersonalDetails, ResidentialAddress,
+};
+pub use nonces::PassportNonces;
+pub use request::{
+    PassportElementType, PassportRequest, PassportScope, PassportScopeElement,
+    PassportScopeElementOne, PassportScopeElementOneOfSeveral,
+};
+
+use std::fmt::{self, Debug};
+
+use openssl::{
+    error::ErrorStack,
+    pkey::Private,
+    rsa::{Padding, Rsa},
+};
+use thiserror::Error;
+
+use crate::types::{EncryptedCredentials, PassportData};
+
+/// An error returned from decrypting Telegram Passport data.
+#[derive(Debug, Error)]
+pub enum PassportError {
+    #[error("A cryptographic error: {0}")]
+    Crypto(#[from] ErrorStack),
+
+    #[error("Invalid base64: {0}")]
+    Base64(#[from] base64::DecodeError),
+
+    #[error("A hash of the decrypted data doesn't match")]
+    HashMismatch,
+
+    #[error("Invalid padding of the decrypted data")]
+    InvalidPadding,
+
+    #[error("Invalid JSON of the decrypted data: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("No credentials for {0}")]
+    MissingCredentials(&'static str),
+
+    #[error("The nonce is unknown, expired or already used")]
+    InvalidNonce,
+}
+
+/// The bot's private RSA key, used to decrypt Telegram Passport data.
+#[derive(Clone)]
+pub struct PassportKey {
+    rsa: Rsa<Private>,
+}
+
+impl PassportKey {
+    /// Reads a PEM-encoded private key.
+    pub fn from_pem(pem: &[u8]) -> Result<Self, PassportError> {
+        Ok(Self::from_rsa(Rsa::private_key_from_pem(pem)?))
+    }
+
+    #[must_use]
+    pub fn from_rsa(rsa: Rsa<Private>) -> Self {
+        Self { rsa }
+    }
+
+    /// The PEM-encoded public key, to be sent in [`PassportRequest`]s.
+    ///
+    /// [`PassportRequest`]: crate::passport::PassportRequest
+    pub fn public_key_pem(&self) -> Result<String, PassportError> {
+        let pem = self.rsa.public_key_to_pem()?;
+        Ok(String::from_utf8(pem).expect("PEM is always ASCII"))
+    }
+
+    /// Decrypts and authenticates credentials of Telegram Passport data.
+    pub fn decrypt_credentials(
+        &self,
+        credentials: &EncryptedCredentials,
+    ) -> Result<Credentials, PassportError> {
+        let encrypted_secret = base64::decode(&credentials.secret)?;
+        let mut secret = vec![0; self.rsa.size() as usize];
+        let len = self.rsa.private_decrypt(&encrypted_secret, &mut secret, Padding::PKCS1_OAEP)?;
+        secret.truncate(len);
+
+        let decrypted = crypto::decrypt(
+            &base64::decode(&credentials.data)?,
+            &secret,
+            &base64::decode(&credentials.hash)?,
+        )?;
+        Ok(serde_json::from_slice(&decrypted)?)
+    }
+
+    /// Decrypts and authenticates all the elements of Telegram Passport data.
+    ///
+    /// Files are not downloaded, but returned with credentials for decrypting
+    /// them.
+    pub fn decrypt(&self, data: &PassportData) -> Result<DecryptedPassportData, PassportError> {
+        let credentials = self.decrypt_credentials(&data.credentials)?;
+        let elements = data
+            .data
+            .iter()
+            .map(|element| elements::decrypt_element(&credentials.secure_data, element))
+            .collect::<Result<_, _>>()?;
+        Ok(DecryptedPassportData { elements, nonce: credentials.nonce })
+    }
+}
+
+impl Debug for PassportKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PassportKey")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{
+        EncryptedPassportElement, EncryptedPassportElementKind, EncryptedPassportElementPassport,
+        EncryptedPassportElementPersonalDetails, EncryptedPassportElementUtilityBill, PassportFile,
+    };
+
+    fn base64_encrypt(data: &[u8]) -> (String, String, String) {
+        let (encrypted, secret, hash) = crypto::tests::encrypt(data);
+        (base64::encode(encrypted), base64::encode(secret), base64::encode(hash))
+    }
+
+    #[test]
+    fn decrypt() {
+        let rsa = Rsa::generate(2048).unwrap();
+
+        let (details, details_secret, details_hash) = base64_encrypt(
+            br#"{
+                "first_name": "Ivan",
+                "last_name": "Ivanov",
+                "birth_date": "01.01.1990",
+                "gender": "male",
+                "country_code": "RU",
+                "residence_country_code": "RU"
+            }"#,
+        );
+        let (document, document_secret, document_hash) =
+            base64_encrypt(br#"{ "document_no": "123" }"#);
+        let (scan, scan_secret, scan_hash) = crypto::tests::encrypt(b"jpeg");
+        let scan_credentials = serde_json::json!({
+            "file_hash": base64::encode(&scan_hash),
+            "secret": base64::encode(&scan_secret)
+        });
+        let credentials = serde_json::json!({
+            "secure_data": {
+                "personal_details": {
+                    "data": { "data_hash": details_hash, "secret": details_secret }
+                },
+                "passport": {
+                    "data": { "data_hash": document_hash, "secret": document_secret },
+                    "front_side": scan_credentials
+                },
+                "utility_bill": { "files": [scan_credentials] }
+            },
+            "nonce": "nonce"
+        });
+        let (data, secret, hash) = crypto::tests::encrypt(credentials.to_string().as_bytes());
+        let mut encrypted_secret = vec![0; rsa.size() as usize];
+        let len = rsa.public_encrypt(&secret, &mut encrypted_secret, Padding::PKCS1_OAEP).unwrap();
+        encrypted_secret.truncate(len);
+
+        let passport_data = PassportData::new(
+            vec![
+                EncryptedPassportElement::new(
+                    "element hash",
+                    EncryptedPassportElementKind::PersonalDetails(
+                        EncryptedPassportElementPersonalDetails::new(details),
+                    ),
+                ),
+                EncryptedPassportElement::new(
+                    "element hash",
+                    EncryptedPassportElementKind::UtilityBill(
+                        EncryptedPassportElementUtilityBill::new(vec![PassportFile::new(
+                            "id",
+                            "unique_id",
+                            4,
+                            0,
+                        )]),
+                    ),
+                ),
+                // A selfie hasn't been requested.
+                EncryptedPassportElement::new(
+                    "element hash",
+                    EncryptedPassportElementKind::Passport(EncryptedPassportElementPassport::new(
+                        document,
+                        PassportFile::new("id", "unique_id", 4, 0),
+                    )),
+                ),
+            ],
+            EncryptedCredentials::new(
+                base64::encode(data),
+                base64::encode(hash),
+                base64::encode(encrypted_secret),
+            ),
+        );
+
+        let decrypted = PassportKey::from_rsa(rsa).decrypt(&passport_data).unwrap();
+        assert_eq!(decrypted.nonce, "nonce");
+        match &decrypted.elements[0].kind {
+            DecryptedPassportElementKind::PersonalDetails(details) => {
+                assert_eq!(details.first_name, "Ivan");
+                assert_eq!(details.middle_name, None);
+            }
+            kind => panic!("Unexpected element: {:?}", kind),
+        }
+        match &decrypted.elements[1].kind {
+            DecryptedPassportElementKind::UtilityBill(bill) => {
+                assert_eq!(bill.files[0].decrypt(&scan).unwrap(), b"jpeg");
+            }
+            kind => panic!("Unexpected element: {:?}", kind),
+        }
+        match &decrypted.elements[2].kind {
+            DecryptedPassportElementKind::Passport(passport) => {
+                assert_eq!(passport.data.document_no, "123");
+                assert_eq!(passport.selfie, None);
+            }
+            kind => panic!("Unexpected element: {:?}", kind),
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use openssl::rand::rand_bytes;
+
+use crate::passport::PassportError;
+
+/// One-time nonces of [`PassportRequest`]s.
+///
+/// A nonce is generated for every request and is accepted only once and only
+/// during `ttl` after it was generated, so Passport data can't be replayed.
+///
+/// [`PassportRequest`]: crate::passport::PassportRequest
+#[derive(Debug)]
+pub struct PassportNonces {
+    nonces: Mutex<HashMap<String, Instant>>,
+    ttl: Duration,
+}
+
+impl PassportNonces {
+    #[must_use]
+    pub fn new(ttl: Duration) -> Self {
+        Self { nonces: Mutex::new(HashMap::new()), ttl }
+    }
+
+    /// Generates and remembers a new random nonce.
+    pub fn generate(&self) -> Result<String, PassportError> {
+        let mut bytes = [0; 16];
+        rand_bytes(&mut bytes)?;
+        let nonce: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+
+        let now = Instant::now();
+        let mut nonces = self.nonces.lock().unwrap();
+        nonces.retain(|_, &mut expires| expires > now);
+        nonces.insert(nonce.clone(), now + self.ttl);
+        Ok(nonce)
+    }
+
+    /// Checks that `nonce` was generated and hasn't expired or been verified
+    /// before.
+    pub fn verify(&self, nonce: &str) -> Result<(), PassportError> {
+        match self.nonces.lock().unwrap().remove(nonce) {
+            Some(expires) if expires > Instant::now() => Ok(()),
+            _ => Err(PassportError::InvalidNonce),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn one_time() {
+        let nonces = PassportNonces::new(Duration::from_secs(60));
+        let nonce = nonces.generate().unwrap();
+
+        assert!(matches!(nonces.verify("unknown"), Err(PassportError::InvalidNonce)));
+        assert!(nonces.verify(&nonce).is_ok());
+        assert!(matches!(nonces.verify(&nonce), Err(PassportError::InvalidNonce)));
+    }
+
+    #[test]
+    fn expired() {
+        let nonces = PassportNonces::new(Duration::from_secs(0));
+        let nonce = nonces.generate().unwrap();
+
+        assert!(matches!(nonces.verify(&nonce), Err(PassportError::InvalidNonce)));
+    }
+}
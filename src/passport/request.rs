@@ -0,0 +1,259 @@
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+
+/// A type of a Telegram Passport element.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum PassportElementType {
+    PersonalDetails,
+    Passport,
+    DriverLicense,
+    IdentityCard,
+    InternalPassport,
+    Address,
+    UtilityBill,
+    BankStatement,
+    RentalAgreement,
+    PassportRegistration,
+    TemporaryRegistration,
+    PhoneNumber,
+    Email,
+}
+
+/// Data requested by a bot.
+///
+/// [The official docs](https://core.telegram.org/passport#passportscope).
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct PassportScope {
+    data: Vec<PassportScopeElement>,
+    v: u8,
+}
+
+impl PassportScope {
+    #[must_use]
+    pub fn new() -> Self {
+        Self { data: Vec::new(), v: 1 }
+    }
+
+    /// Requests an element or one of several elements.
+    #[must_use]
+    pub fn element<E>(mut self, val: E) -> Self
+    where
+        E: Into<PassportScopeElement>,
+    {
+        self.data.push(val.into());
+        self
+    }
+}
+
+impl Default for PassportScope {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A requested element or one of several elements.
+///
+/// [The official docs](https://core.telegram.org/passport#passportscopeelement).
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PassportScopeElement {
+    One(PassportScopeElementOne),
+    OneOfSeveral(PassportScopeElementOneOfSeveral),
+}
+
+impl From<PassportElementType> for PassportScopeElement {
+    fn from(r#type: PassportElementType) -> Self {
+        Self::One(PassportScopeElementOne::new(r#type))
+    }
+}
+
+impl From<PassportScopeElementOne> for PassportScopeElement {
+    fn from(element: PassportScopeElementOne) -> Self {
+        Self::One(element)
+    }
+}
+
+impl From<PassportScopeElementOneOfSeveral> for PassportScopeElement {
+    fn from(element: PassportScopeElementOneOfSeveral) -> Self {
+        Self::OneOfSeveral(element)
+    }
+}
+
+/// A requested element.
+///
+/// [The official docs](https://core.telegram.org/passport#passportscopeelementone).
+#[serde_with_macros::skip_serializing_none]
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct PassportScopeElementOne {
+    r#type: PassportElementType,
+    selfie: Option<bool>,
+    translation: Option<bool>,
+    native_names: Option<bool>,
+}
+
+impl PassportScopeElementOne {
+    #[must_use]
+    pub fn new(r#type: PassportElementType) -> Self {
+        Self { r#type, selfie: None, translation: None, native_names: None }
+    }
+
+    /// Requests a selfie with the document.
+    #[must_use]
+    pub fn selfie(mut self, val: bool) -> Self {
+        self.selfie = Some(val);
+        self
+    }
+
+    /// Requests a translation of the document.
+    #[must_use]
+    pub fn translation(mut self, val: bool) -> Self {
+        self.translation = Some(val);
+        self
+    }
+
+    /// Requests the user's name in the language of their country of
+    /// residence (for [`PassportElementType::PersonalDetails`]).
+    ///
+    /// [`PassportElementType::PersonalDetails`]:
+    /// crate::passport::PassportElementType::PersonalDetails
+    #[must_use]
+    pub fn native_names(mut self, val: bool) -> Self {
+        self.native_names = Some(val);
+        self
+    }
+}
+
+/// Several elements one of which must be provided.
+///
+/// [The official docs](https://core.telegram.org/passport#passportscopeelementoneofseveral).
+#[serde_with_macros::skip_serializing_none]
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct PassportScopeElementOneOfSeveral {
+    one_of: Vec<PassportScopeElementOne>,
+    selfie: Option<bool>,
+    translation: Option<bool>,
+}
+
+impl PassportScopeElementOneOfSeveral {
+    #[must_use]
+    pub fn new<E>(one_of: E) -> Self
+    where
+        E: Into<Vec<PassportScopeElementOne>>,
+    {
+        Self { one_of: one_of.into(), selfie: None, translation: None }
+    }
+
+    /// Requests a selfie with the chosen document.
+    #[must_use]
+    pub fn selfie(mut self, val: bool) -> Self {
+        self.selfie = Some(val);
+        self
+    }
+
+    /// Requests a translation of the chosen document.
+    #[must_use]
+    pub fn translation(mut self, val: bool) -> Self {
+        self.translation = Some(val);
+        self
+    }
+}
+
+/// A request of Telegram Passport data, which is sent to a user as a link.
+///
+/// [The official docs](https://core.telegram.org/passport#requesting-information).
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct PassportRequest {
+    bot_id: i32,
+    scope: PassportScope,
+    public_key: String,
+    nonce: String,
+}
+
+impl PassportRequest {
+    /// `public_key` is the bot's PEM-encoded public key (see
+    /// [`PassportKey::public_key_pem`]) and `nonce` is a unique payload to be
+    /// returned in [`Credentials::nonce`] (see [`PassportNonces`]).
+    ///
+    /// [`PassportKey::public_key_pem`]: crate::passport::PassportKey::public_key_pem
+    /// [`Credentials::nonce`]: crate::passport::Credentials::nonce
+    /// [`PassportNonces`]: crate::passport::PassportNonces
+    #[must_use]
+    pub fn new<K, N>(bot_id: i32, scope: PassportScope, public_key: K, nonce: N) -> Self
+    where
+        K: Into<String>,
+        N: Into<String>,
+    {
+        Self { bot_id, scope, public_key: public_key.into(), nonce: nonce.into() }
+    }
+
+    /// A `tg://` link, which opens the Telegram Passport authorization form.
+    #[must_use]
+    pub fn url(&self) -> Url {
+        let scope = serde_json::to_string(&self.scope).expect("a scope is always serializable");
+        let mut url = Url::parse("tg://resolve").expect("a valid URL");
+        url.query_pairs_mut()
+            .append_pair("domain", "telegrampassport")
+            .append_pair("bot_id", &self.bot_id.to_string())
+            .append_pair("scope", &scope)
+            .append_pair("public_key", &self.public_key)
+            .append_pair("nonce", &self.nonce);
+        url
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scope() {
+        let scope = PassportScope::new()
+            .element(
+                PassportScopeElementOne::new(PassportElementType::PersonalDetails)
+                    .native_names(true),
+            )
+            .element(
+                PassportScopeElementOneOfSeveral::new(vec![
+                    PassportScopeElementOne::new(PassportElementType::Passport),
+                    PassportScopeElementOne::new(PassportElementType::IdentityCard),
+                ])
+                .selfie(true),
+            )
+            .element(PassportElementType::Email);
+
+        assert_eq!(
+            serde_json::to_string(&scope).unwrap(),
+            r#"{"data":[{"type":"personal_details","native_names":true},{"one_of":[{"type":"passport"},{"type":"identity_card"}],"selfie":true},{"type":"email"}],"v":1}"#
+        );
+    }
+
+    #[test]
+    fn url() {
+        let request = PassportRequest::new(
+            123,
+            PassportScope::new().element(PassportElementType::Email),
+            "-----BEGIN PUBLIC KEY-----\nKEY\n-----END PUBLIC KEY-----\n",
+            "nonce",
+        );
+        let url = request.url();
+
+        assert_eq!(url.scheme(), "tg");
+        let pairs: Vec<_> =
+            url.query_pairs().map(|(k, v)| (k.into_owned(), v.into_owned())).collect();
+        assert_eq!(
+            pairs,
+            [
+                ("domain".to_owned(), "telegrampassport".to_owned()),
+                ("bot_id".to_owned(), "123".to_owned()),
+                ("scope".to_owned(), r#"{"data":[{"type":"email"}],"v":1}"#.to_owned()),
+                (
+                    "public_key".to_owned(),
+                    "-----BEGIN PUBLIC KEY-----\nKEY\n-----END PUBLIC KEY-----\n".to_owned()
+                ),
+                ("nonce".to_owned(), "nonce".to_owned()),
+            ]
+        );
+    }
+}
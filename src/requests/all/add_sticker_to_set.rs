@@ -1,12 +1,12 @@
 use crate::{
     net,
-    requests::form_builder::FormBuilder,
+    requests::{form_builder::FormBuilder, FileKind},
     types::{MaskPosition, True},
     Bot,
 };
 
 use crate::{
-    requests::{RequestWithFile, ResponseResult},
+    requests::{Request, ResponseResult},
     types::StickerType,
 };
 
@@ -24,13 +24,19 @@ pub struct AddStickerToSet {
 }
 
 #[async_trait::async_trait]
-impl RequestWithFile for AddStickerToSet {
+impl Request for AddStickerToSet {
     type Output = True;
 
-    async fn send(&self) -> tokio::io::Result<ResponseResult<True>> {
+    async fn send(&self) -> ResponseResult<True> {
         let builder =
             FormBuilder::new().add_text("user_id", &self.user_id).add_text("name", &self.name);
 
+        let limits = self.bot.upload_limits();
+        match &self.sticker_type {
+            StickerType::Png(file) => limits.check(FileKind::PngSticker, file).await?,
+            StickerType::Tgs(file) => limits.check(FileKind::TgsSticker, file).await?,
+        }
+
         let builder = match &self.sticker_type {
             StickerType::Png(file) => builder.add_input_file("png_sticker", &file),
             StickerType::Tgs(file) => builder.add_input_file("tgs_sticker", &file),
@@ -39,16 +45,18 @@ impl RequestWithFile for AddStickerToSet {
         .add_text("emojis", &self.emojis)
         .add_text("mask_position", &self.mask_position);
 
-        Ok(net::request_multipart(
+        net::request_multipart(
             self.bot.client(),
             self.bot.token(),
             "addStickerToSet",
             builder.build(),
         )
-        .await)
+        .await
     }
 }
 
+impl_into_future!(AddStickerToSet);
+
 impl AddStickerToSet {
     pub(crate) fn new<N, E>(
         bot: Bot,
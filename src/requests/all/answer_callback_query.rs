@@ -37,6 +37,8 @@ impl Request for AnswerCallbackQuery {
     }
 }
 
+impl_into_future!(AnswerCallbackQuery);
+
 impl AnswerCallbackQuery {
     pub(crate) fn new<C>(bot: Bot, callback_query_id: C) -> Self
     where
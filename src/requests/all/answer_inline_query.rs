@@ -35,6 +35,8 @@ impl Request for AnswerInlineQuery {
     }
 }
 
+impl_into_future!(AnswerInlineQuery);
+
 impl AnswerInlineQuery {
     pub(crate) fn new<I, R>(bot: Bot, inline_query_id: I, results: R) -> Self
     where
@@ -39,6 +39,8 @@ impl Request for AnswerPreCheckoutQuery {
     }
 }
 
+impl_into_future!(AnswerPreCheckoutQuery);
+
 impl AnswerPreCheckoutQuery {
     pub(crate) fn new<P>(bot: Bot, pre_checkout_query_id: P, ok: bool) -> Self
     where
@@ -35,6 +35,8 @@ impl Request for AnswerShippingQuery {
     }
 }
 
+impl_into_future!(AnswerShippingQuery);
+
 impl AnswerShippingQuery {
     pub(crate) fn new<S>(bot: Bot, shipping_query_id: S, ok: bool) -> Self
     where
@@ -1,6 +1,6 @@
 use crate::{
     net,
-    requests::{form_builder::FormBuilder, RequestWithFile, ResponseResult},
+    requests::{form_builder::FormBuilder, FileKind, Request, ResponseResult},
     types::{MaskPosition, StickerType, True},
     Bot,
 };
@@ -22,15 +22,21 @@ pub struct CreateNewStickerSet {
 }
 
 #[async_trait::async_trait]
-impl RequestWithFile for CreateNewStickerSet {
+impl Request for CreateNewStickerSet {
     type Output = True;
 
-    async fn send(&self) -> tokio::io::Result<ResponseResult<True>> {
+    async fn send(&self) -> ResponseResult<True> {
         let builder = FormBuilder::new()
             .add_text("user_id", &self.user_id)
             .add_text("name", &self.name)
             .add_text("title", &self.title);
 
+        let limits = self.bot.upload_limits();
+        match &self.sticker_type {
+            StickerType::Png(file) => limits.check(FileKind::PngSticker, file).await?,
+            StickerType::Tgs(file) => limits.check(FileKind::TgsSticker, file).await?,
+        }
+
         let builder = match &self.sticker_type {
             StickerType::Png(file) => builder.add_input_file("png_sticker", &file),
             StickerType::Tgs(file) => builder.add_input_file("tgs_sticker", &file),
@@ -40,16 +46,18 @@ impl RequestWithFile for CreateNewStickerSet {
         .add_text("contains_masks", &self.contains_masks)
         .add_text("mask_position", &self.mask_position);
 
-        Ok(net::request_multipart(
+        net::request_multipart(
             self.bot.client(),
             self.bot.token(),
             "createNewStickerSet",
             builder.build(),
         )
-        .await)
+        .await
     }
 }
 
+impl_into_future!(CreateNewStickerSet);
+
 impl CreateNewStickerSet {
     pub(crate) fn new<N, T, E>(
         bot: Bot,
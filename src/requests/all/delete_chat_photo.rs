@@ -29,6 +29,8 @@ impl Request for DeleteChatPhoto {
     }
 }
 
+impl_into_future!(DeleteChatPhoto);
+
 impl DeleteChatPhoto {
     pub(crate) fn new<C>(bot: Bot, chat_id: C) -> Self
     where
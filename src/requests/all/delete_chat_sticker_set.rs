@@ -34,6 +34,8 @@ impl Request for DeleteChatStickerSet {
     }
 }
 
+impl_into_future!(DeleteChatStickerSet);
+
 impl DeleteChatStickerSet {
     pub(crate) fn new<C>(bot: Bot, chat_id: C) -> Self
     where
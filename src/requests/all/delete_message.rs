@@ -40,6 +40,8 @@ impl Request for DeleteMessage {
     }
 }
 
+impl_into_future!(DeleteMessage);
+
 impl DeleteMessage {
     pub(crate) fn new<C>(bot: Bot, chat_id: C, message_id: i32) -> Self
     where
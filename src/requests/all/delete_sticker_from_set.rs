@@ -27,6 +27,8 @@ impl Request for DeleteStickerFromSet {
     }
 }
 
+impl_into_future!(DeleteStickerFromSet);
+
 impl DeleteStickerFromSet {
     pub(crate) fn new<S>(bot: Bot, sticker: S) -> Self
     where
@@ -30,6 +30,8 @@ impl Request for DeleteWebhook {
     }
 }
 
+impl_into_future!(DeleteWebhook);
+
 impl DeleteWebhook {
     pub(crate) fn new(bot: Bot) -> Self {
         Self { bot }
@@ -3,7 +3,7 @@ use serde::Serialize;
 use crate::{
     net,
     requests::{Request, ResponseResult},
-    types::{ChatOrInlineMessage, InlineKeyboardMarkup, Message, ParseMode},
+    types::{ChatOrInlineMessage, InlineKeyboardMarkup, Message, MessageEntity, ParseMode},
     Bot,
 };
 
@@ -25,6 +25,7 @@ pub struct EditMessageCaption {
     chat_or_inline_message: ChatOrInlineMessage,
     caption: Option<String>,
     parse_mode: Option<ParseMode>,
+    caption_entities: Option<Vec<MessageEntity>>,
     reply_markup: Option<InlineKeyboardMarkup>,
 }
 
@@ -37,9 +38,18 @@ impl Request for EditMessageCaption {
     }
 }
 
+impl_into_future!(EditMessageCaption);
+
 impl EditMessageCaption {
     pub(crate) fn new(bot: Bot, chat_or_inline_message: ChatOrInlineMessage) -> Self {
-        Self { bot, chat_or_inline_message, caption: None, parse_mode: None, reply_markup: None }
+        Self {
+            bot,
+            chat_or_inline_message,
+            caption: None,
+            parse_mode: None,
+            caption_entities: None,
+            reply_markup: None,
+        }
     }
 
     pub fn chat_or_inline_message(mut self, val: ChatOrInlineMessage) -> Self {
@@ -59,12 +69,28 @@ impl EditMessageCaption {
     /// Send [Markdown] or [HTML], if you want Telegram apps to show
     /// [bold, italic, fixed-width text or inline URLs] in the media caption.
     ///
+    /// Resets `caption_entities`, because they cannot be used together.
+    ///
     /// [Markdown]: crate::types::ParseMode::Markdown
     /// [HTML]: crate::types::ParseMode::HTML
     /// [bold, italic, fixed-width text or inline URLs]:
     /// crate::types::ParseMode
     pub fn parse_mode(mut self, val: ParseMode) -> Self {
         self.parse_mode = Some(val);
+        self.caption_entities = None;
+        self
+    }
+
+    /// List of special entities that appear in the caption, which can be
+    /// specified instead of `parse_mode`.
+    ///
+    /// Resets `parse_mode`, because they cannot be used together.
+    pub fn caption_entities<T>(mut self, val: T) -> Self
+    where
+        T: Into<Vec<MessageEntity>>,
+    {
+        self.caption_entities = Some(val.into());
+        self.parse_mode = None;
         self
     }
 
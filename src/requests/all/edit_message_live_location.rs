@@ -40,6 +40,8 @@ impl Request for EditMessageLiveLocation {
     }
 }
 
+impl_into_future!(EditMessageLiveLocation);
+
 impl EditMessageLiveLocation {
     pub(crate) fn new(
         bot: Bot,
@@ -32,6 +32,8 @@ impl Request for EditMessageMedia {
     type Output = Message;
 
     async fn send(&self) -> ResponseResult<Message> {
+        self.bot.upload_limits().check_media(&self.media).await?;
+
         let mut params = FormBuilder::new();
 
         match &self.chat_or_inline_message {
@@ -48,7 +50,8 @@ impl Request for EditMessageMedia {
             self.bot.token(),
             "editMessageMedia",
             params
-                .add_text("media", &self.media)
+                .add_input_media("media", &self.media)
+                .await?
                 .add_text("reply_markup", &self.reply_markup)
                 .build(),
         )
@@ -56,6 +59,8 @@ impl Request for EditMessageMedia {
     }
 }
 
+impl_into_future!(EditMessageMedia);
+
 impl EditMessageMedia {
     pub(crate) fn new(
         bot: Bot,
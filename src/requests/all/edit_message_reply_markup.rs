@@ -36,6 +36,8 @@ impl Request for EditMessageReplyMarkup {
     }
 }
 
+impl_into_future!(EditMessageReplyMarkup);
+
 impl EditMessageReplyMarkup {
     pub(crate) fn new(bot: Bot, chat_or_inline_message: ChatOrInlineMessage) -> Self {
         Self { bot, chat_or_inline_message, reply_markup: None }
@@ -3,7 +3,8 @@ use serde::Serialize;
 use crate::{
     net,
     requests::{Request, ResponseResult},
-    types::{ChatOrInlineMessage, InlineKeyboardMarkup, Message, ParseMode},
+    types::{ChatOrInlineMessage, InlineKeyboardMarkup, Message, MessageEntity, ParseMode},
+    utils::markup,
     Bot,
 };
 
@@ -25,6 +26,7 @@ pub struct EditMessageText {
     chat_or_inline_message: ChatOrInlineMessage,
     text: String,
     parse_mode: Option<ParseMode>,
+    entities: Option<Vec<MessageEntity>>,
     disable_web_page_preview: Option<bool>,
     reply_markup: Option<InlineKeyboardMarkup>,
 }
@@ -34,10 +36,13 @@ impl Request for EditMessageText {
     type Output = Message;
 
     async fn send(&self) -> ResponseResult<Message> {
+        markup::debug_validate(&self.bot, &self.text, self.parse_mode);
         net::request_json(self.bot.client(), self.bot.token(), "editMessageText", &self).await
     }
 }
 
+impl_into_future!(EditMessageText);
+
 impl EditMessageText {
     pub(crate) fn new<T>(bot: Bot, chat_or_inline_message: ChatOrInlineMessage, text: T) -> Self
     where
@@ -48,6 +53,7 @@ impl EditMessageText {
             chat_or_inline_message,
             text: text.into(),
             parse_mode: None,
+            entities: None,
             disable_web_page_preview: None,
             reply_markup: None,
         }
@@ -70,11 +76,33 @@ impl EditMessageText {
     /// Send [Markdown] or [HTML], if you want Telegram apps to show [bold,
     /// italic, fixed-width text or inline URLs] in your bot's message.
     ///
+    /// Resets `entities`, because they cannot be used together.
+    ///
+    /// In debug builds, the markup is checked by [`markup::validate`] before
+    /// sending and errors are logged (see also
+    /// [`BotBuilder::skip_markup_validation`]).
+    ///
+    /// [`markup::validate`]: crate::utils::markup::validate
+    /// [`BotBuilder::skip_markup_validation`]: crate::BotBuilder::skip_markup_validation
     /// [Markdown]: https://core.telegram.org/bots/api#markdown-style
     /// [HTML]: https://core.telegram.org/bots/api#html-style
     /// [bold, italic, fixed-width text or inline URLs]: https://core.telegram.org/bots/api#formatting-options
     pub fn parse_mode(mut self, val: ParseMode) -> Self {
         self.parse_mode = Some(val);
+        self.entities = None;
+        self
+    }
+
+    /// List of special entities that appear in the message text, which can be
+    /// specified instead of `parse_mode`.
+    ///
+    /// Resets `parse_mode`, because they cannot be used together.
+    pub fn entities<T>(mut self, val: T) -> Self
+    where
+        T: Into<Vec<MessageEntity>>,
+    {
+        self.entities = Some(val.into());
+        self.parse_mode = None;
         self
     }
 
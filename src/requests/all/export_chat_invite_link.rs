@@ -44,6 +44,8 @@ impl Request for ExportChatInviteLink {
     }
 }
 
+impl_into_future!(ExportChatInviteLink);
+
 impl ExportChatInviteLink {
     pub(crate) fn new<C>(bot: Bot, chat_id: C) -> Self
     where
@@ -30,6 +30,8 @@ impl Request for ForwardMessage {
     }
 }
 
+impl_into_future!(ForwardMessage);
+
 impl ForwardMessage {
     pub(crate) fn new<C, F>(bot: Bot, chat_id: C, from_chat_id: F, message_id: i32) -> Self
     where
@@ -29,6 +29,8 @@ impl Request for GetChat {
     }
 }
 
+impl_into_future!(GetChat);
+
 impl GetChat {
     pub(crate) fn new<C>(bot: Bot, chat_id: C) -> Self
     where
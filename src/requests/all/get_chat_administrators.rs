@@ -32,6 +32,8 @@ impl Request for GetChatAdministrators {
     }
 }
 
+impl_into_future!(GetChatAdministrators);
+
 impl GetChatAdministrators {
     pub(crate) fn new<C>(bot: Bot, chat_id: C) -> Self
     where
@@ -28,6 +28,8 @@ impl Request for GetChatMember {
     }
 }
 
+impl_into_future!(GetChatMember);
+
 impl GetChatMember {
     pub(crate) fn new<C>(bot: Bot, chat_id: C, user_id: i32) -> Self
     where
@@ -27,6 +27,8 @@ impl Request for GetChatMembersCount {
     }
 }
 
+impl_into_future!(GetChatMembersCount);
+
 impl GetChatMembersCount {
     pub(crate) fn new<C>(bot: Bot, chat_id: C) -> Self
     where
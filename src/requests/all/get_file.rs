@@ -43,6 +43,8 @@ impl Request for GetFile {
     }
 }
 
+impl_into_future!(GetFile);
+
 impl GetFile {
     pub(crate) fn new<F>(bot: Bot, file_id: F) -> Self
     where
@@ -38,6 +38,8 @@ impl Request for GetGameHighScores {
     }
 }
 
+impl_into_future!(GetGameHighScores);
+
 impl GetGameHighScores {
     pub(crate) fn new(bot: Bot, chat_or_inline_message: ChatOrInlineMessage, user_id: i32) -> Self {
         Self { bot, chat_or_inline_message, user_id }
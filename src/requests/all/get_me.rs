@@ -26,6 +26,8 @@ impl Request for GetMe {
     }
 }
 
+impl_into_future!(GetMe);
+
 impl GetMe {
     pub(crate) fn new(bot: Bot) -> Self {
         Self { bot }
@@ -26,6 +26,8 @@ impl Request for GetMyCommands {
     }
 }
 
+impl_into_future!(GetMyCommands);
+
 impl GetMyCommands {
     pub(crate) fn new(bot: Bot) -> Self {
         Self { bot }
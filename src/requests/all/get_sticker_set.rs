@@ -27,6 +27,8 @@ impl Request for GetStickerSet {
     }
 }
 
+impl_into_future!(GetStickerSet);
+
 impl GetStickerSet {
     pub(crate) fn new<N>(bot: Bot, name: N) -> Self
     where
@@ -53,6 +53,8 @@ impl Request for GetUpdates {
     }
 }
 
+impl_into_future!(GetUpdates);
+
 impl GetUpdates {
     pub(crate) fn new(bot: Bot) -> Self {
         Self { bot, offset: None, limit: None, timeout: None, allowed_updates: None }
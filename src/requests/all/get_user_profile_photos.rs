@@ -29,6 +29,8 @@ impl Request for GetUserProfilePhotos {
     }
 }
 
+impl_into_future!(GetUserProfilePhotos);
+
 impl GetUserProfilePhotos {
     pub(crate) fn new(bot: Bot, user_id: i32) -> Self {
         Self { bot, user_id, offset: None, limit: None }
@@ -31,6 +31,8 @@ impl Request for GetWebhookInfo {
     }
 }
 
+impl_into_future!(GetWebhookInfo);
+
 impl GetWebhookInfo {
     pub(crate) fn new(bot: Bot) -> Self {
         Self { bot }
@@ -36,6 +36,8 @@ impl Request for KickChatMember {
     }
 }
 
+impl_into_future!(KickChatMember);
+
 impl KickChatMember {
     pub(crate) fn new<C>(bot: Bot, chat_id: C, user_id: i32) -> Self
     where
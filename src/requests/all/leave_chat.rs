@@ -27,6 +27,8 @@ impl Request for LeaveChat {
     }
 }
 
+impl_into_future!(LeaveChat);
+
 impl LeaveChat {
     pub(crate) fn new<C>(bot: Bot, chat_id: C) -> Self
     where
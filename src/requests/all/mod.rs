@@ -59,6 +59,7 @@ mod set_chat_sticker_set;
 mod set_chat_title;
 mod set_game_score;
 mod set_my_commands;
+mod set_passport_data_errors;
 mod set_sticker_position_in_set;
 mod set_sticker_set_thumb;
 mod set_webhook;
@@ -129,6 +130,7 @@ pub use set_chat_sticker_set::*;
 pub use set_chat_title::*;
 pub use set_game_score::*;
 pub use set_my_commands::*;
+pub use set_passport_data_errors::*;
 pub use set_sticker_position_in_set::*;
 pub use set_sticker_set_thumb::*;
 pub use set_webhook::*;
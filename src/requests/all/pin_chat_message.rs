@@ -33,6 +33,8 @@ impl Request for PinChatMessage {
     }
 }
 
+impl_into_future!(PinChatMessage);
+
 impl PinChatMessage {
     pub(crate) fn new<C>(bot: Bot, chat_id: C, message_id: i32) -> Self
     where
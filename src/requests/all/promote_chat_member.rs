@@ -40,6 +40,8 @@ impl Request for PromoteChatMember {
     }
 }
 
+impl_into_future!(PromoteChatMember);
+
 impl PromoteChatMember {
     pub(crate) fn new<C>(bot: Bot, chat_id: C, user_id: i32) -> Self
     where
@@ -34,6 +34,8 @@ impl Request for RestrictChatMember {
     }
 }
 
+impl_into_future!(RestrictChatMember);
+
 impl RestrictChatMember {
     pub(crate) fn new<C>(bot: Bot, chat_id: C, user_id: i32, permissions: ChatPermissions) -> Self
     where
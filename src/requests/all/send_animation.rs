@@ -1,7 +1,11 @@
 use crate::{
     net,
-    requests::{form_builder::FormBuilder, RequestWithFile, ResponseResult},
-    types::{ChatId, InputFile, Message, ParseMode, ReplyMarkup},
+    requests::{
+        form_builder::FormBuilder,
+        upload_cache::{self, CachedMedia},
+        FileKind, Request, ResponseResult,
+    },
+    types::{ChatId, InputFile, Message, MessageEntity, ParseMode, ReplyMarkup},
     Bot,
 };
 
@@ -23,41 +27,62 @@ pub struct SendAnimation {
     pub thumb: Option<InputFile>,
     pub caption: Option<String>,
     pub parse_mode: Option<ParseMode>,
+    pub caption_entities: Option<Vec<MessageEntity>>,
     pub disable_notification: Option<bool>,
     pub reply_to_message_id: Option<i32>,
     pub reply_markup: Option<ReplyMarkup>,
 }
 
 #[async_trait::async_trait]
-impl RequestWithFile for SendAnimation {
+impl Request for SendAnimation {
     type Output = Message;
 
-    async fn send(&self) -> tokio::io::Result<ResponseResult<Message>> {
+    async fn send(&self) -> ResponseResult<Message> {
+        upload_cache::send_cached(&self.bot, CachedMedia::Animation, &self.animation, |cached| {
+            Box::pin(self.send_with(cached))
+        })
+        .await
+    }
+}
+
+impl SendAnimation {
+    /// Sends this request with `cached` instead of `animation`, if any.
+    async fn send_with(&self, cached: Option<InputFile>) -> ResponseResult<Message> {
+        let animation = cached.as_ref().unwrap_or(&self.animation);
+        let limits = self.bot.upload_limits();
+        limits.check(FileKind::Other, animation).await?;
+        if let Some(thumb) = &self.thumb {
+            limits.check(FileKind::Thumb, thumb).await?;
+        }
+
         let mut builder = FormBuilder::new()
             .add_text("chat_id", &self.chat_id)
-            .add_input_file("animation", &self.animation)
+            .add_input_file("animation", animation)
             .await?
             .add_text("duration", &self.duration)
             .add_text("width", &self.width)
             .add_text("height", &self.height)
             .add_text("caption", &self.caption)
             .add_text("parse_mode", &self.parse_mode)
+            .add_text("caption_entities", &self.caption_entities)
             .add_text("disable_notification", &self.disable_notification)
             .add_text("reply_to_message_id", &self.reply_to_message_id)
             .add_text("reply_markup", &self.reply_markup);
         if let Some(thumb) = self.thumb.as_ref() {
             builder = builder.add_input_file("thumb", thumb).await?;
         }
-        Ok(net::request_multipart(
+        net::request_multipart(
             self.bot.client(),
             self.bot.token(),
             "sendAnimation",
             builder.build(),
         )
-        .await)
+        .await
     }
 }
 
+impl_into_future!(SendAnimation);
+
 impl SendAnimation {
     pub(crate) fn new<C>(bot: Bot, chat_id: C, animation: InputFile) -> Self
     where
@@ -73,6 +98,7 @@ impl SendAnimation {
             thumb: None,
             caption: None,
             parse_mode: None,
+            caption_entities: None,
             disable_notification: None,
             reply_to_message_id: None,
             reply_markup: None,
@@ -140,12 +166,28 @@ impl SendAnimation {
     /// Send [Markdown] or [HTML], if you want Telegram apps to show
     /// [bold, italic, fixed-width text or inline URLs] in the media caption.
     ///
+    /// Resets `caption_entities`, because they cannot be used together.
+    ///
     /// [Markdown]: crate::types::ParseMode::Markdown
     /// [HTML]: crate::types::ParseMode::HTML
     /// [bold, italic, fixed-width text or inline URLs]:
     /// crate::types::ParseMode
     pub fn parse_mode(mut self, value: ParseMode) -> Self {
         self.parse_mode = Some(value);
+        self.caption_entities = None;
+        self
+    }
+
+    /// List of special entities that appear in the caption, which can be
+    /// specified instead of `parse_mode`.
+    ///
+    /// Resets `parse_mode`, because they cannot be used together.
+    pub fn caption_entities<T>(mut self, value: T) -> Self
+    where
+        T: Into<Vec<MessageEntity>>,
+    {
+        self.caption_entities = Some(value.into());
+        self.parse_mode = None;
         self
     }
 
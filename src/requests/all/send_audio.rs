@@ -1,7 +1,11 @@
 use crate::{
     net,
-    requests::{form_builder::FormBuilder, RequestWithFile, ResponseResult},
-    types::{ChatId, InputFile, Message, ParseMode, ReplyMarkup},
+    requests::{
+        form_builder::FormBuilder,
+        upload_cache::{self, CachedMedia},
+        FileKind, Request, ResponseResult,
+    },
+    types::{ChatId, InputFile, Message, MessageEntity, ParseMode, ReplyMarkup},
     Bot,
 };
 
@@ -23,6 +27,7 @@ pub struct SendAudio {
     audio: InputFile,
     caption: Option<String>,
     parse_mode: Option<ParseMode>,
+    caption_entities: Option<Vec<MessageEntity>>,
     duration: Option<i32>,
     performer: Option<String>,
     title: Option<String>,
@@ -33,16 +38,34 @@ pub struct SendAudio {
 }
 
 #[async_trait::async_trait]
-impl RequestWithFile for SendAudio {
+impl Request for SendAudio {
     type Output = Message;
 
-    async fn send(&self) -> tokio::io::Result<ResponseResult<Message>> {
+    async fn send(&self) -> ResponseResult<Message> {
+        upload_cache::send_cached(&self.bot, CachedMedia::Audio, &self.audio, |cached| {
+            Box::pin(self.send_with(cached))
+        })
+        .await
+    }
+}
+
+impl SendAudio {
+    /// Sends this request with `cached` instead of `audio`, if any.
+    async fn send_with(&self, cached: Option<InputFile>) -> ResponseResult<Message> {
+        let audio = cached.as_ref().unwrap_or(&self.audio);
+        let limits = self.bot.upload_limits();
+        limits.check(FileKind::Other, audio).await?;
+        if let Some(thumb) = &self.thumb {
+            limits.check(FileKind::Thumb, thumb).await?;
+        }
+
         let mut builder = FormBuilder::new()
             .add_text("chat_id", &self.chat_id)
-            .add_input_file("audio", &self.audio)
+            .add_input_file("audio", audio)
             .await?
             .add_text("caption", &self.caption)
             .add_text("parse_mode", &self.parse_mode)
+            .add_text("caption_entities", &self.caption_entities)
             .add_text("duration", &self.duration)
             .add_text("performer", &self.performer)
             .add_text("title", &self.title)
@@ -52,16 +75,13 @@ impl RequestWithFile for SendAudio {
         if let Some(thumb) = self.thumb.as_ref() {
             builder = builder.add_input_file("thumb", thumb).await?;
         }
-        Ok(net::request_multipart(
-            self.bot.client(),
-            self.bot.token(),
-            "sendAudio",
-            builder.build(),
-        )
-        .await)
+        net::request_multipart(self.bot.client(), self.bot.token(), "sendAudio", builder.build())
+            .await
     }
 }
 
+impl_into_future!(SendAudio);
+
 impl SendAudio {
     pub(crate) fn new<C>(bot: Bot, chat_id: C, audio: InputFile) -> Self
     where
@@ -73,6 +93,7 @@ impl SendAudio {
             audio,
             caption: None,
             parse_mode: None,
+            caption_entities: None,
             duration: None,
             performer: None,
             title: None,
@@ -122,12 +143,28 @@ impl SendAudio {
     /// Send [Markdown] or [HTML], if you want Telegram apps to show
     /// [bold, italic, fixed-width text or inline URLs] in the media caption.
     ///
+    /// Resets `caption_entities`, because they cannot be used together.
+    ///
     /// [Markdown]: crate::types::ParseMode::Markdown
     /// [HTML]: crate::types::ParseMode::HTML
     /// [bold, italic, fixed-width text or inline URLs]:
     /// crate::types::ParseMode
     pub fn parse_mode(mut self, val: ParseMode) -> Self {
         self.parse_mode = Some(val);
+        self.caption_entities = None;
+        self
+    }
+
+    /// List of special entities that appear in the caption, which can be
+    /// specified instead of `parse_mode`.
+    ///
+    /// Resets `parse_mode`, because they cannot be used together.
+    pub fn caption_entities<T>(mut self, val: T) -> Self
+    where
+        T: Into<Vec<MessageEntity>>,
+    {
+        self.caption_entities = Some(val.into());
+        self.parse_mode = None;
         self
     }
 
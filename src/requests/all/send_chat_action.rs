@@ -79,6 +79,8 @@ impl Request for SendChatAction {
     }
 }
 
+impl_into_future!(SendChatAction);
+
 impl SendChatAction {
     pub(crate) fn new<C>(bot: Bot, chat_id: C, action: SendChatActionKind) -> Self
     where
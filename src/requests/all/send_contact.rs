@@ -34,6 +34,8 @@ impl Request for SendContact {
     }
 }
 
+impl_into_future!(SendContact);
+
 impl SendContact {
     pub(crate) fn new<C, P, F>(bot: Bot, chat_id: C, phone_number: P, first_name: F) -> Self
     where
@@ -33,6 +33,8 @@ impl Request for SendDice {
     }
 }
 
+impl_into_future!(SendDice);
+
 impl SendDice {
     pub(crate) fn new<C>(bot: Bot, chat_id: C) -> Self
     where
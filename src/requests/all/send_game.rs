@@ -31,6 +31,8 @@ impl Request for SendGame {
     }
 }
 
+impl_into_future!(SendGame);
+
 impl SendGame {
     pub(crate) fn new<G>(bot: Bot, chat_id: i32, game_short_name: G) -> Self
     where
@@ -49,6 +49,8 @@ impl Request for SendInvoice {
     }
 }
 
+impl_into_future!(SendInvoice);
+
 impl SendInvoice {
     #[allow(clippy::too_many_arguments)]
     pub(crate) fn new<T, D, Pl, Pt, S, C, Pr>(
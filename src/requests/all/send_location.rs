@@ -33,6 +33,8 @@ impl Request for SendLocation {
     }
 }
 
+impl_into_future!(SendLocation);
+
 impl SendLocation {
     pub(crate) fn new<C>(bot: Bot, chat_id: C, latitude: f32, longitude: f32) -> Self
     where
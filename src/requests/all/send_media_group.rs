@@ -22,13 +22,18 @@ impl Request for SendMediaGroup {
     type Output = Vec<Message>;
 
     async fn send(&self) -> ResponseResult<Vec<Message>> {
+        for media in &self.media {
+            self.bot.upload_limits().check_media(media).await?;
+        }
+
         net::request_multipart(
             self.bot.client(),
             self.bot.token(),
             "sendMediaGroup",
             FormBuilder::new()
                 .add_text("chat_id", &self.chat_id)
-                .add_text("media", &self.media)
+                .add_input_media_group("media", &self.media)
+                .await?
                 .add_text("disable_notification", &self.disable_notification)
                 .add_text("reply_to_message_id", &self.reply_to_message_id)
                 .build(),
@@ -37,6 +42,8 @@ impl Request for SendMediaGroup {
     }
 }
 
+impl_into_future!(SendMediaGroup);
+
 impl SendMediaGroup {
     pub(crate) fn new<C, M>(bot: Bot, chat_id: C, media: M) -> Self
     where
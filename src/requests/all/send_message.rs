@@ -3,7 +3,12 @@ use serde::Serialize;
 use crate::{
     net,
     requests::{Request, ResponseResult},
-    types::{ChatId, Message, ParseMode, ReplyMarkup},
+    types::{ChatId, Message, MessageEntity, ParseMode, ReplyMarkup},
+    utils::{
+        markup::{self, parse_html, parse_markdown_v2},
+        split::{split_formatted, MAX_TEXT_LENGTH},
+        FormattedText,
+    },
     Bot,
 };
 
@@ -18,6 +23,7 @@ pub struct SendMessage {
     pub chat_id: ChatId,
     pub text: String,
     pub parse_mode: Option<ParseMode>,
+    pub entities: Option<Vec<MessageEntity>>,
     pub disable_web_page_preview: Option<bool>,
     pub disable_notification: Option<bool>,
     pub reply_to_message_id: Option<i32>,
@@ -29,10 +35,13 @@ impl Request for SendMessage {
     type Output = Message;
 
     async fn send(&self) -> ResponseResult<Message> {
+        markup::debug_validate(&self.bot, &self.text, self.parse_mode);
         net::request_json(self.bot.client(), self.bot.token(), "sendMessage", &self).await
     }
 }
 
+impl_into_future!(SendMessage);
+
 impl SendMessage {
     pub(crate) fn new<C, T>(bot: Bot, chat_id: C, text: T) -> Self
     where
@@ -44,6 +53,7 @@ impl SendMessage {
             chat_id: chat_id.into(),
             text: text.into(),
             parse_mode: None,
+            entities: None,
             disable_web_page_preview: None,
             disable_notification: None,
             reply_to_message_id: None,
@@ -73,12 +83,34 @@ impl SendMessage {
     /// Send [Markdown] or [HTML], if you want Telegram apps to show
     /// [bold, italic, fixed-width text or inline URLs] in the media caption.
     ///
+    /// Resets `entities`, because they cannot be used together.
+    ///
+    /// In debug builds, the markup is checked by [`markup::validate`] before
+    /// sending and errors are logged (see also
+    /// [`BotBuilder::skip_markup_validation`]).
+    ///
+    /// [`markup::validate`]: crate::utils::markup::validate
+    /// [`BotBuilder::skip_markup_validation`]: crate::BotBuilder::skip_markup_validation
     /// [Markdown]: crate::types::ParseMode::Markdown
     /// [HTML]: crate::types::ParseMode::HTML
     /// [bold, italic, fixed-width text or inline URLs]:
     /// crate::types::ParseMode
     pub fn parse_mode(mut self, value: ParseMode) -> Self {
         self.parse_mode = Some(value);
+        self.entities = None;
+        self
+    }
+
+    /// List of special entities that appear in the message text, which can be
+    /// specified instead of `parse_mode`.
+    ///
+    /// Resets `parse_mode`, because they cannot be used together.
+    pub fn entities<T>(mut self, value: T) -> Self
+    where
+        T: Into<Vec<MessageEntity>>,
+    {
+        self.entities = Some(value.into());
+        self.parse_mode = None;
         self
     }
 
@@ -118,4 +150,61 @@ impl SendMessage {
         self.reply_markup = Some(value.into());
         self
     }
+
+    /// Sends the message, splitting the text into several messages if it's
+    /// longer than [`MAX_TEXT_LENGTH`].
+    ///
+    /// The text is cut at paragraph, line or word boundaries without breaking
+    /// the markup (see [`split_formatted`]). Parts are sent in order with
+    /// `entities` instead of `parse_mode`; only the first one replies to
+    /// `reply_to_message_id` and only the last one has `reply_markup`.
+    ///
+    /// A text with invalid markup or with the legacy [`ParseMode::Markdown`]
+    /// is sent as is, without splitting.
+    ///
+    /// [`MAX_TEXT_LENGTH`]: crate::utils::split::MAX_TEXT_LENGTH
+    /// [`split_formatted`]: crate::utils::split::split_formatted
+    /// [`ParseMode::Markdown`]: crate::types::ParseMode::Markdown
+    pub async fn send_split(&self) -> ResponseResult<Vec<Message>> {
+        let text = match self.formatted_text() {
+            Some(text) if text.utf16_len() > MAX_TEXT_LENGTH => text,
+            _ => return Ok(vec![self.send().await?]),
+        };
+
+        let parts = split_formatted(&text, MAX_TEXT_LENGTH);
+        let last = parts.len() - 1;
+        let mut messages = Vec::with_capacity(parts.len());
+
+        for (i, part) in parts.into_iter().enumerate() {
+            let (text, entities) = part.into_parts();
+            let mut request = self.clone();
+            request.text = text;
+            request.parse_mode = None;
+            request.entities = Some(entities);
+            if i != 0 {
+                request.reply_to_message_id = None;
+            }
+            if i != last {
+                request.reply_markup = None;
+            }
+
+            messages.push(request.send().await?);
+        }
+
+        Ok(messages)
+    }
+
+    #[allow(deprecated)]
+    fn formatted_text(&self) -> Option<FormattedText> {
+        if let Some(entities) = &self.entities {
+            return Some(FormattedText::from_parts(self.text.as_str(), entities.as_slice()));
+        }
+
+        match self.parse_mode {
+            None => Some(self.text.as_str().into()),
+            Some(ParseMode::HTML) => parse_html(&self.text).ok(),
+            Some(ParseMode::MarkdownV2) => parse_markdown_v2(&self.text).ok(),
+            Some(ParseMode::Markdown) => None,
+        }
+    }
 }
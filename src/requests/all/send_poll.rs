@@ -41,6 +41,8 @@ impl Request for SendPoll {
     }
 }
 
+impl_into_future!(SendPoll);
+
 impl SendPoll {
     pub(crate) fn new<C, Q, O>(bot: Bot, chat_id: C, question: Q, options: O) -> Self
     where
@@ -1,6 +1,10 @@
 use crate::{
     net,
-    requests::{form_builder::FormBuilder, RequestWithFile, ResponseResult},
+    requests::{
+        form_builder::FormBuilder,
+        upload_cache::{self, CachedMedia},
+        FileKind, Request, ResponseResult,
+    },
     types::{ChatId, InputFile, Message, ReplyMarkup},
     Bot,
 };
@@ -21,27 +25,43 @@ pub struct SendSticker {
 }
 
 #[async_trait::async_trait]
-impl RequestWithFile for SendSticker {
+impl Request for SendSticker {
     type Output = Message;
 
-    async fn send(&self) -> tokio::io::Result<ResponseResult<Message>> {
-        Ok(net::request_multipart(
+    async fn send(&self) -> ResponseResult<Message> {
+        upload_cache::send_cached(&self.bot, CachedMedia::Sticker, &self.sticker, |cached| {
+            Box::pin(self.send_with(cached))
+        })
+        .await
+    }
+}
+
+impl SendSticker {
+    /// Sends this request with `cached` instead of `sticker`, if any.
+    async fn send_with(&self, cached: Option<InputFile>) -> ResponseResult<Message> {
+        let sticker = cached.as_ref().unwrap_or(&self.sticker);
+        let limits = self.bot.upload_limits();
+        limits.check(FileKind::Other, sticker).await?;
+
+        net::request_multipart(
             self.bot.client(),
             self.bot.token(),
             "sendSticker",
             FormBuilder::new()
                 .add_text("chat_id", &self.chat_id)
-                .add_input_file("sticker", &self.sticker)
+                .add_input_file("sticker", sticker)
                 .await?
                 .add_text("disable_notification", &self.disable_notification)
                 .add_text("reply_to_message_id", &self.reply_to_message_id)
                 .add_text("reply_markup", &self.reply_markup)
                 .build(),
         )
-        .await)
+        .await
     }
 }
 
+impl_into_future!(SendSticker);
+
 impl SendSticker {
     pub(crate) fn new<C>(bot: Bot, chat_id: C, sticker: InputFile) -> Self
     where
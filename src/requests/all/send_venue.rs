@@ -36,6 +36,8 @@ impl Request for SendVenue {
     }
 }
 
+impl_into_future!(SendVenue);
+
 impl SendVenue {
     pub(crate) fn new<C, T, A>(
         bot: Bot,
@@ -1,7 +1,11 @@
 use crate::{
     net,
-    requests::{form_builder::FormBuilder, RequestWithFile, ResponseResult},
-    types::{ChatId, InputFile, Message, ParseMode, ReplyMarkup},
+    requests::{
+        form_builder::FormBuilder,
+        upload_cache::{self, CachedMedia},
+        FileKind, Request, ResponseResult,
+    },
+    types::{ChatId, InputFile, Message, MessageEntity, ParseMode, ReplyMarkup},
     Bot,
 };
 
@@ -23,6 +27,7 @@ pub struct SendVideo {
     thumb: Option<InputFile>,
     caption: Option<String>,
     parse_mode: Option<ParseMode>,
+    caption_entities: Option<Vec<MessageEntity>>,
     supports_streaming: Option<bool>,
     disable_notification: Option<bool>,
     reply_to_message_id: Option<i32>,
@@ -30,19 +35,37 @@ pub struct SendVideo {
 }
 
 #[async_trait::async_trait]
-impl RequestWithFile for SendVideo {
+impl Request for SendVideo {
     type Output = Message;
 
-    async fn send(&self) -> tokio::io::Result<ResponseResult<Message>> {
+    async fn send(&self) -> ResponseResult<Message> {
+        upload_cache::send_cached(&self.bot, CachedMedia::Video, &self.video, |cached| {
+            Box::pin(self.send_with(cached))
+        })
+        .await
+    }
+}
+
+impl SendVideo {
+    /// Sends this request with `cached` instead of `video`, if any.
+    async fn send_with(&self, cached: Option<InputFile>) -> ResponseResult<Message> {
+        let video = cached.as_ref().unwrap_or(&self.video);
+        let limits = self.bot.upload_limits();
+        limits.check(FileKind::Other, video).await?;
+        if let Some(thumb) = &self.thumb {
+            limits.check(FileKind::Thumb, thumb).await?;
+        }
+
         let mut builder = FormBuilder::new()
             .add_text("chat_id", &self.chat_id)
-            .add_input_file("video", &self.video)
+            .add_input_file("video", video)
             .await?
             .add_text("duration", &self.duration)
             .add_text("width", &self.width)
             .add_text("height", &self.height)
             .add_text("caption", &self.caption)
             .add_text("parse_mode", &self.parse_mode)
+            .add_text("caption_entities", &self.caption_entities)
             .add_text("supports_streaming", &self.supports_streaming)
             .add_text("disable_notification", &self.disable_notification)
             .add_text("reply_to_message_id", &self.reply_to_message_id)
@@ -50,16 +73,13 @@ impl RequestWithFile for SendVideo {
         if let Some(thumb) = self.thumb.as_ref() {
             builder = builder.add_input_file("thumb", thumb).await?;
         }
-        Ok(net::request_multipart(
-            self.bot.client(),
-            self.bot.token(),
-            "sendVideo",
-            builder.build(),
-        )
-        .await)
+        net::request_multipart(self.bot.client(), self.bot.token(), "sendVideo", builder.build())
+            .await
     }
 }
 
+impl_into_future!(SendVideo);
+
 impl SendVideo {
     pub(crate) fn new<C>(bot: Bot, chat_id: C, video: InputFile) -> Self
     where
@@ -75,6 +95,7 @@ impl SendVideo {
             thumb: None,
             caption: None,
             parse_mode: None,
+            caption_entities: None,
             supports_streaming: None,
             disable_notification: None,
             reply_to_message_id: None,
@@ -155,12 +176,28 @@ impl SendVideo {
     /// Send [Markdown] or [HTML], if you want Telegram apps to show
     /// [bold, italic, fixed-width text or inline URLs] in the media caption.
     ///
+    /// Resets `caption_entities`, because they cannot be used together.
+    ///
     /// [Markdown]: crate::types::ParseMode::Markdown
     /// [HTML]: crate::types::ParseMode::HTML
     /// [bold, italic, fixed-width text or inline URLs]:
     /// crate::types::ParseMode
     pub fn parse_mode(mut self, val: ParseMode) -> Self {
         self.parse_mode = Some(val);
+        self.caption_entities = None;
+        self
+    }
+
+    /// List of special entities that appear in the caption, which can be
+    /// specified instead of `parse_mode`.
+    ///
+    /// Resets `parse_mode`, because they cannot be used together.
+    pub fn caption_entities<T>(mut self, val: T) -> Self
+    where
+        T: Into<Vec<MessageEntity>>,
+    {
+        self.caption_entities = Some(val.into());
+        self.parse_mode = None;
         self
     }
 
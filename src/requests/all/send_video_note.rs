@@ -1,6 +1,10 @@
 use crate::{
     net,
-    requests::{form_builder::FormBuilder, RequestWithFile, ResponseResult},
+    requests::{
+        form_builder::FormBuilder,
+        upload_cache::{self, CachedMedia},
+        FileKind, Request, ResponseResult,
+    },
     types::{ChatId, InputFile, Message, ReplyMarkup},
     Bot,
 };
@@ -25,13 +29,30 @@ pub struct SendVideoNote {
 }
 
 #[async_trait::async_trait]
-impl RequestWithFile for SendVideoNote {
+impl Request for SendVideoNote {
     type Output = Message;
 
-    async fn send(&self) -> tokio::io::Result<ResponseResult<Message>> {
+    async fn send(&self) -> ResponseResult<Message> {
+        upload_cache::send_cached(&self.bot, CachedMedia::VideoNote, &self.video_note, |cached| {
+            Box::pin(self.send_with(cached))
+        })
+        .await
+    }
+}
+
+impl SendVideoNote {
+    /// Sends this request with `cached` instead of `video_note`, if any.
+    async fn send_with(&self, cached: Option<InputFile>) -> ResponseResult<Message> {
+        let video_note = cached.as_ref().unwrap_or(&self.video_note);
+        let limits = self.bot.upload_limits();
+        limits.check(FileKind::Other, video_note).await?;
+        if let Some(thumb) = &self.thumb {
+            limits.check(FileKind::Thumb, thumb).await?;
+        }
+
         let mut builder = FormBuilder::new()
             .add_text("chat_id", &self.chat_id)
-            .add_input_file("video_note", &self.video_note)
+            .add_input_file("video_note", video_note)
             .await?
             .add_text("duration", &self.duration)
             .add_text("length", &self.length)
@@ -41,16 +62,18 @@ impl RequestWithFile for SendVideoNote {
         if let Some(thumb) = self.thumb.as_ref() {
             builder = builder.add_input_file("thumb", thumb).await?;
         }
-        Ok(net::request_multipart(
+        net::request_multipart(
             self.bot.client(),
             self.bot.token(),
             "sendVideoNote",
             builder.build(),
         )
-        .await)
+        .await
     }
 }
 
+impl_into_future!(SendVideoNote);
+
 impl SendVideoNote {
     pub(crate) fn new<C>(bot: Bot, chat_id: C, video_note: InputFile) -> Self
     where
@@ -1,7 +1,11 @@
 use crate::{
     net,
-    requests::{form_builder::FormBuilder, RequestWithFile, ResponseResult},
-    types::{ChatId, InputFile, Message, ParseMode, ReplyMarkup},
+    requests::{
+        form_builder::FormBuilder,
+        upload_cache::{self, CachedMedia},
+        FileKind, Request, ResponseResult,
+    },
+    types::{ChatId, InputFile, Message, MessageEntity, ParseMode, ReplyMarkup},
     Bot,
 };
 
@@ -24,6 +28,7 @@ pub struct SendVoice {
     voice: InputFile,
     caption: Option<String>,
     parse_mode: Option<ParseMode>,
+    caption_entities: Option<Vec<MessageEntity>>,
     duration: Option<i32>,
     disable_notification: Option<bool>,
     reply_to_message_id: Option<i32>,
@@ -31,30 +36,47 @@ pub struct SendVoice {
 }
 
 #[async_trait::async_trait]
-impl RequestWithFile for SendVoice {
+impl Request for SendVoice {
     type Output = Message;
 
-    async fn send(&self) -> tokio::io::Result<ResponseResult<Message>> {
-        Ok(net::request_multipart(
+    async fn send(&self) -> ResponseResult<Message> {
+        upload_cache::send_cached(&self.bot, CachedMedia::Voice, &self.voice, |cached| {
+            Box::pin(self.send_with(cached))
+        })
+        .await
+    }
+}
+
+impl SendVoice {
+    /// Sends this request with `cached` instead of `voice`, if any.
+    async fn send_with(&self, cached: Option<InputFile>) -> ResponseResult<Message> {
+        let voice = cached.as_ref().unwrap_or(&self.voice);
+        let limits = self.bot.upload_limits();
+        limits.check(FileKind::Other, voice).await?;
+
+        net::request_multipart(
             self.bot.client(),
             self.bot.token(),
             "sendVoice",
             FormBuilder::new()
                 .add_text("chat_id", &self.chat_id)
-                .add_input_file("voice", &self.voice)
+                .add_input_file("voice", voice)
                 .await?
                 .add_text("caption", &self.caption)
                 .add_text("parse_mode", &self.parse_mode)
+                .add_text("caption_entities", &self.caption_entities)
                 .add_text("duration", &self.duration)
                 .add_text("disable_notification", &self.disable_notification)
                 .add_text("reply_to_message_id", &self.reply_to_message_id)
                 .add_text("reply_markup", &self.reply_markup)
                 .build(),
         )
-        .await)
+        .await
     }
 }
 
+impl_into_future!(SendVoice);
+
 impl SendVoice {
     pub(crate) fn new<C>(bot: Bot, chat_id: C, voice: InputFile) -> Self
     where
@@ -66,6 +88,7 @@ impl SendVoice {
             voice,
             caption: None,
             parse_mode: None,
+            caption_entities: None,
             duration: None,
             disable_notification: None,
             reply_to_message_id: None,
@@ -111,12 +134,28 @@ impl SendVoice {
     /// Send [Markdown] or [HTML], if you want Telegram apps to show
     /// [bold, italic, fixed-width text or inline URLs] in the media caption.
     ///
+    /// Resets `caption_entities`, because they cannot be used together.
+    ///
     /// [Markdown]: crate::types::ParseMode::Markdown
     /// [HTML]: crate::types::ParseMode::HTML
     /// [bold, italic, fixed-width text or inline URLs]:
     /// crate::types::ParseMode
     pub fn parse_mode(mut self, val: ParseMode) -> Self {
         self.parse_mode = Some(val);
+        self.caption_entities = None;
+        self
+    }
+
+    /// List of special entities that appear in the caption, which can be
+    /// specified instead of `parse_mode`.
+    ///
+    /// Resets `parse_mode`, because they cannot be used together.
+    pub fn caption_entities<T>(mut self, val: T) -> Self
+    where
+        T: Into<Vec<MessageEntity>>,
+    {
+        self.caption_entities = Some(val.into());
+        self.parse_mode = None;
         self
     }
 
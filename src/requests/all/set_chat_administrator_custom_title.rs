@@ -36,6 +36,8 @@ impl Request for SetChatAdministratorCustomTitle {
     }
 }
 
+impl_into_future!(SetChatAdministratorCustomTitle);
+
 impl SetChatAdministratorCustomTitle {
     pub(crate) fn new<C, CT>(bot: Bot, chat_id: C, user_id: i32, custom_title: CT) -> Self
     where
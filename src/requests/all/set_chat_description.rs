@@ -32,6 +32,8 @@ impl Request for SetChatDescription {
     }
 }
 
+impl_into_future!(SetChatDescription);
+
 impl SetChatDescription {
     pub(crate) fn new<C>(bot: Bot, chat_id: C) -> Self
     where
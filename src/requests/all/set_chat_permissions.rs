@@ -31,6 +31,8 @@ impl Request for SetChatPermissions {
     }
 }
 
+impl_into_future!(SetChatPermissions);
+
 impl SetChatPermissions {
     pub(crate) fn new<C>(bot: Bot, chat_id: C, permissions: ChatPermissions) -> Self
     where
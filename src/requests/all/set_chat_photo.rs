@@ -31,6 +31,8 @@ impl Request for SetChatPhoto {
     }
 }
 
+impl_into_future!(SetChatPhoto);
+
 impl SetChatPhoto {
     pub(crate) fn new<C>(bot: Bot, chat_id: C, photo: InputFile) -> Self
     where
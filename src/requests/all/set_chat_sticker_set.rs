@@ -32,6 +32,8 @@ impl Request for SetChatStickerSet {
     }
 }
 
+impl_into_future!(SetChatStickerSet);
+
 impl SetChatStickerSet {
     pub(crate) fn new<C, S>(bot: Bot, chat_id: C, sticker_set_name: S) -> Self
     where
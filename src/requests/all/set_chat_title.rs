@@ -31,6 +31,8 @@ impl Request for SetChatTitle {
     }
 }
 
+impl_into_future!(SetChatTitle);
+
 impl SetChatTitle {
     pub(crate) fn new<C, T>(bot: Bot, chat_id: C, title: T) -> Self
     where
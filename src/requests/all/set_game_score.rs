@@ -40,6 +40,8 @@ impl Request for SetGameScore {
     }
 }
 
+impl_into_future!(SetGameScore);
+
 impl SetGameScore {
     pub(crate) fn new(
         bot: Bot,
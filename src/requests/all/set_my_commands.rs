@@ -28,6 +28,8 @@ impl Request for SetMyCommands {
     }
 }
 
+impl_into_future!(SetMyCommands);
+
 impl SetMyCommands {
     pub(crate) fn new<C>(bot: Bot, commands: C) -> Self
     where
@@ -0,0 +1,65 @@
+use serde::Serialize;
+
+use crate::{
+    net,
+    requests::{Request, ResponseResult},
+    types::{PassportElementError, True},
+    Bot,
+};
+
+/// Informs a user that some of the Telegram Passport elements they provided
+/// contains errors.
+///
+/// The user will not be able to re-submit their Passport to you until the
+/// errors are fixed (the contents of the field for which you returned the
+/// error must change).
+///
+/// Use this if the data submitted by the user doesn't satisfy the standards
+/// your service requires for any reason. For example, if a birthday date
+/// seems invalid, a submitted document is blurry, a scan shows evidence of
+/// tampering, etc. Supply some details in the error message to make sure the
+/// user knows how to correct the issues.
+///
+/// [The official docs](https://core.telegram.org/bots/api#setpassportdataerrors).
+#[derive(Debug, Clone, Serialize)]
+pub struct SetPassportDataErrors {
+    #[serde(skip_serializing)]
+    bot: Bot,
+    user_id: i32,
+    errors: Vec<PassportElementError>,
+}
+
+#[async_trait::async_trait]
+impl Request for SetPassportDataErrors {
+    type Output = True;
+
+    async fn send(&self) -> ResponseResult<True> {
+        net::request_json(self.bot.client(), self.bot.token(), "setPassportDataErrors", &self).await
+    }
+}
+
+impl_into_future!(SetPassportDataErrors);
+
+impl SetPassportDataErrors {
+    pub(crate) fn new<E>(bot: Bot, user_id: i32, errors: E) -> Self
+    where
+        E: Into<Vec<PassportElementError>>,
+    {
+        Self { bot, user_id, errors: errors.into() }
+    }
+
+    /// User identifier.
+    pub fn user_id(mut self, val: i32) -> Self {
+        self.user_id = val;
+        self
+    }
+
+    /// A JSON-serialized array describing the errors.
+    pub fn errors<E>(mut self, val: E) -> Self
+    where
+        E: Into<Vec<PassportElementError>>,
+    {
+        self.errors = val.into();
+        self
+    }
+}
@@ -30,6 +30,8 @@ impl Request for SetStickerPositionInSet {
     }
 }
 
+impl_into_future!(SetStickerPositionInSet);
+
 impl SetStickerPositionInSet {
     pub(crate) fn new<S>(bot: Bot, sticker: S, position: i32) -> Self
     where
@@ -30,6 +30,8 @@ impl Request for SetStickerSetThumb {
     }
 }
 
+impl_into_future!(SetStickerSetThumb);
+
 impl SetStickerSetThumb {
     pub(crate) fn new<S>(bot: Bot, name: S, user_id: i32) -> Self
     where
@@ -1,8 +1,6 @@
-use serde::Serialize;
-
 use crate::{
     net,
-    requests::{Request, ResponseResult},
+    requests::{form_builder::FormBuilder, Request, ResponseResult},
     types::{AllowedUpdate, InputFile, True},
     Bot,
 };
@@ -23,10 +21,8 @@ use crate::{
 /// [The official docs](https://core.telegram.org/bots/api#setwebhook).
 ///
 /// [`Update`]: crate::types::Update
-#[serde_with_macros::skip_serializing_none]
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone)]
 pub struct SetWebhook {
-    #[serde(skip_serializing)]
     bot: Bot,
     url: String,
     certificate: Option<InputFile>,
@@ -39,10 +35,26 @@ impl Request for SetWebhook {
     type Output = True;
 
     async fn send(&self) -> ResponseResult<True> {
-        net::request_json(self.bot.client(), self.bot.token(), "setWebhook", &self).await
+        let mut builder = FormBuilder::new().add_text("url", &self.url);
+        if let Some(certificate) = &self.certificate {
+            builder = builder.add_input_file("certificate", certificate).await?;
+        }
+
+        net::request_multipart(
+            self.bot.client(),
+            self.bot.token(),
+            "setWebhook",
+            builder
+                .add_text("max_connections", &self.max_connections)
+                .add_text("allowed_updates", &self.allowed_updates)
+                .build(),
+        )
+        .await
     }
 }
 
+impl_into_future!(SetWebhook);
+
 impl SetWebhook {
     pub(crate) fn new<U>(bot: Bot, url: U) -> Self
     where
@@ -37,6 +37,8 @@ impl Request for StopMessageLiveLocation {
     }
 }
 
+impl_into_future!(StopMessageLiveLocation);
+
 impl StopMessageLiveLocation {
     pub(crate) fn new(bot: Bot, chat_or_inline_message: ChatOrInlineMessage) -> Self {
         Self { bot, chat_or_inline_message, reply_markup: None }
@@ -31,6 +31,8 @@ impl Request for StopPoll {
         net::request_json(self.bot.client(), self.bot.token(), "stopPoll", &self).await
     }
 }
+
+impl_into_future!(StopPoll);
 impl StopPoll {
     pub(crate) fn new<C>(bot: Bot, chat_id: C, message_id: i32) -> Self
     where
@@ -31,6 +31,8 @@ impl Request for UnbanChatMember {
     }
 }
 
+impl_into_future!(UnbanChatMember);
+
 impl UnbanChatMember {
     pub(crate) fn new<C>(bot: Bot, chat_id: C, user_id: i32) -> Self
     where
@@ -31,6 +31,8 @@ impl Request for UnpinChatMessage {
     }
 }
 
+impl_into_future!(UnpinChatMessage);
+
 impl UnpinChatMessage {
     pub(crate) fn new<C>(bot: Bot, chat_id: C) -> Self
     where
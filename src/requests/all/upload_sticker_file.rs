@@ -1,8 +1,6 @@
-use serde::Serialize;
-
 use crate::{
     net,
-    requests::{Request, ResponseResult},
+    requests::{form_builder::FormBuilder, FileKind, Request, ResponseResult},
     types::{File, InputFile},
     Bot,
 };
@@ -15,10 +13,8 @@ use crate::{
 ///
 /// [`Bot::create_new_sticker_set`]: crate::Bot::create_new_sticker_set
 /// [`Bot::add_sticker_to_set`]: crate::Bot::add_sticker_to_set
-#[serde_with_macros::skip_serializing_none]
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone)]
 pub struct UploadStickerFile {
-    #[serde(skip_serializing)]
     bot: Bot,
     user_id: i32,
     png_sticker: InputFile,
@@ -28,10 +24,25 @@ impl Request for UploadStickerFile {
     type Output = File;
 
     async fn send(&self) -> ResponseResult<File> {
-        net::request_json(self.bot.client(), self.bot.token(), "uploadStickerFile", &self).await
+        let limits = self.bot.upload_limits();
+        limits.check(FileKind::PngSticker, &self.png_sticker).await?;
+
+        net::request_multipart(
+            self.bot.client(),
+            self.bot.token(),
+            "uploadStickerFile",
+            FormBuilder::new()
+                .add_text("user_id", &self.user_id)
+                .add_input_file("png_sticker", &self.png_sticker)
+                .await?
+                .build(),
+        )
+        .await
     }
 }
 
+impl_into_future!(UploadStickerFile);
+
 impl UploadStickerFile {
     pub(crate) fn new(bot: Bot, user_id: i32, png_sticker: InputFile) -> Self {
         Self { bot, user_id, png_sticker }
@@ -3,9 +3,10 @@ use std::{borrow::Cow, path::PathBuf};
 use reqwest::multipart::Form;
 
 use crate::{
-    requests::utils::{file_from_memory_to_part, file_to_part},
+    requests::utils::{file_from_memory_to_part, file_to_part, stream_to_part},
     types::{
-        ChatId, InlineKeyboardMarkup, InputFile, InputMedia, MaskPosition, ParseMode, ReplyMarkup,
+        AllowedUpdate, ChatId, InlineKeyboardMarkup, InputFile, InputMedia, MaskPosition,
+        MessageEntity, ParseMode, ReplyMarkup,
     },
 };
 
@@ -42,10 +43,59 @@ impl FormBuilder {
             }
             InputFile::Url(url) => self.add_text(name, url),
             InputFile::FileId(file_id) => self.add_text(name, file_id),
+            InputFile::Stream { file_name, stream } => Self {
+                form: self
+                    .form
+                    .part(name.into().into_owned(), stream_to_part(stream, file_name.clone())?),
+            },
         })
     }
 
-    // used in SendMediaGroup
+    /// Adds `media` as JSON, attaching all its files to be uploaded.
+    pub async fn add_input_media<'a, N>(
+        self,
+        name: N,
+        media: &InputMedia,
+    ) -> tokio::io::Result<Self>
+    where
+        N: Into<Cow<'a, str>>,
+    {
+        let mut media = media.clone();
+        let builder = self.attach_files(media.files_mut()).await?;
+        Ok(builder.add_text(name, &media))
+    }
+
+    /// Adds a group of `media` as JSON, attaching all its files to be
+    /// uploaded.
+    pub async fn add_input_media_group<'a, N>(
+        self,
+        name: N,
+        media: &[InputMedia],
+    ) -> tokio::io::Result<Self>
+    where
+        N: Into<Cow<'a, str>>,
+    {
+        let mut media = media.to_vec();
+        let builder = self.attach_files(media.iter_mut().flat_map(InputMedia::files_mut)).await?;
+        Ok(builder.add_text(name, &media))
+    }
+
+    /// Adds a part for every file to be uploaded, replacing it with
+    /// `attach://<name>` of the part.
+    async fn attach_files<'f, I>(mut self, files: I) -> tokio::io::Result<Self>
+    where
+        I: IntoIterator<Item = &'f mut InputFile>,
+    {
+        let files: Vec<_> = files.into_iter().filter(|file| file.is_upload()).collect();
+        for (i, file) in files.into_iter().enumerate() {
+            let name = format!("file{}", i);
+            self = self.add_input_file(name.as_str(), file).await?;
+            // `FileId` is serialized as is.
+            *file = InputFile::FileId(format!("attach://{}", name));
+        }
+        Ok(self)
+    }
+
     pub async fn add_file<'a, N>(self, name: N, path_to_file: PathBuf) -> tokio::io::Result<Self>
     where
         N: Into<Cow<'a, str>>,
@@ -94,7 +144,19 @@ macro_rules! impl_for_struct {
     };
 }
 
-impl_for_struct!(bool, i32, i64, u32, ReplyMarkup, InlineKeyboardMarkup, MaskPosition);
+impl_for_struct!(
+    bool,
+    i32,
+    i64,
+    u32,
+    ReplyMarkup,
+    InlineKeyboardMarkup,
+    MaskPosition,
+    Vec<MessageEntity>,
+    Vec<AllowedUpdate>,
+    InputMedia,
+    Vec<InputMedia>
+);
 
 impl<T> IntoFormText for Option<T>
 where
@@ -105,22 +167,6 @@ where
     }
 }
 
-// TODO: fix InputMedia implementation of IntoFormValue (for now it doesn't
-// encode files :|)
-impl IntoFormText for Vec<InputMedia> {
-    fn into_form_text(&self) -> Option<String> {
-        let json = serde_json::to_string(self).expect("serde_json::to_string failed");
-        Some(json)
-    }
-}
-
-impl IntoFormText for InputMedia {
-    fn into_form_text(&self) -> Option<String> {
-        let json = serde_json::to_string(self).expect("serde_json::to_string failed");
-        Some(json)
-    }
-}
-
 impl IntoFormText for str {
     fn into_form_text(&self) -> Option<String> {
         Some(self.to_owned())
@@ -154,3 +200,65 @@ impl IntoFormText for String {
         Some(self.clone())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{InputMediaPhoto, InputMediaVideo};
+    use std::net::Ipv4Addr;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    /// Returns a body of a request with `form`, received by a local server.
+    async fn body(form: Form) -> String {
+        let mut listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let end = format!("--{}--", form.boundary());
+
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            while !String::from_utf8_lossy(&request).contains(&end) {
+                let mut buf = [0; 1024];
+                let n = stream.read(&mut buf).await.unwrap();
+                assert_ne!(n, 0, "unexpected end of a request");
+                request.extend_from_slice(&buf[..n]);
+            }
+            stream.write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n").await.unwrap();
+            String::from_utf8(request).unwrap()
+        });
+
+        reqwest::Client::new().post(&url).multipart(form).send().await.unwrap();
+        server.await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn input_media_group() {
+        let media = [
+            InputMedia::Video(
+                InputMediaVideo::new(InputFile::memory("video.mp4", &b"video"[..]))
+                    .thumb(InputFile::memory("thumb.jpg", &b"thumb"[..])),
+            ),
+            InputMedia::Photo(InputMediaPhoto::new(InputFile::file_id("123456"))),
+            InputMedia::Photo(InputMediaPhoto::new(InputFile::memory("photo.jpg", &b"photo"[..]))),
+        ];
+        let form = FormBuilder::new().add_input_media_group("media", &media).await.unwrap().build();
+        let body = body(form).await;
+
+        for (name, file_name, data) in &[
+            ("file0", "video.mp4", "video"),
+            ("file1", "thumb.jpg", "thumb"),
+            ("file2", "photo.jpg", "photo"),
+        ] {
+            let part = format!("name=\"{}\"; filename=\"{}\"", name, file_name);
+            assert!(body.contains(&part), "no part {} in {}", name, body);
+            assert!(body.contains(&format!("\r\n\r\n{}\r\n--", data)));
+        }
+        assert!(!body.contains("name=\"file3\""));
+        assert!(body.contains(
+            r#"[{"type":"video","media":"attach://file0","thumb":"attach://file1"},{"type":"photo","media":"123456"},{"type":"photo","media":"attach://file2"}]"#
+        ));
+    }
+}
@@ -1,10 +1,51 @@
 //! API requests.
+//!
+//! Every request implements [`Request`] and [`IntoFuture`], so it can be sent
+//! either by [`Request::send`] (without consuming the request) or just by
+//! `.await`:
+//!
+//! ```no_run
+//! use teloxide::prelude::*;
+//!
+//! # async fn run() -> ResponseResult<()> {
+//! let bot = Bot::from_env();
+//! let message = bot.send_message(0, "Hi!").await?;
+//! bot.send_message(0, "Hi again!").reply_to_message_id(message.id).await?;
+//! # Ok(()) }
+//! ```
+//!
+//! [`Request`]: crate::requests::Request
+//! [`IntoFuture`]: std::future::IntoFuture
+//! [`Request::send`]: crate::requests::Request::send
+
+/// Implements [`IntoFuture`] for a request, sending it by `.await`.
+///
+/// [`IntoFuture`]: std::future::IntoFuture
+macro_rules! impl_into_future {
+    ($request:ty) => {
+        impl std::future::IntoFuture for $request {
+            type Output =
+                crate::requests::ResponseResult<<$request as crate::requests::Request>::Output>;
+            type IntoFuture = futures::future::BoxFuture<'static, Self::Output>;
+
+            fn into_future(self) -> Self::IntoFuture {
+                Box::pin(async move { crate::requests::Request::send(&self).await })
+            }
+        }
+    };
+}
+
+pub mod upload_cache;
 
 mod all;
 mod form_builder;
 mod utils;
+mod validation;
 
 pub use all::*;
+pub use validation::UploadLimits;
+
+pub(crate) use validation::FileKind;
 
 /// A type that is returned after making a request to Telegram.
 pub type ResponseResult<T> = Result<T, crate::RequestError>;
@@ -21,17 +62,10 @@ pub trait Request {
     type Output;
 
     /// Asynchronously sends this request to Telegram and returns the result.
+    ///
+    /// Files are read while sending, so failing to read them results in
+    /// [`RequestError::Io`].
+    ///
+    /// [`RequestError::Io`]: crate::RequestError::Io
     async fn send(&self) -> ResponseResult<Self::Output>;
 }
-
-/// Designates an API request with possibly sending file.
-#[async_trait::async_trait]
-pub trait RequestWithFile {
-    /// A data structure returned if success.
-    type Output;
-
-    /// Asynchronously sends this request to Telegram and returns the result.
-    /// Returns `tokio::io::Result::Err` when trying to send file which does not
-    /// exists.
-    async fn send(&self) -> tokio::io::Result<ResponseResult<Self::Output>>;
-}
@@ -0,0 +1,351 @@
+//! Reusing `file_id`s of uploaded files.
+//!
+//! Once a file is uploaded, Telegram returns its `file_id`, which can be sent
+//! instead of the file. If a [`Bot`] is built with an [`UploadCache`] (see
+//! [`BotBuilder::upload_cache`]), [`InputFile::File`] and
+//! [`InputFile::Memory`] are uploaded only once by [`SendPhoto`],
+//! [`SendDocument`], [`SendVideo`], [`SendAudio`], [`SendAnimation`],
+//! [`SendVoice`], [`SendVideoNote`] and [`SendSticker`]: after a successful
+//! upload, a `file_id` of the file is saved and then sent instead.
+//!
+//! A file on a disk is identified by its path, size and modification time, a
+//! file in memory -- by its name and a SHA-256 hash of its data. If Telegram
+//! doesn't accept a saved `file_id` anymore, it's removed from the cache and
+//! the file is uploaded again.
+//!
+//! # Example
+//! ```no_run
+//! use teloxide::{
+//!     prelude::*, requests::upload_cache::InMemUploadCache, types::InputFile, BotBuilder,
+//! };
+//!
+//! # async fn run() -> ResponseResult<()> {
+//! let bot = BotBuilder::new().upload_cache(InMemUploadCache::new()).build();
+//!
+//! // Uploads the file...
+//! bot.send_photo(1, InputFile::file("logo.png")).await?;
+//! // ...and sends only its `file_id`.
+//! bot.send_photo(2, InputFile::file("logo.png")).await?;
+//! # Ok(()) }
+//! ```
+//!
+//! [`Bot`]: crate::Bot
+//! [`UploadCache`]: crate::requests::upload_cache::UploadCache
+//! [`BotBuilder::upload_cache`]: crate::BotBuilder::upload_cache
+//! [`InputFile::File`]: crate::types::InputFile::File
+//! [`InputFile::Memory`]: crate::types::InputFile::Memory
+//! [`SendPhoto`]: crate::requests::SendPhoto
+//! [`SendDocument`]: crate::requests::SendDocument
+//! [`SendVideo`]: crate::requests::SendVideo
+//! [`SendAudio`]: crate::requests::SendAudio
+//! [`SendAnimation`]: crate::requests::SendAnimation
+//! [`SendVoice`]: crate::requests::SendVoice
+//! [`SendVideoNote`]: crate::requests::SendVideoNote
+//! [`SendSticker`]: crate::requests::SendSticker
+
+use std::{
+    collections::HashMap,
+    fmt::{self, Debug},
+    sync::Arc,
+    time::UNIX_EPOCH,
+};
+
+use futures::future::BoxFuture;
+use sha2::{Digest, Sha256};
+use tokio::sync::Mutex;
+
+use crate::{
+    requests::ResponseResult,
+    types::{InputFile, Message},
+    ApiErrorKind, Bot, KnownApiErrorKind, RequestError,
+};
+
+/// A store of `file_id`s of uploaded files.
+///
+/// Keys are built from files, see the [module-level documentation].
+///
+/// A cache is only an optimization, so its methods don't return errors: if a
+/// `file_id` can't be loaded or saved, a file is just uploaded again. Log
+/// errors of your storage inside an implementation.
+///
+/// [module-level documentation]: crate::requests::upload_cache
+pub trait UploadCache: Send + Sync {
+    /// Returns a `file_id` saved with the `key`.
+    fn get_file_id(self: Arc<Self>, key: String) -> BoxFuture<'static, Option<String>>;
+
+    /// Saves a `file_id` with the `key`.
+    fn save_file_id(self: Arc<Self>, key: String, file_id: String) -> BoxFuture<'static, ()>;
+
+    /// Removes a `file_id` saved with the `key`.
+    fn remove_file_id(self: Arc<Self>, key: String) -> BoxFuture<'static, ()>;
+}
+
+impl Debug for dyn UploadCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("UploadCache")
+    }
+}
+
+/// An upload cache based on a hash map. Stores all the `file_id`s directly in
+/// RAM.
+///
+/// ## Note
+/// All the `file_id`s will be lost after you restart your bot, so every file
+/// will be uploaded once again.
+#[derive(Debug)]
+pub struct InMemUploadCache {
+    map: Mutex<HashMap<String, String>>,
+}
+
+impl InMemUploadCache {
+    #[must_use]
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self { map: Mutex::new(HashMap::new()) })
+    }
+}
+
+impl UploadCache for InMemUploadCache {
+    fn get_file_id(self: Arc<Self>, key: String) -> BoxFuture<'static, Option<String>> {
+        Box::pin(async move { self.map.lock().await.get(&key).cloned() })
+    }
+
+    fn save_file_id(self: Arc<Self>, key: String, file_id: String) -> BoxFuture<'static, ()> {
+        Box::pin(async move {
+            self.map.lock().await.insert(key, file_id);
+        })
+    }
+
+    fn remove_file_id(self: Arc<Self>, key: String) -> BoxFuture<'static, ()> {
+        Box::pin(async move {
+            self.map.lock().await.remove(&key);
+        })
+    }
+}
+
+/// A kind of media, which file can be cached.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum CachedMedia {
+    Photo,
+    Document,
+    Video,
+    Audio,
+    Animation,
+    Voice,
+    VideoNote,
+    Sticker,
+}
+
+impl CachedMedia {
+    fn name(self) -> &'static str {
+        match self {
+            CachedMedia::Photo => "photo",
+            CachedMedia::Document => "document",
+            CachedMedia::Video => "video",
+            CachedMedia::Audio => "audio",
+            CachedMedia::Animation => "animation",
+            CachedMedia::Voice => "voice",
+            CachedMedia::VideoNote => "video_note",
+            CachedMedia::Sticker => "sticker",
+        }
+    }
+
+    /// Returns a `file_id` of this media in `message`.
+    fn file_id(self, message: &Message) -> Option<&str> {
+        let file_id = match self {
+            CachedMedia::Photo => &message.photo()?.iter().max_by_key(|size| size.width)?.file_id,
+            CachedMedia::Document => &message.document()?.file_id,
+            CachedMedia::Video => &message.video()?.file_id,
+            CachedMedia::Audio => &message.audio()?.file_id,
+            CachedMedia::Animation => &message.animation()?.file_id,
+            CachedMedia::Voice => &message.voice()?.file_id,
+            CachedMedia::VideoNote => &message.video_note()?.file_id,
+            CachedMedia::Sticker => &message.sticker()?.file_id,
+        };
+        Some(file_id)
+    }
+}
+
+/// Builds a key of `file` sent as `media`, if it can be cached.
+async fn cache_key(media: CachedMedia, file: &InputFile) -> Option<String> {
+    match file {
+        InputFile::File(path) => {
+            let path = tokio::fs::canonicalize(path).await.ok()?;
+            let metadata = tokio::fs::metadata(&path).await.ok()?;
+            let modified = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+            Some(format!(
+                "{}:file:{}:{}:{}",
+                media.name(),
+                metadata.len(),
+                modified.as_nanos(),
+                path.display()
+            ))
+        }
+        InputFile::Memory { file_name, data } => {
+            Some(format!("{}:memory:{:x}:{}", media.name(), Sha256::digest(data), file_name))
+        }
+        _ => None,
+    }
+}
+
+/// Sends `file` as `media` by `send`, replacing it with a cached `file_id` if
+/// possible.
+///
+/// `send` is called with a file to be sent instead of `file` or `None`.
+pub(crate) async fn send_cached<'a, F>(
+    bot: &Bot,
+    media: CachedMedia,
+    file: &InputFile,
+    send: F,
+) -> ResponseResult<Message>
+where
+    F: Fn(Option<InputFile>) -> BoxFuture<'a, ResponseResult<Message>>,
+{
+    let cache = match bot.upload_cache() {
+        Some(cache) => cache,
+        None => return send(None).await,
+    };
+    let key = match cache_key(media, file).await {
+        Some(key) => key,
+        None => return send(None).await,
+    };
+
+    if let Some(file_id) = Arc::clone(cache).get_file_id(key.clone()).await {
+        match send(Some(InputFile::FileId(file_id))).await {
+            Err(RequestError::ApiError {
+                kind:
+                    ApiErrorKind::Known(KnownApiErrorKind::WrongFileID)
+                    | ApiErrorKind::Known(KnownApiErrorKind::FileIdInvalid),
+                ..
+            }) => Arc::clone(cache).remove_file_id(key.clone()).await,
+            res => return res,
+        }
+    }
+
+    let message = send(None).await?;
+    if let Some(file_id) = media.file_id(&message) {
+        Arc::clone(cache).save_file_id(key, file_id.to_owned()).await;
+    }
+    Ok(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BotBuilder;
+    use reqwest::StatusCode;
+
+    /// A cache of one `file_id`, which logs calls.
+    #[derive(Default)]
+    struct MockCache {
+        file_id: std::sync::Mutex<Option<String>>,
+        calls: std::sync::Mutex<Vec<String>>,
+    }
+
+    impl UploadCache for MockCache {
+        fn get_file_id(self: Arc<Self>, _key: String) -> BoxFuture<'static, Option<String>> {
+            self.calls.lock().unwrap().push("get".to_owned());
+            let file_id = self.file_id.lock().unwrap().clone();
+            Box::pin(async move { file_id })
+        }
+
+        fn save_file_id(self: Arc<Self>, _key: String, file_id: String) -> BoxFuture<'static, ()> {
+            self.calls.lock().unwrap().push(format!("save {}", file_id));
+            *self.file_id.lock().unwrap() = Some(file_id);
+            Box::pin(async {})
+        }
+
+        fn remove_file_id(self: Arc<Self>, _key: String) -> BoxFuture<'static, ()> {
+            self.calls.lock().unwrap().push("remove".to_owned());
+            *self.file_id.lock().unwrap() = None;
+            Box::pin(async {})
+        }
+    }
+
+    fn message(file_id: &str) -> Message {
+        let json = format!(
+            r#"{{
+                "message_id": 1,
+                "date": 0,
+                "chat": {{ "id": 1, "type": "private" }},
+                "document": {{ "file_id": "{}", "file_unique_id": "" }}
+            }}"#,
+            file_id
+        );
+        serde_json::from_str(&json).unwrap()
+    }
+
+    fn api_error(kind: KnownApiErrorKind) -> RequestError {
+        RequestError::ApiError {
+            status_code: StatusCode::BAD_REQUEST,
+            kind: ApiErrorKind::Known(kind),
+        }
+    }
+
+    /// Sends a document with a cache containing `file_id`, where `wrong` and
+    /// `invalid` are rejected by Telegram, and returns sent files and calls of
+    /// the cache.
+    async fn send(file_id: Option<&str>) -> (Vec<Option<InputFile>>, Vec<String>) {
+        let cache = Arc::new(MockCache::default());
+        *cache.file_id.lock().unwrap() = file_id.map(ToOwned::to_owned);
+        let bot = BotBuilder::new().token("token").upload_cache(Arc::clone(&cache)).build();
+
+        let sent = std::sync::Mutex::new(Vec::new());
+        let file = InputFile::memory("doc.txt", &b"doc"[..]);
+        send_cached(&bot, CachedMedia::Document, &file, |file| {
+            sent.lock().unwrap().push(file.clone());
+            let res = match file {
+                Some(InputFile::FileId(file_id)) => match file_id.as_str() {
+                    "wrong" => Err(api_error(KnownApiErrorKind::WrongFileID)),
+                    "invalid" => Err(api_error(KnownApiErrorKind::FileIdInvalid)),
+                    _ => Ok(message(&file_id)),
+                },
+                _ => Ok(message("uploaded")),
+            };
+            Box::pin(async { res })
+        })
+        .await
+        .unwrap();
+
+        let calls = cache.calls.lock().unwrap().clone();
+        (sent.into_inner().unwrap(), calls)
+    }
+
+    #[tokio::test]
+    async fn send_cached_hit() {
+        let (sent, calls) = send(Some("cached")).await;
+        assert_eq!(sent, vec![Some(InputFile::file_id("cached"))]);
+        assert_eq!(calls, vec!["get"]);
+    }
+
+    #[tokio::test]
+    async fn send_cached_miss() {
+        let (sent, calls) = send(None).await;
+        assert_eq!(sent, vec![None]);
+        assert_eq!(calls, vec!["get", "save uploaded"]);
+    }
+
+    #[tokio::test]
+    async fn send_cached_stale() {
+        for file_id in &["wrong", "invalid"] {
+            let (sent, calls) = send(Some(file_id)).await;
+            assert_eq!(sent, vec![Some(InputFile::file_id(*file_id)), None]);
+            assert_eq!(calls, vec!["get", "remove", "save uploaded"]);
+        }
+    }
+
+    #[tokio::test]
+    async fn keys() {
+        let logo = InputFile::memory("logo.png", &b"logo"[..]);
+        let key = cache_key(CachedMedia::Photo, &logo).await.unwrap();
+
+        assert_eq!(key, cache_key(CachedMedia::Photo, &logo.clone()).await.unwrap());
+        assert_ne!(key, cache_key(CachedMedia::Document, &logo).await.unwrap());
+        assert_ne!(
+            key,
+            cache_key(CachedMedia::Photo, &InputFile::memory("logo.png", &b"lOgo"[..]))
+                .await
+                .unwrap()
+        );
+        assert_eq!(cache_key(CachedMedia::Photo, &InputFile::file_id("123456")).await, None);
+    }
+}
@@ -1,9 +1,18 @@
-use std::{borrow::Cow, path::PathBuf};
+use std::{
+    borrow::Cow,
+    path::PathBuf,
+    pin::Pin,
+    sync::Mutex,
+    task::{Context, Poll},
+};
 
 use bytes::{Bytes, BytesMut};
+use futures::{Stream, StreamExt};
 use reqwest::{multipart::Part, Body};
 use tokio_util::codec::{Decoder, FramedRead};
 
+use crate::types::InputStream;
+
 struct FileDecoder;
 
 impl Decoder for FileDecoder {
@@ -29,3 +38,48 @@ pub async fn file_to_part(path_to_file: PathBuf) -> std::io::Result<Part> {
 pub fn file_from_memory_to_part(data: Cow<'static, [u8]>, name: String) -> Part {
     Part::bytes(data).file_name(name)
 }
+
+pub fn stream_to_part(stream: &InputStream, file_name: String) -> std::io::Result<Part> {
+    let (reader, len, progress) = stream.take().ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "the stream has been already uploaded",
+        )
+    })?;
+
+    let mut sent = 0;
+    let chunks = FramedRead::new(reader, FileDecoder).inspect(move |chunk| {
+        if let (Ok(chunk), Some(progress)) = (chunk, &progress) {
+            sent += chunk.len() as u64;
+            progress(sent, len);
+        }
+    });
+    let body = Body::wrap_stream(SyncStream(Mutex::new(chunks)));
+
+    // The length must be exact, see `InputStream::with_len`.
+    let part = match len {
+        Some(len) => Part::stream_with_length(body, len),
+        None => Part::stream(body),
+    };
+    Ok(part.file_name(file_name))
+}
+
+/// Makes a `Send` stream `Sync` as required by [`Body::wrap_stream`].
+///
+/// The mutex is never locked, because the stream is polled by a mutable
+/// reference.
+///
+/// [`Body::wrap_stream`]: reqwest::Body::wrap_stream
+struct SyncStream<S>(Mutex<S>);
+
+impl<S> Stream for SyncStream<S>
+where
+    S: Stream + Unpin,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let stream = self.get_mut().0.get_mut().unwrap_or_else(|err| err.into_inner());
+        Pin::new(stream).poll_next(cx)
+    }
+}
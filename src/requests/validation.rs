@@ -0,0 +1,269 @@
+use mime::Mime;
+use tokio::io::AsyncReadExt;
+
+use crate::{
+    errors::InvalidFileError,
+    types::{InputFile, InputMedia, MimeWrapper},
+};
+
+const MB: u64 = 1024 * 1024;
+
+/// Limits of uploaded files, checked before sending a request.
+///
+/// Telegram rejects some files (e.g. too large ones or thumbnails which
+/// aren't JPEG) only after they are uploaded, so [`Bot`] checks sizes and
+/// types of [`InputFile::File`], [`InputFile::Memory`] and
+/// [`InputFile::Stream`] (with a known length) beforehand and returns
+/// [`RequestError::InvalidFile`] without sending anything. Types are detected
+/// by the first bytes of files, so unknown ones are never rejected.
+///
+/// The default limits are those of the Telegram's Bot API server. Use
+/// [`UploadLimits::local_server`] with a [local Bot API server] or
+/// [`UploadLimits::unchecked`] to disable the checks.
+///
+/// [`Bot`]: crate::Bot
+/// [`InputFile::File`]: crate::types::InputFile::File
+/// [`InputFile::Memory`]: crate::types::InputFile::Memory
+/// [`InputFile::Stream`]: crate::types::InputFile::Stream
+/// [`RequestError::InvalidFile`]: crate::RequestError::InvalidFile
+/// [`UploadLimits::local_server`]: crate::requests::UploadLimits::local_server
+/// [`UploadLimits::unchecked`]: crate::requests::UploadLimits::unchecked
+/// [local Bot API server]: https://github.com/tdlib/telegram-bot-api
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct UploadLimits {
+    photo_size: u64,
+    file_size: u64,
+    enabled: bool,
+}
+
+impl UploadLimits {
+    /// Limits of the Telegram's Bot API server: 10 MB for photos and 50 MB
+    /// for other files.
+    #[must_use]
+    pub fn new() -> Self {
+        Self { photo_size: 10 * MB, file_size: 50 * MB, enabled: true }
+    }
+
+    /// Limits of a local Bot API server: 10 MB for photos and 2000 MB for
+    /// other files.
+    #[must_use]
+    pub fn local_server() -> Self {
+        Self::new().file_size(2000 * MB)
+    }
+
+    /// No checks at all.
+    #[must_use]
+    pub fn unchecked() -> Self {
+        Self { enabled: false, ..Self::new() }
+    }
+
+    /// The maximum size of a photo in bytes.
+    #[must_use]
+    pub fn photo_size(mut self, bytes: u64) -> Self {
+        self.photo_size = bytes;
+        self
+    }
+
+    /// The maximum size of a file other than a photo in bytes.
+    #[must_use]
+    pub fn file_size(mut self, bytes: u64) -> Self {
+        self.file_size = bytes;
+        self
+    }
+
+    /// Checks `file` sent as `kind`.
+    pub(crate) async fn check(
+        &self,
+        kind: FileKind,
+        file: &InputFile,
+    ) -> Result<(), InvalidFileError> {
+        if !self.enabled {
+            return Ok(());
+        }
+        let (size, header) = match read_header(file).await {
+            Some(header) => header,
+            None => return Ok(()),
+        };
+
+        let (max_size, types): (u64, &[&str]) = match kind {
+            FileKind::Photo => (
+                self.photo_size,
+                &["image/jpeg", "image/png", "image/gif", "image/webp", "image/bmp"],
+            ),
+            FileKind::Other => (self.file_size, &[]),
+            FileKind::Thumb => (200 * 1024, &["image/jpeg"]),
+            FileKind::PngSticker => (512 * 1024, &["image/png"]),
+            FileKind::TgsSticker => (64 * 1024, &["application/gzip"]),
+        };
+
+        if let Some(size) = size {
+            if size > max_size {
+                return Err(InvalidFileError::TooLarge { size, max_size });
+            }
+        }
+        if let Some(mime) = sniff(&header) {
+            if !types.is_empty() && !types.contains(&mime.as_ref()) {
+                return Err(InvalidFileError::WrongType {
+                    mime: MimeWrapper(mime),
+                    expected: types.join(", "),
+                });
+            }
+        }
+        if kind == FileKind::PngSticker {
+            if let Some((width, height)) = png_dimensions(&header) {
+                if width.max(height) != 512 {
+                    return Err(InvalidFileError::WrongDimensions {
+                        width,
+                        height,
+                        expected: "one side of 512 pixels and the other one not longer",
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks all the files of `media`.
+    pub(crate) async fn check_media(&self, media: &InputMedia) -> Result<(), InvalidFileError> {
+        let (media, thumb) = match media {
+            InputMedia::Photo(photo) => return self.check(FileKind::Photo, &photo.media).await,
+            InputMedia::Video(video) => (&video.media, &video.thumb),
+            InputMedia::Animation(animation) => (&animation.media, &animation.thumb),
+            InputMedia::Audio(audio) => (&audio.media, &audio.thumb),
+            InputMedia::Document(document) => (&document.media, &document.thumb),
+        };
+
+        self.check(FileKind::Other, media).await?;
+        if let Some(thumb) = thumb {
+            self.check(FileKind::Thumb, thumb).await?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for UploadLimits {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A purpose of an uploaded file, which defines its limits.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum FileKind {
+    Photo,
+    Other,
+    Thumb,
+    PngSticker,
+    TgsSticker,
+}
+
+/// The number of bytes needed to detect a type and dimensions of a file.
+const HEADER_LEN: usize = 32;
+
+/// Returns a size (if known) and first bytes of a file to be uploaded.
+async fn read_header(file: &InputFile) -> Option<(Option<u64>, Vec<u8>)> {
+    match file {
+        InputFile::File(path) => {
+            // Failing to read the file is reported when it's uploaded.
+            let file = tokio::fs::File::open(path).await.ok()?;
+            let size = file.metadata().await.ok()?.len();
+            let mut header = Vec::with_capacity(HEADER_LEN);
+            file.take(HEADER_LEN as u64).read_to_end(&mut header).await.ok()?;
+            Some((Some(size), header))
+        }
+        InputFile::Memory { data, .. } => {
+            Some((Some(data.len() as u64), data[..data.len().min(HEADER_LEN)].to_vec()))
+        }
+        InputFile::Stream { stream, .. } => Some((stream.known_len(), Vec::new())),
+        _ => None,
+    }
+}
+
+/// Detects a type of a file by its first bytes.
+fn sniff(header: &[u8]) -> Option<Mime> {
+    let mime = match header {
+        [0xFF, 0xD8, 0xFF, ..] => "image/jpeg",
+        [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n', ..] => "image/png",
+        [b'G', b'I', b'F', b'8', ..] => "image/gif",
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => "image/webp",
+        [b'B', b'M', ..] => "image/bmp",
+        [0x1F, 0x8B, ..] => "application/gzip",
+        [b'%', b'P', b'D', b'F', ..] => "application/pdf",
+        [b'P', b'K', 0x03, 0x04, ..] => "application/zip",
+        [_, _, _, _, b'f', b't', b'y', b'p', ..] => "video/mp4",
+        [0x1A, 0x45, 0xDF, 0xA3, ..] => "video/webm",
+        [b'O', b'g', b'g', b'S', ..] => "audio/ogg",
+        [b'I', b'D', b'3', ..] => "audio/mpeg",
+        _ => return None,
+    };
+    Some(mime.parse().expect("a valid MIME type"))
+}
+
+/// Returns width and height of a PNG image from its IHDR chunk.
+fn png_dimensions(header: &[u8]) -> Option<(u32, u32)> {
+    if header.get(12..16)? != b"IHDR" {
+        return None;
+    }
+    let size = header.get(16..24)?;
+    let width = u32::from_be_bytes([size[0], size[1], size[2], size[3]]);
+    let height = u32::from_be_bytes([size[4], size[5], size[6], size[7]]);
+    Some((width, height))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn png(width: u32, height: u32) -> InputFile {
+        let mut data = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+        data.extend_from_slice(&width.to_be_bytes());
+        data.extend_from_slice(&height.to_be_bytes());
+        InputFile::memory("sticker.png", data)
+    }
+
+    #[tokio::test]
+    async fn sizes() {
+        let limits = UploadLimits::new().photo_size(4);
+
+        assert!(limits
+            .check(FileKind::Photo, &InputFile::memory("a.jpg", &b"\xFF\xD8\xFF"[..]))
+            .await
+            .is_ok());
+        assert!(matches!(
+            limits
+                .check(FileKind::Photo, &InputFile::memory("a.jpg", &b"\xFF\xD8\xFF\0\0"[..]))
+                .await,
+            Err(InvalidFileError::TooLarge { size: 5, max_size: 4 })
+        ));
+        assert!(UploadLimits::unchecked()
+            .photo_size(4)
+            .check(FileKind::Photo, &InputFile::memory("a.jpg", &b"\xFF\xD8\xFF\0\0"[..]))
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn types() {
+        let limits = UploadLimits::new();
+
+        assert!(matches!(
+            limits.check(FileKind::Thumb, &png(90, 90)).await,
+            Err(InvalidFileError::WrongType { .. })
+        ));
+        // Unknown types are not rejected.
+        assert!(limits.check(FileKind::Thumb, &InputFile::memory("a", &b"????"[..])).await.is_ok());
+        assert!(limits.check(FileKind::Thumb, &InputFile::file_id("123456")).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn sticker_dimensions() {
+        let limits = UploadLimits::new();
+
+        assert!(limits.check(FileKind::PngSticker, &png(512, 300)).await.is_ok());
+        assert!(matches!(
+            limits.check(FileKind::PngSticker, &png(500, 300)).await,
+            Err(InvalidFileError::WrongDimensions { width: 500, height: 300, .. })
+        ));
+    }
+}
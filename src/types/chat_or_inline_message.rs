@@ -8,5 +8,5 @@ use serde::{Deserialize, Serialize};
 #[non_exhaustive]
 pub enum ChatOrInlineMessage {
     Chat { chat_id: ChatId, message_id: i32 },
-    Inline { inline_message_id: i32 },
+    Inline { inline_message_id: String },
 }
@@ -118,13 +118,14 @@ pub struct EncryptedPassportElementPassport {
     pub front_side: PassportFile,
 
     /// Encrypted file with the selfie of the user holding a document,
-    /// provided by the user; available for `passport`, `driver_license`,
-    /// `identity_card` and `internal_passport`. The file can be decrypted
-    /// and verified using the accompanying [`EncryptedCredentials`].
+    /// provided by the user; available if requested for `passport`,
+    /// `driver_license`, `identity_card` and `internal_passport`. The file
+    /// can be decrypted and verified using the accompanying
+    /// [`EncryptedCredentials`].
     ///
     /// [`EncryptedCredentials`]:
     /// crate::types::EncryptedCredentials
-    pub selfie: PassportFile,
+    pub selfie: Option<PassportFile>,
 
     /// Array of encrypted files with translated versions of documents
     /// provided by the user. Available if requested for `passport`,
@@ -140,11 +141,11 @@ pub struct EncryptedPassportElementPassport {
 }
 
 impl EncryptedPassportElementPassport {
-    pub fn new<S>(data: S, front_side: PassportFile, selfie: PassportFile) -> Self
+    pub fn new<S>(data: S, front_side: PassportFile) -> Self
     where
         S: Into<String>,
     {
-        Self { data: data.into(), front_side, selfie, translation: None }
+        Self { data: data.into(), front_side, selfie: None, translation: None }
     }
 
     pub fn data<S>(mut self, val: S) -> Self
@@ -161,7 +162,7 @@ impl EncryptedPassportElementPassport {
     }
 
     pub fn selfie(mut self, val: PassportFile) -> Self {
-        self.selfie = val;
+        self.selfie = Some(val);
         self
     }
 
@@ -207,13 +208,14 @@ pub struct EncryptedPassportElementDriverLicense {
     pub reverse_side: PassportFile,
 
     /// Encrypted file with the selfie of the user holding a document,
-    /// provided by the user; available for `passport`, `driver_license`,
-    /// `identity_card` and `internal_passport`. The file can be decrypted
-    /// and verified using the accompanying [`EncryptedCredentials`].
+    /// provided by the user; available if requested for `passport`,
+    /// `driver_license`, `identity_card` and `internal_passport`. The file
+    /// can be decrypted and verified using the accompanying
+    /// [`EncryptedCredentials`].
     ///
     /// [`EncryptedCredentials`]:
     /// crate::types::EncryptedCredentials
-    pub selfie: PassportFile,
+    pub selfie: Option<PassportFile>,
 
     /// Array of encrypted files with translated versions of documents
     /// provided by the user. Available if requested for `passport`,
@@ -229,16 +231,11 @@ pub struct EncryptedPassportElementDriverLicense {
 }
 
 impl EncryptedPassportElementDriverLicense {
-    pub fn new<S>(
-        data: S,
-        front_side: PassportFile,
-        reverse_side: PassportFile,
-        selfie: PassportFile,
-    ) -> Self
+    pub fn new<S>(data: S, front_side: PassportFile, reverse_side: PassportFile) -> Self
     where
         S: Into<String>,
     {
-        Self { data: data.into(), front_side, reverse_side, selfie, translation: None }
+        Self { data: data.into(), front_side, reverse_side, selfie: None, translation: None }
     }
 
     pub fn data<S>(mut self, val: S) -> Self
@@ -260,7 +257,7 @@ impl EncryptedPassportElementDriverLicense {
     }
 
     pub fn selfie(mut self, val: PassportFile) -> Self {
-        self.selfie = val;
+        self.selfie = Some(val);
         self
     }
     pub fn translation<P>(mut self, val: P) -> Self
@@ -305,13 +302,14 @@ pub struct EncryptedPassportElementIdentityCard {
     pub reverse_side: PassportFile,
 
     /// Encrypted file with the selfie of the user holding a document,
-    /// provided by the user; available for `passport`, `driver_license`,
-    /// `identity_card` and `internal_passport`. The file can be decrypted
-    /// and verified using the accompanying [`EncryptedCredentials`].
+    /// provided by the user; available if requested for `passport`,
+    /// `driver_license`, `identity_card` and `internal_passport`. The file
+    /// can be decrypted and verified using the accompanying
+    /// [`EncryptedCredentials`].
     ///
     /// [`EncryptedCredentials`]:
     /// crate::types::EncryptedCredentials
-    pub selfie: PassportFile,
+    pub selfie: Option<PassportFile>,
 
     /// Array of encrypted files with translated versions of documents
     /// provided by the user. Available if requested for `passport`,
@@ -327,16 +325,11 @@ pub struct EncryptedPassportElementIdentityCard {
 }
 
 impl EncryptedPassportElementIdentityCard {
-    pub fn new<S>(
-        data: S,
-        front_side: PassportFile,
-        reverse_side: PassportFile,
-        selfie: PassportFile,
-    ) -> Self
+    pub fn new<S>(data: S, front_side: PassportFile, reverse_side: PassportFile) -> Self
     where
         S: Into<String>,
     {
-        Self { data: data.into(), front_side, reverse_side, selfie, translation: None }
+        Self { data: data.into(), front_side, reverse_side, selfie: None, translation: None }
     }
 
     pub fn data<S>(mut self, val: S) -> Self
@@ -358,7 +351,7 @@ impl EncryptedPassportElementIdentityCard {
     }
 
     pub fn selfie(mut self, val: PassportFile) -> Self {
-        self.selfie = val;
+        self.selfie = Some(val);
         self
     }
     pub fn translation<P>(mut self, val: P) -> Self
@@ -394,13 +387,14 @@ pub struct EncryptedPassportElementInternalPassport {
     pub front_side: PassportFile,
 
     /// Encrypted file with the selfie of the user holding a document,
-    /// provided by the user; available for `passport`, `driver_license`,
-    /// `identity_card` and `internal_passport`. The file can be decrypted
-    /// and verified using the accompanying [`EncryptedCredentials`].
+    /// provided by the user; available if requested for `passport`,
+    /// `driver_license`, `identity_card` and `internal_passport`. The file
+    /// can be decrypted and verified using the accompanying
+    /// [`EncryptedCredentials`].
     ///
     /// [`EncryptedCredentials`]:
     /// crate::types::EncryptedCredentials
-    pub selfie: PassportFile,
+    pub selfie: Option<PassportFile>,
 
     /// Array of encrypted files with translated versions of documents
     /// provided by the user. Available if requested for `passport`,
@@ -416,11 +410,11 @@ pub struct EncryptedPassportElementInternalPassport {
 }
 
 impl EncryptedPassportElementInternalPassport {
-    pub fn new<S>(data: S, front_side: PassportFile, selfie: PassportFile) -> Self
+    pub fn new<S>(data: S, front_side: PassportFile) -> Self
     where
         S: Into<String>,
     {
-        Self { data: data.into(), front_side, selfie, translation: None }
+        Self { data: data.into(), front_side, selfie: None, translation: None }
     }
 
     pub fn data<S>(mut self, val: S) -> Self
@@ -437,7 +431,7 @@ impl EncryptedPassportElementInternalPassport {
     }
 
     pub fn selfie(mut self, val: PassportFile) -> Self {
-        self.selfie = val;
+        self.selfie = Some(val);
         self
     }
 
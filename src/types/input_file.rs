@@ -1,6 +1,13 @@
 use serde::{Deserialize, Serialize};
+use tokio::io::AsyncRead;
 
-use std::{borrow::Cow, path::PathBuf};
+use std::{
+    borrow::Cow,
+    fmt::{self, Debug},
+    hash::{Hash, Hasher},
+    path::PathBuf,
+    sync::{Arc, Mutex, PoisonError},
+};
 
 /// This object represents the contents of a file to be uploaded.
 ///
@@ -9,9 +16,17 @@ use std::{borrow::Cow, path::PathBuf};
 #[non_exhaustive]
 pub enum InputFile {
     File(PathBuf),
-    Memory { file_name: String, data: Cow<'static, [u8]> },
+    Memory {
+        file_name: String,
+        data: Cow<'static, [u8]>,
+    },
     Url(String),
     FileId(String),
+    #[serde(skip)]
+    Stream {
+        file_name: String,
+        stream: InputStream,
+    },
 }
 
 impl InputFile {
@@ -44,6 +59,24 @@ impl InputFile {
         Self::FileId(file_id.into())
     }
 
+    /// Creates a file read from `stream` while uploading.
+    ///
+    /// See [`InputStream`] for a known length and progress reporting.
+    ///
+    /// [`InputStream`]: crate::types::InputStream
+    pub fn stream<S, R>(file_name: S, stream: R) -> Self
+    where
+        S: Into<String>,
+        R: Into<InputStream>,
+    {
+        Self::Stream { file_name: file_name.into(), stream: stream.into() }
+    }
+
+    /// Returns `true`, if the file must be uploaded with multipart/form-data.
+    pub(crate) fn is_upload(&self) -> bool {
+        matches!(self, Self::File(_) | Self::Memory { .. } | Self::Stream { .. })
+    }
+
     pub fn as_file(&self) -> Option<&PathBuf> {
         match self {
             Self::File(path) => Some(path),
@@ -89,13 +122,140 @@ impl Serialize for InputFile {
                     &format!("attach://{}", path.file_name().unwrap().to_string_lossy()),
                 )
             }
-            InputFile::Memory { data, .. } => {
+            InputFile::Memory { file_name, .. } => {
                 // NOTE: file should be actually attached with
                 // multipart/form-data
-                serializer.serialize_str(&format!("attach://{}", String::from_utf8_lossy(data)))
+                serializer.serialize_str(&format!("attach://{}", file_name))
+            }
+            InputFile::Stream { file_name, .. } => {
+                // NOTE: file should be actually attached with
+                // multipart/form-data
+                serializer.serialize_str(&format!("attach://{}", file_name))
             }
             InputFile::Url(url) => serializer.serialize_str(url),
             InputFile::FileId(id) => serializer.serialize_str(id),
         }
     }
 }
+
+type Reader = Box<dyn AsyncRead + Send + Unpin>;
+type Progress = Arc<dyn Fn(u64, Option<u64>) + Send + Sync>;
+
+/// A source of a file, which is read while uploading.
+///
+/// The stream can be uploaded only once: its clones (and clones of an
+/// [`InputFile`] containing it) share the same stream, and sending a
+/// request with an already read stream fails with an I/O error.
+///
+/// ## Examples
+///
+/// ```
+/// use teloxide::types::{InputFile, InputStream};
+///
+/// let data: &'static [u8] = b"a,b\n1,2\n";
+/// let stream = InputStream::new(data)
+///     .with_len(data.len() as u64)
+///     .progress(|sent, total| log::info!("{}/{:?} bytes", sent, total));
+/// let file = InputFile::stream("table.csv", stream);
+/// ```
+///
+/// [`InputFile`]: crate::types::InputFile
+#[derive(Clone)]
+pub struct InputStream {
+    reader: Arc<Mutex<Option<Reader>>>,
+    len: Option<u64>,
+    progress: Option<Progress>,
+}
+
+impl InputStream {
+    pub fn new<R>(reader: R) -> Self
+    where
+        R: AsyncRead + Send + Unpin + 'static,
+    {
+        Self { reader: Arc::new(Mutex::new(Some(Box::new(reader)))), len: None, progress: None }
+    }
+
+    /// The number of bytes in the stream, if known in advance.
+    ///
+    /// It's sent as the length of the multipart part, checked against upload
+    /// limits and passed to a progress callback, so it must be exactly the
+    /// number of bytes the stream yields: otherwise the request is broken.
+    #[must_use]
+    pub fn with_len(mut self, len: u64) -> Self {
+        self.len = Some(len);
+        self
+    }
+
+    /// A callback, called with the number of uploaded bytes and the length of
+    /// the stream (if known) every time a chunk is read from the stream.
+    #[must_use]
+    pub fn progress<F>(mut self, f: F) -> Self
+    where
+        F: Fn(u64, Option<u64>) + Send + Sync + 'static,
+    {
+        self.progress = Some(Arc::new(f));
+        self
+    }
+
+    pub(crate) fn known_len(&self) -> Option<u64> {
+        self.len
+    }
+
+    /// Takes the reader out of the stream, returning `None` if it's already
+    /// taken.
+    pub(crate) fn take(&self) -> Option<(Reader, Option<u64>, Option<Progress>)> {
+        // The lock is held only to take the reader, so it's fine if another
+        // thread has panicked meanwhile.
+        let reader = self.reader.lock().unwrap_or_else(PoisonError::into_inner).take()?;
+        Some((reader, self.len, self.progress.clone()))
+    }
+}
+
+impl<R> From<R> for InputStream
+where
+    R: AsyncRead + Send + Unpin + 'static,
+{
+    fn from(reader: R) -> Self {
+        Self::new(reader)
+    }
+}
+
+impl Debug for InputStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InputStream").field("len", &self.len).finish()
+    }
+}
+
+impl PartialEq for InputStream {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.reader, &other.reader)
+    }
+}
+
+impl Eq for InputStream {}
+
+impl Hash for InputStream {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (Arc::as_ptr(&self.reader) as *const ()).hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stream_is_taken_once() {
+        let file = InputFile::stream("a.txt", &b"abc"[..]);
+        let clone = file.clone();
+        assert_eq!(file, clone);
+
+        let stream = match clone {
+            InputFile::Stream { stream, .. } => stream,
+            _ => unreachable!(),
+        };
+        assert!(stream.take().is_some());
+        assert!(stream.take().is_none());
+        assert_ne!(stream, InputStream::new(&b"abc"[..]));
+    }
+}
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::types::{InputFile, ParseMode};
+use crate::types::{InputFile, MessageEntity, ParseMode};
 
 /// This object represents the content of a media message to be sent.
 ///
@@ -37,11 +37,15 @@ pub struct InputMediaPhoto {
     /// [HTML]: https://core.telegram.org/bots/api#html-style
     /// [bold, italic, fixed-width text or inline URLs]: https://core.telegram.org/bots/api#formatting-options
     pub parse_mode: Option<ParseMode>,
+
+    /// List of special entities that appear in the caption, which can be
+    /// specified instead of `parse_mode`.
+    pub caption_entities: Option<Vec<MessageEntity>>,
 }
 
 impl InputMediaPhoto {
     pub fn new(media: InputFile) -> Self {
-        Self { media, caption: None, parse_mode: None }
+        Self { media, caption: None, parse_mode: None, caption_entities: None }
     }
 
     pub fn media(mut self, val: InputFile) -> Self {
@@ -57,8 +61,20 @@ impl InputMediaPhoto {
         self
     }
 
+    /// Resets `caption_entities`, because they cannot be used together.
     pub fn parse_mode(mut self, val: ParseMode) -> Self {
         self.parse_mode = Some(val);
+        self.caption_entities = None;
+        self
+    }
+
+    /// Resets `parse_mode`, because they cannot be used together.
+    pub fn caption_entities<T>(mut self, val: T) -> Self
+    where
+        T: Into<Vec<MessageEntity>>,
+    {
+        self.caption_entities = Some(val.into());
+        self.parse_mode = None;
         self
     }
 }
@@ -91,6 +107,10 @@ pub struct InputMediaVideo {
     /// [bold, italic, fixed-width text or inline URLs]: https://core.telegram.org/bots/api#formatting-options
     pub parse_mode: Option<ParseMode>,
 
+    /// List of special entities that appear in the caption, which can be
+    /// specified instead of `parse_mode`.
+    pub caption_entities: Option<Vec<MessageEntity>>,
+
     /// Video width.
     pub width: Option<u16>,
 
@@ -111,6 +131,7 @@ impl InputMediaVideo {
             thumb: None,
             caption: None,
             parse_mode: None,
+            caption_entities: None,
             width: None,
             height: None,
             duration: None,
@@ -136,8 +157,20 @@ impl InputMediaVideo {
         self
     }
 
+    /// Resets `caption_entities`, because they cannot be used together.
     pub fn parse_mode(mut self, val: ParseMode) -> Self {
         self.parse_mode = Some(val);
+        self.caption_entities = None;
+        self
+    }
+
+    /// Resets `parse_mode`, because they cannot be used together.
+    pub fn caption_entities<T>(mut self, val: T) -> Self
+    where
+        T: Into<Vec<MessageEntity>>,
+    {
+        self.caption_entities = Some(val.into());
+        self.parse_mode = None;
         self
     }
 
@@ -191,6 +224,10 @@ pub struct InputMediaAnimation {
     /// [bold, italic, fixed-width text or inline URLs]: https://core.telegram.org/bots/api#formatting-options
     pub parse_mode: Option<ParseMode>,
 
+    /// List of special entities that appear in the caption, which can be
+    /// specified instead of `parse_mode`.
+    pub caption_entities: Option<Vec<MessageEntity>>,
+
     /// Animation width.
     pub width: Option<u16>,
 
@@ -208,6 +245,7 @@ impl InputMediaAnimation {
             thumb: None,
             caption: None,
             parse_mode: None,
+            caption_entities: None,
             width: None,
             height: None,
             duration: None,
@@ -232,8 +270,20 @@ impl InputMediaAnimation {
         self
     }
 
+    /// Resets `caption_entities`, because they cannot be used together.
     pub fn parse_mode(mut self, val: ParseMode) -> Self {
         self.parse_mode = Some(val);
+        self.caption_entities = None;
+        self
+    }
+
+    /// Resets `parse_mode`, because they cannot be used together.
+    pub fn caption_entities<T>(mut self, val: T) -> Self
+    where
+        T: Into<Vec<MessageEntity>>,
+    {
+        self.caption_entities = Some(val.into());
+        self.parse_mode = None;
         self
     }
 
@@ -281,6 +331,10 @@ pub struct InputMediaAudio {
     /// [bold, italic, fixed-width text or inline URLs]: https://core.telegram.org/bots/api#formatting-options
     pub parse_mode: Option<ParseMode>,
 
+    /// List of special entities that appear in the caption, which can be
+    /// specified instead of `parse_mode`.
+    pub caption_entities: Option<Vec<MessageEntity>>,
+
     /// Duration of the audio in seconds.
     pub duration: Option<u16>,
 
@@ -298,6 +352,7 @@ impl InputMediaAudio {
             thumb: None,
             caption: None,
             parse_mode: None,
+            caption_entities: None,
             performer: None,
             title: None,
             duration: None,
@@ -322,8 +377,20 @@ impl InputMediaAudio {
         self
     }
 
+    /// Resets `caption_entities`, because they cannot be used together.
     pub fn parse_mode(mut self, val: ParseMode) -> Self {
         self.parse_mode = Some(val);
+        self.caption_entities = None;
+        self
+    }
+
+    /// Resets `parse_mode`, because they cannot be used together.
+    pub fn caption_entities<T>(mut self, val: T) -> Self
+    where
+        T: Into<Vec<MessageEntity>>,
+    {
+        self.caption_entities = Some(val.into());
+        self.parse_mode = None;
         self
     }
 
@@ -376,11 +443,15 @@ pub struct InputMediaDocument {
     /// [HTML]: https://core.telegram.org/bots/api#html-style
     /// [bold, italic, fixed-width text or inline URLs]: https://core.telegram.org/bots/api#formatting-options
     pub parse_mode: Option<ParseMode>,
+
+    /// List of special entities that appear in the caption, which can be
+    /// specified instead of `parse_mode`.
+    pub caption_entities: Option<Vec<MessageEntity>>,
 }
 
 impl InputMediaDocument {
     pub fn new(media: InputFile) -> Self {
-        Self { media, thumb: None, caption: None, parse_mode: None }
+        Self { media, thumb: None, caption: None, parse_mode: None, caption_entities: None }
     }
 
     pub fn thumb(mut self, val: InputFile) -> Self {
@@ -396,8 +467,20 @@ impl InputMediaDocument {
         self
     }
 
+    /// Resets `caption_entities`, because they cannot be used together.
     pub fn parse_mode(mut self, val: ParseMode) -> Self {
         self.parse_mode = Some(val);
+        self.caption_entities = None;
+        self
+    }
+
+    /// Resets `parse_mode`, because they cannot be used together.
+    pub fn caption_entities<T>(mut self, val: T) -> Self
+    where
+        T: Into<Vec<MessageEntity>>,
+    {
+        self.caption_entities = Some(val.into());
+        self.parse_mode = None;
         self
     }
 }
@@ -412,6 +495,18 @@ impl InputMedia {
             | InputMedia::Video(InputMediaVideo { media, .. }) => media,
         }
     }
+
+    /// All the files of this media, including a thumbnail.
+    pub(crate) fn files_mut(&mut self) -> Vec<&mut InputFile> {
+        let (media, thumb) = match self {
+            InputMedia::Photo(InputMediaPhoto { media, .. }) => (media, None),
+            InputMedia::Document(InputMediaDocument { media, thumb, .. })
+            | InputMedia::Audio(InputMediaAudio { media, thumb, .. })
+            | InputMedia::Animation(InputMediaAnimation { media, thumb, .. })
+            | InputMedia::Video(InputMediaVideo { media, thumb, .. }) => (media, thumb.as_mut()),
+        };
+        std::iter::once(media).chain(thumb).collect()
+    }
 }
 
 impl From<InputMedia> for InputFile {
@@ -429,6 +524,7 @@ impl From<InputMedia> for InputFile {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::MessageEntityKind;
 
     #[test]
     fn photo_serialize() {
@@ -437,6 +533,7 @@ mod tests {
             media: InputFile::FileId(String::from("123456")),
             caption: None,
             parse_mode: None,
+            caption_entities: None,
         });
 
         let actual_json = serde_json::to_string(&photo).unwrap();
@@ -451,6 +548,7 @@ mod tests {
             thumb: None,
             caption: None,
             parse_mode: None,
+            caption_entities: None,
             width: None,
             height: None,
             duration: None,
@@ -469,6 +567,7 @@ mod tests {
             thumb: None,
             caption: None,
             parse_mode: None,
+            caption_entities: None,
             width: None,
             height: None,
             duration: None,
@@ -486,6 +585,7 @@ mod tests {
             thumb: None,
             caption: None,
             parse_mode: None,
+            caption_entities: None,
             duration: None,
             performer: None,
             title: None,
@@ -503,9 +603,47 @@ mod tests {
             thumb: None,
             caption: None,
             parse_mode: None,
+            caption_entities: None,
         });
 
         let actual_json = serde_json::to_string(&video).unwrap();
         assert_eq!(expected_json, actual_json);
     }
+
+    #[test]
+    fn files() {
+        let mut media = InputMedia::Video(
+            InputMediaVideo::new(InputFile::memory("video.mp4", &b"video"[..]))
+                .thumb(InputFile::file("thumb.jpg")),
+        );
+        let files = media.files_mut();
+        assert_eq!(files.len(), 2);
+        assert!(files.into_iter().all(|file| file.is_upload()));
+
+        let mut media = InputMedia::Photo(InputMediaPhoto::new(InputFile::file_id("123456")));
+        assert!(!media.files_mut()[0].is_upload());
+        assert_eq!(
+            serde_json::to_string(&InputMedia::Photo(InputMediaPhoto::new(InputFile::memory(
+                "photo.jpg",
+                &b"photo"[..]
+            ))))
+            .unwrap(),
+            r#"{"type":"photo","media":"attach://photo.jpg"}"#
+        );
+    }
+
+    #[test]
+    fn caption_entities_serialize() {
+        let photo = InputMediaPhoto::new(InputFile::file_id("123456"))
+            .parse_mode(ParseMode::HTML)
+            .caption_entities(vec![MessageEntity::new(MessageEntityKind::Bold, 0, 4)]);
+        assert_eq!(photo.parse_mode, None);
+        assert_eq!(
+            serde_json::to_string(&InputMedia::Photo(photo.clone())).unwrap(),
+            r#"{"type":"photo","media":"123456","caption_entities":[{"type":"bold","offset":0,"length":4}]}"#
+        );
+
+        let photo = photo.parse_mode(ParseMode::HTML);
+        assert_eq!(photo.caption_entities, None);
+    }
 }
@@ -42,6 +42,18 @@ impl KeyboardButton {
     }
 }
 
+impl From<&str> for KeyboardButton {
+    fn from(text: &str) -> Self {
+        Self::new(text)
+    }
+}
+
+impl From<String> for KeyboardButton {
+    fn from(text: String) -> Self {
+        Self::new(text)
+    }
+}
+
 // Serialize + Deserialize are implemented by hand
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 #[non_exhaustive]
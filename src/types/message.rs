@@ -2,11 +2,15 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::types::{
-    chat::{ChatKind, PublicChatKind},
-    Animation, Audio, Chat, ChatPublic, Contact, Dice, Document, Game, InlineKeyboardMarkup,
-    Invoice, Location, MessageEntity, PassportData, PhotoSize, Poll, PublicChatChannel,
-    PublicChatSupergroup, Sticker, SuccessfulPayment, True, User, Venue, Video, VideoNote, Voice,
+use crate::{
+    types::{
+        chat::{ChatKind, PublicChatKind},
+        Animation, Audio, Chat, ChatPublic, Contact, Dice, Document, Game, InlineKeyboardMarkup,
+        Invoice, Location, MessageEntity, PassportData, PhotoSize, Poll, PublicChatChannel,
+        PublicChatSupergroup, Sticker, SuccessfulPayment, True, User, Venue, Video, VideoNote,
+        Voice,
+    },
+    utils::render::{render_html, render_markdown_v2},
 };
 
 /// This object represents a message.
@@ -1544,6 +1548,38 @@ impl Message {
             _ => None,
         }
     }
+
+    /// Renders the text of this message with its entities into a string with
+    /// the [HTML style].
+    ///
+    /// [HTML style]: https://core.telegram.org/bots/api#html-style
+    pub fn text_html(&self) -> Option<String> {
+        Some(render_html(self.text()?, self.entities().unwrap_or(&[])))
+    }
+
+    /// Renders the text of this message with its entities into a string with
+    /// the [MarkdownV2 style].
+    ///
+    /// [MarkdownV2 style]: https://core.telegram.org/bots/api#markdownv2-style
+    pub fn text_markdown_v2(&self) -> Option<String> {
+        Some(render_markdown_v2(self.text()?, self.entities().unwrap_or(&[])))
+    }
+
+    /// Renders the caption of this message with its entities into a string
+    /// with the [HTML style].
+    ///
+    /// [HTML style]: https://core.telegram.org/bots/api#html-style
+    pub fn caption_html(&self) -> Option<String> {
+        Some(render_html(self.caption()?, self.caption_entities().unwrap_or(&[])))
+    }
+
+    /// Renders the caption of this message with its entities into a string
+    /// with the [MarkdownV2 style].
+    ///
+    /// [MarkdownV2 style]: https://core.telegram.org/bots/api#markdownv2-style
+    pub fn caption_markdown_v2(&self) -> Option<String> {
+        Some(render_markdown_v2(self.caption()?, self.caption_entities().unwrap_or(&[])))
+    }
 }
 
 #[cfg(test)]
@@ -1750,4 +1786,57 @@ mod tests {
         let message = from_str::<Message>(json);
         assert!(message.is_ok());
     }
+
+    #[test]
+    fn text_html_with_overlapping_entities() {
+        let json = r#"{
+          "message_id": 1,
+          "date": 1568290622,
+          "chat": {
+           "id": 250918540,
+           "first_name": "Андрей",
+           "type": "private"
+          },
+          "text": "🦀 a<b_c",
+          "entities": [
+           {"type": "bold", "offset": 3, "length": 4},
+           {"type": "italic", "offset": 5, "length": 3}
+          ]
+        }"#;
+        let message = from_str::<Message>(json).unwrap();
+
+        assert_eq!(message.text_html().unwrap(), "🦀 <b>a&lt;<i>b_</i></b><i>c</i>");
+        assert_eq!(message.text_markdown_v2().unwrap(), "🦀 *a<_b\\__*_c_");
+        assert_eq!(message.caption_html(), None);
+    }
+
+    #[test]
+    fn caption_html() {
+        let json = r#"{
+          "message_id": 1,
+          "date": 1568290622,
+          "chat": {
+           "id": 250918540,
+           "first_name": "Андрей",
+           "type": "private"
+          },
+          "photo": [
+           {
+            "file_id": "AgADAgAD36sxG-PX0UvQSXIn9rccdw-ACA4ABAEAAwIAA20AAybcBAABFgQ",
+            "file_unique_id":"",
+            "file_size": 18188,
+            "width": 320,
+            "height": 239
+           }
+          ],
+          "caption": "Привет, мир!",
+          "caption_entities": [
+           {"type": "text_link", "offset": 8, "length": 3, "url": "https://t.me"}
+          ]
+        }"#;
+        let message = from_str::<Message>(json).unwrap();
+
+        assert_eq!(message.caption_html().unwrap(), "Привет, <a href=\"https://t.me\">мир</a>!");
+        assert_eq!(message.text_html(), None);
+    }
 }
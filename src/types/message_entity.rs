@@ -64,9 +64,16 @@ pub enum MessageEntityKind {
 }
 
 impl MessageEntity {
+    /// Extracts a text covered by this entity from `message`.
+    ///
+    /// [`MessageEntity::offset`] and [`MessageEntity::length`] are measured in
+    /// UTF-16 code units, so they are not byte indices of the text.
+    ///
+    /// [`MessageEntity::offset`]: crate::types::MessageEntity::offset
+    /// [`MessageEntity::length`]: crate::types::MessageEntity::length
     pub fn text_from(&self, message: &Message) -> Option<String> {
-        let text = message.text();
-        Some(String::from(&text?[self.offset..self.offset + self.length]))
+        let text: Vec<u16> = message.text()?.encode_utf16().collect();
+        String::from_utf16(text.get(self.offset..self.offset + self.length)?).ok()
     }
 }
 
@@ -1,5 +1,9 @@
 use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
 
+/// A currency supported by [Telegram Payments], named by its ISO 4217 code.
+///
+/// [Telegram Payments]: https://core.telegram.org/bots/payments#supported-currencies
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub enum Currency {
     AED,
@@ -87,3 +91,33 @@ pub enum Currency {
     YER,
     ZAR,
 }
+
+impl Currency {
+    /// The number of digits past the decimal point, i.e. `amount` of
+    /// [`LabeledPrice`] is `10.pow(exponent)` times a price in major units.
+    ///
+    /// See the `exp` parameter in [`currencies.json`].
+    ///
+    /// [`LabeledPrice`]: crate::types::LabeledPrice
+    /// [`currencies.json`]: https://core.telegram.org/bots/payments/currencies.json
+    #[must_use]
+    pub fn exponent(self) -> u32 {
+        match self {
+            Currency::CLP
+            | Currency::ISK
+            | Currency::JPY
+            | Currency::KRW
+            | Currency::PYG
+            | Currency::UGX
+            | Currency::VND => 0,
+            _ => 2,
+        }
+    }
+}
+
+impl Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Variants are named by their codes.
+        fmt::Debug::fmt(self, f)
+    }
+}
@@ -43,28 +43,28 @@ pub enum PassportElementErrorKind {
     #[serde(rename = "data")]
     DataField(PassportElementErrorDataField),
 
-    #[serde(rename = "snake_case")]
+    #[serde(rename = "front_side")]
     FrontSide(PassportElementErrorFrontSide),
 
-    #[serde(rename = "snake_case")]
+    #[serde(rename = "reverse_side")]
     ReverseSide(PassportElementErrorReverseSide),
 
-    #[serde(rename = "snake_case")]
+    #[serde(rename = "selfie")]
     Selfie(PassportElementErrorSelfie),
 
-    #[serde(rename = "snake_case")]
+    #[serde(rename = "file")]
     File(PassportElementErrorFile),
 
-    #[serde(rename = "snake_case")]
+    #[serde(rename = "files")]
     Files(PassportElementErrorFiles),
 
-    #[serde(rename = "snake_case")]
+    #[serde(rename = "translation_file")]
     TranslationFile(PassportElementErrorTranslationFile),
 
-    #[serde(rename = "snake_case")]
+    #[serde(rename = "translation_files")]
     TranslationFiles(PassportElementErrorTranslationFiles),
 
-    #[serde(rename = "snake_case")]
+    #[serde(rename = "unspecified")]
     Unspecified(PassportElementErrorUnspecified),
 }
 
@@ -407,11 +407,11 @@ pub struct PassportElementErrorUnspecified {
 }
 
 impl PassportElementErrorUnspecified {
-    pub fn new<S>(r#type: PassportElementErrorUnspecifiedType, file_hash: S) -> Self
+    pub fn new<S>(r#type: PassportElementErrorUnspecifiedType, element_hash: S) -> Self
     where
         S: Into<String>,
     {
-        Self { r#type, element_hash: file_hash.into() }
+        Self { r#type, element_hash: element_hash.into() }
     }
 
     pub fn r#type(mut self, val: PassportElementErrorUnspecifiedType) -> Self {
@@ -524,15 +524,19 @@ pub enum PassportElementErrorTranslationFilesType {
 #[serde(rename_all = "snake_case")]
 #[non_exhaustive]
 pub enum PassportElementErrorUnspecifiedType {
-    DataField,
-    FrontSide,
-    ReverseSide,
-    Selfie,
-    File,
-    Files,
-    TranslationFile,
-    TranslationFiles,
-    Unspecified,
+    PersonalDetails,
+    Passport,
+    DriverLicense,
+    IdentityCard,
+    InternalPassport,
+    Address,
+    UtilityBill,
+    BankStatement,
+    RentalAgreement,
+    PassportRegistration,
+    TemporaryRegistration,
+    PhoneNumber,
+    Email,
 }
 
 #[cfg(test)]
@@ -555,4 +559,20 @@ mod tests {
             r#"{"message":"This is an error message!","source":"data","type":"internal_passport","field_name":"The field name","data_hash":"This is a data hash"}"#
         );
     }
+
+    #[test]
+    fn serialize_front_side() {
+        let data = PassportElementError::new(
+            "Blurry",
+            PassportElementErrorKind::FrontSide(PassportElementErrorFrontSide::new(
+                PassportElementErrorFrontSideType::Passport,
+                "hash",
+            )),
+        );
+
+        assert_eq!(
+            serde_json::to_string(&data).unwrap(),
+            r#"{"message":"Blurry","source":"front_side","type":"passport","file_hash":"hash"}"#
+        );
+    }
 }
@@ -0,0 +1,1205 @@
+//! Typed data of callback buttons.
+//!
+//! Define a type deriving [`Serialize`] and [`Deserialize`] with
+//! [`callback_data!`] (or implement [`CallbackData`] for it manually) to put
+//! it into [`InlineKeyboardButton::callback`] and parse it back from
+//! [`CallbackQuery::data`] (see also [`DispatcherHandlerRxExt::data`]).
+//!
+//! # Example
+//! ```
+//! use serde::{Deserialize, Serialize};
+//! use teloxide::{
+//!     callback_data,
+//!     utils::callback_data::{str_len, CallbackData, MaxLen},
+//! };
+//!
+//! callback_data! {
+//!     #[derive(Serialize, Deserialize, Debug, PartialEq)]
+//!     enum Action {
+//!         Like { post_id: u32 },
+//!         // A reason is at most 16 bytes long.
+//!         Delete(u32, Option<String> as Option::<()>::MAX_LEN + str_len(16)),
+//!     }
+//! }
+//!
+//! let data = Action::Delete(42, Some("spam".to_owned())).to_callback_data().unwrap();
+//! assert_eq!(data, "0:1:42:1:spam");
+//! assert_eq!(
+//!     Action::from_callback_data(&data).unwrap(),
+//!     Action::Delete(42, Some("spam".to_owned()))
+//! );
+//! ```
+//!
+//! # Encoding
+//! Data is encoded positionally: values are separated by `:`, field names are
+//! omitted and enum variants are replaced by their indices, so it's usually
+//! much shorter than JSON. The first value is [`CallbackData::VERSION`].
+//!
+//! Since field names are omitted, attributes like `#[serde(flatten)]`,
+//! `#[serde(untagged)]` and `#[serde(skip_serializing_if = "...")]` are not
+//! supported.
+//!
+//! # Length
+//! Every implementation declares [`CallbackData::MAX_ENCODED_LEN`], an upper
+//! bound of its encoding, and a program using [`CallbackData`] of a type,
+//! which can be encoded into more than [`MAX_CALLBACK_DATA_LENGTH`] bytes,
+//! doesn't compile:
+//!
+//! ```compile_fail
+//! use serde::{Deserialize, Serialize};
+//! use teloxide::{
+//!     callback_data,
+//!     utils::callback_data::{str_len, CallbackData},
+//! };
+//!
+//! callback_data! {
+//!     #[derive(Serialize, Deserialize)]
+//!     struct Search(String as str_len(32));
+//! }
+//!
+//! Search("query".to_owned()).to_callback_data();
+//! ```
+//!
+//! [`callback_data!`] computes the bound from [`MaxLen`] of the fields and
+//! bounds given after `as` (built with [`variant_len`], [`str_len`],
+//! [`seq_len`] and [`max_len`]). A manual implementation declares the bound
+//! itself, so the check is only as good as the declared constant. Values,
+//! which don't fit into the bound (e.g. too long strings), are rejected with
+//! [`CallbackDataError::TooLong`] at runtime.
+//!
+//! [`Serialize`]: serde::Serialize
+//! [`Deserialize`]: serde::Deserialize
+//! [`InlineKeyboardButton::callback`]: crate::types::InlineKeyboardButton::callback
+//! [`CallbackQuery::data`]: crate::types::CallbackQuery::data
+//! [`DispatcherHandlerRxExt::data`]: crate::dispatching::DispatcherHandlerRxExt::data
+//! [`CallbackData::VERSION`]: crate::utils::callback_data::CallbackData::VERSION
+//! [`CallbackData::MAX_ENCODED_LEN`]: crate::utils::callback_data::CallbackData::MAX_ENCODED_LEN
+//! [`CallbackData`]: crate::utils::callback_data::CallbackData
+//! [`callback_data!`]: crate::callback_data
+//! [`MAX_CALLBACK_DATA_LENGTH`]: crate::utils::callback_data::MAX_CALLBACK_DATA_LENGTH
+//! [`MaxLen`]: crate::utils::callback_data::MaxLen
+//! [`variant_len`]: crate::utils::callback_data::variant_len
+//! [`str_len`]: crate::utils::callback_data::str_len
+//! [`seq_len`]: crate::utils::callback_data::seq_len
+//! [`max_len`]: crate::utils::callback_data::max_len
+//! [`CallbackDataError::TooLong`]: crate::utils::callback_data::CallbackDataError::TooLong
+
+use serde::{
+    de::{self, DeserializeOwned, DeserializeSeed, IntoDeserializer, Visitor},
+    ser, Serialize,
+};
+use std::{fmt::Display, str::FromStr};
+use thiserror::Error;
+
+/// The maximum length of callback data in bytes.
+pub const MAX_CALLBACK_DATA_LENGTH: usize = 64;
+
+/// A type which can be stored in callback buttons.
+///
+/// See the [module-level documentation](crate::utils::callback_data).
+pub trait CallbackData: Serialize + DeserializeOwned {
+    /// The version of the encoding.
+    ///
+    /// Increment it when the type changes, so data of buttons sent before
+    /// won't be misinterpreted, but rejected with
+    /// [`CallbackDataError::VersionMismatch`].
+    ///
+    /// [`CallbackDataError::VersionMismatch`]: crate::utils::callback_data::CallbackDataError::VersionMismatch
+    const VERSION: u32 = 0;
+
+    /// The maximum length of an encoded value in bytes, without the version.
+    ///
+    /// [`callback_data!`] computes it from the fields, a manual
+    /// implementation must not undercount it.
+    ///
+    /// Together with the version, it must not exceed
+    /// [`MAX_CALLBACK_DATA_LENGTH`], otherwise a program calling
+    /// [`CallbackData::to_callback_data`] doesn't compile.
+    ///
+    /// [`MAX_CALLBACK_DATA_LENGTH`]: crate::utils::callback_data::MAX_CALLBACK_DATA_LENGTH
+    /// [`CallbackData::to_callback_data`]: crate::utils::callback_data::CallbackData::to_callback_data
+    /// [`callback_data!`]: crate::callback_data
+    const MAX_ENCODED_LEN: usize;
+
+    #[doc(hidden)]
+    const ASSERT_FITS: () = assert!(
+        digits(Self::VERSION as u64) + Self::MAX_ENCODED_LEN <= MAX_CALLBACK_DATA_LENGTH,
+        "`CallbackData::MAX_ENCODED_LEN` exceeds 64 bytes"
+    );
+
+    /// Encodes `self` into a string.
+    ///
+    /// Fails with [`CallbackDataError::TooLong`], if the encoding is longer
+    /// than declared by [`CallbackData::MAX_ENCODED_LEN`].
+    ///
+    /// [`CallbackDataError::TooLong`]: crate::utils::callback_data::CallbackDataError::TooLong
+    /// [`CallbackData::MAX_ENCODED_LEN`]: crate::utils::callback_data::CallbackData::MAX_ENCODED_LEN
+    fn to_callback_data(&self) -> Result<String, CallbackDataError> {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::ASSERT_FITS;
+
+        let mut serializer = Serializer { out: Self::VERSION.to_string() };
+        self.serialize(&mut serializer)?;
+
+        let max_len = digits(Self::VERSION as u64) + Self::MAX_ENCODED_LEN;
+        match serializer.out.len() {
+            len if len > max_len => Err(CallbackDataError::TooLong { len, max_len }),
+            _ => Ok(serializer.out),
+        }
+    }
+
+    /// Decodes data encoded by [`CallbackData::to_callback_data`].
+    ///
+    /// [`CallbackData::to_callback_data`]: crate::utils::callback_data::CallbackData::to_callback_data
+    fn from_callback_data(data: &str) -> Result<Self, CallbackDataError> {
+        let mut deserializer = Deserializer { tokens: split(data).into_iter() };
+
+        let version = deserializer.parse()?;
+        if version != Self::VERSION {
+            return Err(CallbackDataError::VersionMismatch {
+                expected: Self::VERSION,
+                found: version,
+            });
+        }
+
+        let value = Self::deserialize(&mut deserializer)?;
+        match deserializer.tokens.next() {
+            Some(_) => Err(CallbackDataError::Invalid("trailing data".to_owned())),
+            None => Ok(value),
+        }
+    }
+}
+
+/// An upper bound of an encoded value, a part of
+/// [`CallbackData::MAX_ENCODED_LEN`].
+///
+/// Floats, strings and collections are not bounded, use [`str_len`] and
+/// [`seq_len`] with their expected sizes instead.
+///
+/// [`CallbackData::MAX_ENCODED_LEN`]: crate::utils::callback_data::CallbackData::MAX_ENCODED_LEN
+/// [`str_len`]: crate::utils::callback_data::str_len
+/// [`seq_len`]: crate::utils::callback_data::seq_len
+pub trait MaxLen {
+    /// The maximum length of an encoded value in bytes.
+    const MAX_LEN: usize;
+}
+
+macro_rules! max_len {
+    ($($ty:ty => $len:expr),* $(,)?) => {
+        $(
+            impl MaxLen for $ty {
+                const MAX_LEN: usize = $len;
+            }
+        )*
+    };
+}
+
+// A separator and a value, e.g. `:-128`.
+max_len! {
+    () => 0,
+    bool => 2,
+    char => 5,
+    u8 => 4,
+    u16 => 6,
+    u32 => 11,
+    u64 => 21,
+    usize => 21,
+    i8 => 5,
+    i16 => 7,
+    i32 => 12,
+    i64 => 21,
+    isize => 21,
+}
+
+impl<T: MaxLen> MaxLen for Option<T> {
+    const MAX_LEN: usize = 2 + T::MAX_LEN;
+}
+
+impl<A: MaxLen, B: MaxLen> MaxLen for (A, B) {
+    const MAX_LEN: usize = A::MAX_LEN + B::MAX_LEN;
+}
+
+impl<A: MaxLen, B: MaxLen, C: MaxLen> MaxLen for (A, B, C) {
+    const MAX_LEN: usize = A::MAX_LEN + B::MAX_LEN + C::MAX_LEN;
+}
+
+/// The maximum length of an encoded variant index of an enum with `variants`
+/// variants.
+pub const fn variant_len(variants: usize) -> usize {
+    1 + digits(variants.saturating_sub(1) as u64)
+}
+
+/// The maximum length of an encoded string of at most `max_bytes` bytes
+/// (special characters are escaped).
+pub const fn str_len(max_bytes: usize) -> usize {
+    1 + 2 * max_bytes
+}
+
+/// The maximum length of an encoded sequence of at most `max_items` items,
+/// each of which is at most `item_len` bytes long.
+///
+/// Maps are sequences of key-value pairs.
+pub const fn seq_len(max_items: usize, item_len: usize) -> usize {
+    1 + digits(max_items as u64) + max_items * item_len
+}
+
+/// The maximum of two lengths, e.g. of two enum variants.
+pub const fn max_len(a: usize, b: usize) -> usize {
+    if a > b {
+        a
+    } else {
+        b
+    }
+}
+
+#[doc(hidden)]
+pub const fn max_len_of(lens: &[usize]) -> usize {
+    let mut max = 0;
+    let mut i = 0;
+    while i < lens.len() {
+        max = max_len(max, lens[i]);
+        i += 1;
+    }
+    max
+}
+
+const fn digits(mut n: u64) -> usize {
+    let mut digits = 1;
+    while n >= 10 {
+        n /= 10;
+        digits += 1;
+    }
+    digits
+}
+
+/// Defines a type and implements [`CallbackData`] and [`MaxLen`] for it with
+/// a length bound computed from its fields.
+///
+/// Structs (with named, unnamed or no fields) and enums without generics are
+/// supported. The bound of a field is [`MaxLen::MAX_LEN`] of its type or, for
+/// types without one (strings, collections), an expression after `as`. An
+/// optional `version = ...;` before the type sets [`CallbackData::VERSION`].
+///
+/// # Example
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use teloxide::{
+///     callback_data,
+///     utils::callback_data::{str_len, CallbackData, MaxLen},
+/// };
+///
+/// callback_data! {
+///     version = 1;
+///
+///     #[derive(Serialize, Deserialize, Debug, PartialEq)]
+///     enum Action {
+///         Like { post_id: u32 },
+///         // A reason is at most 16 bytes long.
+///         Delete(u32, Option<String> as Option::<()>::MAX_LEN + str_len(16)),
+///     }
+/// }
+///
+/// assert_eq!(Action::MAX_ENCODED_LEN, 2 + 11 + 2 + 33);
+/// assert_eq!(Action::Like { post_id: 7 }.to_callback_data().unwrap(), "1:0:7");
+/// ```
+///
+/// [`CallbackData`]: crate::utils::callback_data::CallbackData
+/// [`MaxLen`]: crate::utils::callback_data::MaxLen
+/// [`MaxLen::MAX_LEN`]: crate::utils::callback_data::MaxLen::MAX_LEN
+/// [`CallbackData::VERSION`]: crate::utils::callback_data::CallbackData::VERSION
+#[macro_export]
+macro_rules! callback_data {
+    (@len $ty:ty) => {
+        <$ty as $crate::utils::callback_data::MaxLen>::MAX_LEN
+    };
+    (@len $ty:ty, $len:expr) => {
+        $len
+    };
+    (@impl $name:ident [$($version:expr)?] $len:expr) => {
+        impl $crate::utils::callback_data::CallbackData for $name {
+            $(const VERSION: u32 = $version;)?
+            const MAX_ENCODED_LEN: usize = $len;
+        }
+
+        impl $crate::utils::callback_data::MaxLen for $name {
+            const MAX_LEN: usize = $len;
+        }
+    };
+    (@type [$($version:expr)?] $(#[$meta:meta])* $vis:vis struct $name:ident {
+        $($(#[$fmeta:meta])* $fvis:vis $field:ident: $fty:ty $(as $flen:expr)?),* $(,)?
+    }) => {
+        $(#[$meta])* $vis struct $name {
+            $($(#[$fmeta])* $fvis $field: $fty),*
+        }
+
+        $crate::callback_data!(
+            @impl $name [$($version)?] 0 $(+ $crate::callback_data!(@len $fty $(, $flen)?))*
+        );
+    };
+    (@type [$($version:expr)?] $(#[$meta:meta])* $vis:vis struct $name:ident(
+        $($(#[$fmeta:meta])* $fvis:vis $fty:ty $(as $flen:expr)?),* $(,)?
+    );) => {
+        $(#[$meta])* $vis struct $name($($(#[$fmeta])* $fvis $fty),*);
+
+        $crate::callback_data!(
+            @impl $name [$($version)?] 0 $(+ $crate::callback_data!(@len $fty $(, $flen)?))*
+        );
+    };
+    (@type [$($version:expr)?] $(#[$meta:meta])* $vis:vis struct $name:ident;) => {
+        $(#[$meta])* $vis struct $name;
+
+        $crate::callback_data!(@impl $name [$($version)?] 0);
+    };
+    (@type [$($version:expr)?] $(#[$meta:meta])* $vis:vis enum $name:ident {
+        $(
+            $(#[$vmeta:meta])* $variant:ident
+            $({ $($(#[$fmeta:meta])* $field:ident: $fty:ty $(as $flen:expr)?),* $(,)? })?
+            $(($($(#[$tmeta:meta])* $tty:ty $(as $tlen:expr)?),* $(,)?))?
+        ),* $(,)?
+    }) => {
+        $(#[$meta])* $vis enum $name {
+            $(
+                $(#[$vmeta])* $variant
+                $({ $($(#[$fmeta])* $field: $fty),* })?
+                $(($($(#[$tmeta])* $tty),*))?
+            ),*
+        }
+
+        $crate::callback_data!(
+            @impl $name [$($version)?]
+            $crate::utils::callback_data::variant_len([$(stringify!($variant)),*].len())
+                + $crate::utils::callback_data::max_len_of(&[$(
+                    0 $($(+ $crate::callback_data!(@len $fty $(, $flen)?))*)?
+                        $($(+ $crate::callback_data!(@len $tty $(, $tlen)?))*)?
+                ),*])
+        );
+    };
+    (version = $version:expr; $($type:tt)*) => {
+        $crate::callback_data!(@type [$version] $($type)*);
+    };
+    ($(#[$meta:meta])* $vis:vis $kind:ident $($type:tt)*) => {
+        $crate::callback_data!(@type [] $(#[$meta])* $vis $kind $($type)*);
+    };
+}
+
+/// Errors occurring while encoding and decoding [`CallbackData`].
+///
+/// [`CallbackData`]: crate::utils::callback_data::CallbackData
+#[derive(Debug, Error, Clone, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum CallbackDataError {
+    #[error("callback data is {len} bytes long, but at most {max_len} bytes are declared")]
+    TooLong { len: usize, max_len: usize },
+
+    #[error("callback data of version {found}, but version {expected} is expected")]
+    VersionMismatch { expected: u32, found: u32 },
+
+    #[error("invalid callback data: {0}")]
+    Invalid(String),
+}
+
+impl ser::Error for CallbackDataError {
+    fn custom<T>(msg: T) -> Self
+    where
+        T: Display,
+    {
+        Self::Invalid(msg.to_string())
+    }
+}
+
+impl de::Error for CallbackDataError {
+    fn custom<T>(msg: T) -> Self
+    where
+        T: Display,
+    {
+        Self::Invalid(msg.to_string())
+    }
+}
+
+fn unsupported(what: &str) -> CallbackDataError {
+    CallbackDataError::Invalid(format!("{} are not supported", what))
+}
+
+/// Splits data into unescaped values.
+fn split(data: &str) -> Vec<String> {
+    let mut tokens = vec![String::new()];
+    let mut chars = data.chars();
+    while let Some(c) = chars.next() {
+        let token = tokens.last_mut().unwrap();
+        match c {
+            '\\' => token.extend(chars.next()),
+            ':' => tokens.push(String::new()),
+            c => token.push(c),
+        }
+    }
+    tokens
+}
+
+struct Serializer {
+    out: String,
+}
+
+impl Serializer {
+    fn token<T>(&mut self, value: T) -> Result<(), CallbackDataError>
+    where
+        T: Display,
+    {
+        self.out.push(':');
+        for c in value.to_string().chars() {
+            if c == ':' || c == '\\' {
+                self.out.push('\\');
+            }
+            self.out.push(c);
+        }
+        Ok(())
+    }
+}
+
+impl ser::Serializer for &mut Serializer {
+    type Ok = ();
+    type Error = CallbackDataError;
+
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    fn serialize_bool(self, v: bool) -> Result<(), CallbackDataError> {
+        self.token(v as u8)
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), CallbackDataError> {
+        self.token(v)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), CallbackDataError> {
+        self.token(v)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), CallbackDataError> {
+        self.token(v)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), CallbackDataError> {
+        self.token(v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), CallbackDataError> {
+        self.token(v)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), CallbackDataError> {
+        self.token(v)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), CallbackDataError> {
+        self.token(v)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), CallbackDataError> {
+        self.token(v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), CallbackDataError> {
+        self.token(v)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), CallbackDataError> {
+        self.token(v)
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), CallbackDataError> {
+        self.token(v)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), CallbackDataError> {
+        self.token(v)
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), CallbackDataError> {
+        self.token(v.len())?;
+        v.iter().try_for_each(|byte| self.token(byte))
+    }
+
+    fn serialize_none(self) -> Result<(), CallbackDataError> {
+        self.token(0)
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<(), CallbackDataError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.token(1)?;
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), CallbackDataError> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), CallbackDataError> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<(), CallbackDataError> {
+        self.token(variant_index)
+    }
+
+    fn serialize_newtype_struct<T>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), CallbackDataError>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> Result<(), CallbackDataError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.token(variant_index)?;
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self, CallbackDataError> {
+        self.token(len.ok_or_else(|| unsupported("sequences of unknown length"))?)?;
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self, CallbackDataError> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self, CallbackDataError> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self, CallbackDataError> {
+        self.token(variant_index)?;
+        Ok(self)
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self, CallbackDataError> {
+        self.token(len.ok_or_else(|| unsupported("maps of unknown length"))?)?;
+        Ok(self)
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self, CallbackDataError> {
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self, CallbackDataError> {
+        self.token(variant_index)?;
+        Ok(self)
+    }
+}
+
+impl ser::SerializeSeq for &mut Serializer {
+    type Ok = ();
+    type Error = CallbackDataError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), CallbackDataError>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), CallbackDataError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTuple for &mut Serializer {
+    type Ok = ();
+    type Error = CallbackDataError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), CallbackDataError>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), CallbackDataError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTupleStruct for &mut Serializer {
+    type Ok = ();
+    type Error = CallbackDataError;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), CallbackDataError>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), CallbackDataError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTupleVariant for &mut Serializer {
+    type Ok = ();
+    type Error = CallbackDataError;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), CallbackDataError>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), CallbackDataError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeMap for &mut Serializer {
+    type Ok = ();
+    type Error = CallbackDataError;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), CallbackDataError>
+    where
+        T: Serialize + ?Sized,
+    {
+        key.serialize(&mut **self)
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), CallbackDataError>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), CallbackDataError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeStruct for &mut Serializer {
+    type Ok = ();
+    type Error = CallbackDataError;
+
+    fn serialize_field<T>(&mut self, _key: &'static str, value: &T) -> Result<(), CallbackDataError>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(&mut **self)
+    }
+
+    fn skip_field(&mut self, _key: &'static str) -> Result<(), CallbackDataError> {
+        Err(unsupported("skipped fields"))
+    }
+
+    fn end(self) -> Result<(), CallbackDataError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeStructVariant for &mut Serializer {
+    type Ok = ();
+    type Error = CallbackDataError;
+
+    fn serialize_field<T>(&mut self, _key: &'static str, value: &T) -> Result<(), CallbackDataError>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(&mut **self)
+    }
+
+    fn skip_field(&mut self, _key: &'static str) -> Result<(), CallbackDataError> {
+        Err(unsupported("skipped fields"))
+    }
+
+    fn end(self) -> Result<(), CallbackDataError> {
+        Ok(())
+    }
+}
+
+struct Deserializer {
+    tokens: std::vec::IntoIter<String>,
+}
+
+impl Deserializer {
+    fn next(&mut self) -> Result<String, CallbackDataError> {
+        self.tokens
+            .next()
+            .ok_or_else(|| CallbackDataError::Invalid("unexpected end of data".to_owned()))
+    }
+
+    fn parse<T>(&mut self) -> Result<T, CallbackDataError>
+    where
+        T: FromStr,
+    {
+        let token = self.next()?;
+        token.parse().map_err(|_| CallbackDataError::Invalid(format!("unexpected `{}`", token)))
+    }
+}
+
+impl<'de> de::Deserializer<'de> for &mut Deserializer {
+    type Error = CallbackDataError;
+
+    fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value, CallbackDataError>
+    where
+        V: Visitor<'de>,
+    {
+        Err(unsupported("self-describing types"))
+    }
+
+    fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value, CallbackDataError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_bool(self.parse::<u8>()? != 0)
+    }
+
+    fn deserialize_i8<V>(self, visitor: V) -> Result<V::Value, CallbackDataError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_i8(self.parse()?)
+    }
+
+    fn deserialize_i16<V>(self, visitor: V) -> Result<V::Value, CallbackDataError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_i16(self.parse()?)
+    }
+
+    fn deserialize_i32<V>(self, visitor: V) -> Result<V::Value, CallbackDataError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_i32(self.parse()?)
+    }
+
+    fn deserialize_i64<V>(self, visitor: V) -> Result<V::Value, CallbackDataError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_i64(self.parse()?)
+    }
+
+    fn deserialize_u8<V>(self, visitor: V) -> Result<V::Value, CallbackDataError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_u8(self.parse()?)
+    }
+
+    fn deserialize_u16<V>(self, visitor: V) -> Result<V::Value, CallbackDataError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_u16(self.parse()?)
+    }
+
+    fn deserialize_u32<V>(self, visitor: V) -> Result<V::Value, CallbackDataError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_u32(self.parse()?)
+    }
+
+    fn deserialize_u64<V>(self, visitor: V) -> Result<V::Value, CallbackDataError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_u64(self.parse()?)
+    }
+
+    fn deserialize_f32<V>(self, visitor: V) -> Result<V::Value, CallbackDataError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_f32(self.parse()?)
+    }
+
+    fn deserialize_f64<V>(self, visitor: V) -> Result<V::Value, CallbackDataError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_f64(self.parse()?)
+    }
+
+    fn deserialize_char<V>(self, visitor: V) -> Result<V::Value, CallbackDataError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_char(self.parse()?)
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, CallbackDataError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_string(self.next()?)
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, CallbackDataError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_string(self.next()?)
+    }
+
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, CallbackDataError>
+    where
+        V: Visitor<'de>,
+    {
+        let len = self.parse()?;
+        let bytes = (0..len).map(|_| self.parse()).collect::<Result<Vec<u8>, _>>()?;
+        visitor.visit_byte_buf(bytes)
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, CallbackDataError>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, CallbackDataError>
+    where
+        V: Visitor<'de>,
+    {
+        match self.parse::<u8>()? {
+            0 => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, CallbackDataError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, CallbackDataError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, CallbackDataError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, CallbackDataError>
+    where
+        V: Visitor<'de>,
+    {
+        let len = self.parse()?;
+        visitor.visit_seq(Access { de: self, len })
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, CallbackDataError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_seq(Access { de: self, len })
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, CallbackDataError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_seq(Access { de: self, len })
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, CallbackDataError>
+    where
+        V: Visitor<'de>,
+    {
+        let len = self.parse()?;
+        visitor.visit_map(Access { de: self, len })
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, CallbackDataError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_seq(Access { de: self, len: fields.len() })
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, CallbackDataError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_enum(self)
+    }
+
+    fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value, CallbackDataError>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, CallbackDataError>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_any(visitor)
+    }
+}
+
+/// Access to elements of sequences and maps of the known length.
+struct Access<'a> {
+    de: &'a mut Deserializer,
+    len: usize,
+}
+
+impl<'de> de::SeqAccess<'de> for Access<'_> {
+    type Error = CallbackDataError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, CallbackDataError>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        if self.len == 0 {
+            return Ok(None);
+        }
+        self.len -= 1;
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len)
+    }
+}
+
+impl<'de> de::MapAccess<'de> for Access<'_> {
+    type Error = CallbackDataError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, CallbackDataError>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        de::SeqAccess::next_element_seed(self, seed)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, CallbackDataError>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        seed.deserialize(&mut *self.de)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len)
+    }
+}
+
+impl<'de> de::EnumAccess<'de> for &mut Deserializer {
+    type Error = CallbackDataError;
+    type Variant = Self;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self), CallbackDataError>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let index: u32 = self.parse()?;
+        let value = seed.deserialize(index.into_deserializer())?;
+        Ok((value, self))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for &mut Deserializer {
+    type Error = CallbackDataError;
+
+    fn unit_variant(self) -> Result<(), CallbackDataError> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, CallbackDataError>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value, CallbackDataError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_seq(Access { de: self, len })
+    }
+
+    fn struct_variant<V>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, CallbackDataError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_seq(Access { de: self, len: fields.len() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use std::collections::BTreeMap;
+
+    callback_data! {
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        enum Action {
+            Refresh,
+            // At most 20 bytes of a string and 4 options.
+            Open(String as str_len(20)),
+            Vote { poll: u64, options: Vec<u8> as seq_len(4, u8::MAX_LEN), anonymous: bool },
+            Move(i32, i32),
+        }
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Page {
+        tags: BTreeMap<String, Option<char>>,
+        unit: (),
+    }
+
+    impl CallbackData for Page {
+        const VERSION: u32 = 3;
+        // At most 2 tags of 1 byte.
+        const MAX_ENCODED_LEN: usize = seq_len(2, str_len(1) + Option::<char>::MAX_LEN);
+    }
+
+    callback_data! {
+        version = 12;
+
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        struct Point {
+            x: u8,
+            #[serde(default)]
+            pub y: Option<u8>,
+        }
+    }
+
+    callback_data! {
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        pub(crate) struct Pair(Point, pub bool);
+    }
+
+    callback_data! {
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        struct Empty;
+    }
+
+    fn round_trip<T>(value: T, data: &str)
+    where
+        T: CallbackData + std::fmt::Debug + PartialEq,
+    {
+        assert_eq!(value.to_callback_data().unwrap(), data);
+        assert_eq!(T::from_callback_data(data).unwrap(), value);
+    }
+
+    #[test]
+    fn encoding() {
+        round_trip(Action::Refresh, "0:0");
+        round_trip(Action::Open("a:b\\c".to_owned()), "0:1:a\\:b\\\\c");
+        round_trip(Action::Open(String::new()), "0:1:");
+        round_trip(Action::Vote { poll: 7, options: vec![1, 3], anonymous: true }, "0:2:7:2:1:3:1");
+        round_trip(Action::Move(-1, 2), "0:3:-1:2");
+
+        let tags = vec![("x".to_owned(), Some('y')), ("z".to_owned(), None)].into_iter().collect();
+        round_trip(Page { tags, unit: () }, "3:2:x:1:y:z:0");
+    }
+
+    #[test]
+    fn lengths() {
+        assert_eq!(Action::MAX_ENCODED_LEN, 43);
+        assert_eq!((Point::VERSION, Point::MAX_ENCODED_LEN), (12, 10));
+        assert_eq!((Pair::VERSION, Pair::MAX_ENCODED_LEN), (0, 12));
+        assert_eq!(Empty::MAX_ENCODED_LEN, 0);
+
+        round_trip(Pair(Point { x: 255, y: Some(0) }, false), "0:255:1:0:0");
+        round_trip(Empty, "0");
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(Action::Open("x".repeat(20)).to_callback_data().map(|data| data.len()), Ok(24));
+        assert_eq!(
+            Action::Open(":".repeat(21)).to_callback_data(),
+            Err(CallbackDataError::TooLong { len: 46, max_len: 44 })
+        );
+        assert_eq!(
+            Page::from_callback_data("0:0:"),
+            Err(CallbackDataError::VersionMismatch { expected: 3, found: 0 })
+        );
+        assert!(Action::from_callback_data("0:4").is_err());
+        assert!(Action::from_callback_data("0:3:1").is_err());
+        assert!(Action::from_callback_data("0:0:1").is_err());
+        assert!(Action::from_callback_data("").is_err());
+    }
+}
@@ -0,0 +1,437 @@
+//! Formatting of markup with automatically escaped arguments.
+//!
+//! See [`html_format!`] and [`markdown_format!`].
+//!
+//! [`html_format!`]: crate::html_format
+//! [`markdown_format!`]: crate::markdown_format
+
+use crate::utils::{html, markdown};
+use std::fmt::{self, Display};
+
+/// Formats a string with the [HTML style], escaping all the arguments.
+///
+/// The template is trusted markup, while arguments are escaped by
+/// [`html::escape`], so user-controlled strings cannot inject tags. Wrap an
+/// argument in [`Raw`] to insert already formatted markup as is.
+///
+/// The template is validated at compile time: unclosed or unsupported tags
+/// fail to compile. Arguments are formatted via [`Display`], so placeholders
+/// must be either `{}` or positional like `{0}`. Named placeholders (which
+/// would capture variables unescaped) and formatting parameters like `{:?}`
+/// are rejected.
+///
+/// # Example
+/// ```
+/// use teloxide::{html_format, utils::format::Raw};
+///
+/// let name = "<script>";
+/// let text = html_format!("Hello, <b>{}</b>! {}", name, Raw("<i>Bye</i>"));
+///
+/// assert_eq!(text, "Hello, <b>&lt;script&gt;</b>! <i>Bye</i>");
+/// ```
+///
+/// ```compile_fail
+/// teloxide::html_format!("<b>Unclosed {}", 1);
+/// ```
+///
+/// ```compile_fail
+/// let x = "<script>";
+/// teloxide::html_format!("{x}");
+/// ```
+///
+/// [HTML style]: https://core.telegram.org/bots/api#html-style
+/// [`html::escape`]: crate::utils::html::escape
+/// [`Raw`]: crate::utils::format::Raw
+/// [`Display`]: std::fmt::Display
+#[macro_export]
+macro_rules! html_format {
+    ($template:literal $(, $arg:expr)* $(,)?) => {{
+        const _: () = ::std::assert!(
+            $crate::utils::format::is_valid_html_template($template),
+            "invalid html_format! template"
+        );
+        ::std::format!($template $(, $crate::utils::format::Html(&$arg))*)
+    }};
+}
+
+/// Formats a string with the [MarkdownV2 style], escaping all the arguments.
+///
+/// The template is trusted markup, while arguments are escaped, so
+/// user-controlled strings cannot inject markup. Wrap an argument in [`Raw`]
+/// to insert already formatted markup as is.
+///
+/// The template is validated at compile time: unescaped reserved characters
+/// or unclosed entities fail to compile. Remember that `{` and `}` are
+/// reserved too, so they're written as `\\{{` and `\\}}`. Arguments are
+/// formatted via [`Display`], so placeholders must be either `{}` or
+/// positional like `{0}`. Named placeholders (which would capture variables
+/// unescaped) and formatting parameters like `{:?}` are rejected.
+///
+/// # Example
+/// ```
+/// use teloxide::markdown_format;
+///
+/// let price = 1.5;
+/// let text = markdown_format!("*Total:* {} USD", price);
+///
+/// assert_eq!(text, "*Total:* 1\\.5 USD");
+/// ```
+///
+/// ```compile_fail
+/// teloxide::markdown_format!("Total: {}.", 1);
+/// ```
+///
+/// ```compile_fail
+/// let x = "*bold*";
+/// teloxide::markdown_format!("{x}");
+/// ```
+///
+/// [MarkdownV2 style]: https://core.telegram.org/bots/api#markdownv2-style
+/// [`Raw`]: crate::utils::format::Raw
+/// [`Display`]: std::fmt::Display
+#[macro_export]
+macro_rules! markdown_format {
+    ($template:literal $(, $arg:expr)* $(,)?) => {{
+        const _: () = ::std::assert!(
+            $crate::utils::format::is_valid_markdown_v2_template($template),
+            "invalid markdown_format! template"
+        );
+        ::std::format!($template $(, $crate::utils::format::MarkdownV2(&$arg))*)
+    }};
+}
+
+/// Inserts an argument of [`html_format!`] and [`markdown_format!`] without
+/// escaping.
+///
+/// [`html_format!`]: crate::html_format
+/// [`markdown_format!`]: crate::markdown_format
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Raw<T>(pub T);
+
+/// An argument of [`html_format!`] and [`markdown_format!`].
+///
+/// [`html_format!`]: crate::html_format
+/// [`markdown_format!`]: crate::markdown_format
+pub trait FormatArg {
+    fn to_html(&self) -> String;
+    fn to_markdown_v2(&self) -> String;
+}
+
+impl<T> FormatArg for T
+where
+    T: Display + ?Sized,
+{
+    fn to_html(&self) -> String {
+        html::escape(&self.to_string()).replace("\"", "&quot;")
+    }
+
+    fn to_markdown_v2(&self) -> String {
+        markdown::escape(&self.to_string().replace(r"\", r"\\"))
+    }
+}
+
+impl<T> FormatArg for Raw<T>
+where
+    T: Display,
+{
+    fn to_html(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn to_markdown_v2(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+#[doc(hidden)]
+pub struct Html<'a, T: ?Sized>(pub &'a T);
+
+impl<T> Display for Html<'_, T>
+where
+    T: FormatArg + ?Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0.to_html())
+    }
+}
+
+#[doc(hidden)]
+pub struct MarkdownV2<'a, T: ?Sized>(pub &'a T);
+
+impl<T> Display for MarkdownV2<'_, T>
+where
+    T: FormatArg + ?Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0.to_markdown_v2())
+    }
+}
+
+/// The maximum nesting of entities in a template.
+const MAX_DEPTH: usize = 16;
+
+/// Checks that placeholders of a template are either `{}` or positional,
+/// since other ones (like `{name}`) aren't wrapped into escaping types.
+const fn are_valid_placeholders(s: &[u8]) -> bool {
+    let mut i = 0;
+    while i < s.len() {
+        if (s[i] == b'{' || s[i] == b'}') && i + 1 < s.len() && s[i + 1] == s[i] {
+            i += 2;
+        } else if s[i] == b'{' {
+            i += 1;
+            while i < s.len() && s[i].is_ascii_digit() {
+                i += 1;
+            }
+            if i == s.len() || s[i] != b'}' {
+                return false;
+            }
+            i += 1;
+        } else if s[i] == b'}' {
+            return false;
+        } else {
+            i += 1;
+        }
+    }
+
+    true
+}
+
+/// Checks that tags of an [`html_format!`] template are supported and
+/// balanced.
+///
+/// [`html_format!`]: crate::html_format
+#[doc(hidden)]
+pub const fn is_valid_html_template(template: &str) -> bool {
+    let s = template.as_bytes();
+    if !are_valid_placeholders(s) {
+        return false;
+    }
+    let mut stack = [0; MAX_DEPTH];
+    let mut depth = 0;
+
+    let mut i = 0;
+    while i < s.len() {
+        if s[i] != b'<' {
+            i += 1;
+            continue;
+        }
+
+        let closing = i + 1 < s.len() && s[i + 1] == b'/';
+        let name_start = if closing { i + 2 } else { i + 1 };
+        let mut name_end = name_start;
+        while name_end < s.len() && s[name_end].is_ascii_alphabetic() {
+            name_end += 1;
+        }
+        let mut end = name_end;
+        while end < s.len() && s[end] != b'>' {
+            end += 1;
+        }
+
+        let tag = html_tag(s, name_start, name_end);
+        if tag == 0 || end == s.len() {
+            return false;
+        }
+
+        if closing {
+            if depth == 0 || stack[depth - 1] != tag {
+                return false;
+            }
+            depth -= 1;
+        } else {
+            if depth == MAX_DEPTH {
+                return false;
+            }
+            stack[depth] = tag;
+            depth += 1;
+        }
+        i = end + 1;
+    }
+
+    depth == 0
+}
+
+/// Returns a non-zero identifier of a supported tag named `s[start..end]`.
+const fn html_tag(s: &[u8], start: usize, end: usize) -> u8 {
+    const TAGS: [&str; 12] =
+        ["b", "strong", "i", "em", "u", "ins", "s", "strike", "del", "a", "code", "pre"];
+
+    let mut tag = 0;
+    while tag < TAGS.len() {
+        let name = TAGS[tag].as_bytes();
+        if name.len() == end - start {
+            let mut i = 0;
+            // Names contain only ASCII letters, so `| 0x20` makes them
+            // lowercase.
+            while i < name.len() && s[start + i] | 0x20 == name[i] {
+                i += 1;
+            }
+            if i == name.len() {
+                return tag as u8 + 1;
+            }
+        }
+        tag += 1;
+    }
+
+    0
+}
+
+/// Checks that a [`markdown_format!`] template has no unescaped reserved
+/// characters and that its entities are closed and properly nested.
+///
+/// [`markdown_format!`]: crate::markdown_format
+#[doc(hidden)]
+pub const fn is_valid_markdown_v2_template(template: &str) -> bool {
+    const LINK: u8 = b'[';
+    const UNDERLINE: u8 = 0;
+
+    let s = template.as_bytes();
+    if !are_valid_placeholders(s) {
+        return false;
+    }
+    let mut stack = [0; MAX_DEPTH];
+    let mut depth = 0;
+
+    let mut i = 0;
+    while i < s.len() {
+        match s[i] {
+            // `\{{` and `\}}` are escaped `{` and `}` after formatting.
+            b'\\' if i + 2 < s.len() && (s[i + 1] == b'{' || s[i + 1] == b'}') => {
+                if s[i + 2] != s[i + 1] {
+                    return false;
+                }
+                i += 3;
+            }
+            b'\\' => i += 2,
+            b'{' if i + 1 < s.len() && s[i + 1] == b'{' => return false,
+            // A placeholder, which is escaped and validated above.
+            b'{' => {
+                while i < s.len() && s[i] != b'}' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            b'}' => return false,
+            b'`' => {
+                let fence =
+                    if i + 2 < s.len() && s[i + 1] == b'`' && s[i + 2] == b'`' { 3 } else { 1 };
+                i += fence;
+                loop {
+                    if i >= s.len() {
+                        return false;
+                    }
+                    if s[i] == b'\\' {
+                        i += 2;
+                    } else if s[i] == b'`'
+                        && (fence == 1 || (i + 2 < s.len() && s[i + 1] == b'`' && s[i + 2] == b'`'))
+                    {
+                        i += fence;
+                        break;
+                    } else {
+                        i += 1;
+                    }
+                }
+            }
+            marker @ b'*' | marker @ b'_' | marker @ b'~' => {
+                let (marker, len) = if marker == b'_' && i + 1 < s.len() && s[i + 1] == b'_' {
+                    (UNDERLINE, 2)
+                } else {
+                    (marker, 1)
+                };
+
+                if depth > 0 && stack[depth - 1] == marker {
+                    depth -= 1;
+                } else {
+                    let mut j = 0;
+                    while j < depth {
+                        if stack[j] == marker {
+                            return false;
+                        }
+                        j += 1;
+                    }
+                    if depth == MAX_DEPTH {
+                        return false;
+                    }
+                    stack[depth] = marker;
+                    depth += 1;
+                }
+                i += len;
+            }
+            b'[' => {
+                if depth == MAX_DEPTH {
+                    return false;
+                }
+                stack[depth] = LINK;
+                depth += 1;
+                i += 1;
+            }
+            b']' => {
+                if depth == 0 || stack[depth - 1] != LINK || i + 1 >= s.len() || s[i + 1] != b'(' {
+                    return false;
+                }
+                depth -= 1;
+                i += 2;
+                loop {
+                    if i >= s.len() {
+                        return false;
+                    }
+                    if s[i] == b'\\' {
+                        i += 2;
+                    } else if s[i] == b')' {
+                        i += 1;
+                        break;
+                    } else {
+                        i += 1;
+                    }
+                }
+            }
+            b'(' | b')' | b'>' | b'#' | b'+' | b'-' | b'=' | b'|' | b'.' | b'!' => return false,
+            _ => i += 1,
+        }
+    }
+
+    depth == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn html_template() {
+        assert!(is_valid_html_template("<b>{}</b> <A HREF=\"{}\">x</A> a > b &amp; {{}}"));
+        assert!(is_valid_html_template("<pre><code class=\"language-rust\">{}</code></pre>"));
+        assert!(!is_valid_html_template("<b>{}"));
+        assert!(!is_valid_html_template("<b><i>{}</b></i>"));
+        assert!(!is_valid_html_template("<span>{}</span>"));
+        assert!(!is_valid_html_template("<b"));
+        assert!(is_valid_html_template("<b>{0}</b> {1}"));
+        assert!(!is_valid_html_template("<b>{name}</b>"));
+        assert!(!is_valid_html_template("<a href=\"{url}\">x</a>"));
+        assert!(!is_valid_html_template("{:?}"));
+        assert!(!is_valid_html_template("{0:>5}"));
+    }
+
+    #[test]
+    fn markdown_v2_template() {
+        assert!(is_valid_markdown_v2_template(
+            "*{}* _\r__{0}__\r_ [{}]({}) `a.b` ```\n{}.\n``` \\{{{}\\}} 1\\.5"
+        ));
+        assert!(!is_valid_markdown_v2_template("{}."));
+        assert!(!is_valid_markdown_v2_template("{{}}"));
+        assert!(!is_valid_markdown_v2_template("*{}"));
+        assert!(!is_valid_markdown_v2_template("*_{}*_"));
+        assert!(!is_valid_markdown_v2_template("[{}]"));
+        assert!(!is_valid_markdown_v2_template("`{}"));
+        assert!(!is_valid_markdown_v2_template("*{name}*"));
+        assert!(!is_valid_markdown_v2_template("{:?}"));
+    }
+
+    #[test]
+    fn escaping() {
+        assert_eq!(
+            html_format!("<a href=\"{}\">{}</a>", "\"><b>", Raw("<i>x</i>")),
+            "<a href=\"&quot;&gt;&lt;b&gt;\"><i>x</i></a>"
+        );
+        assert_eq!(markdown_format!("*{}* {}", "a_b\\", Raw("_c_")), "*a\\_b\\\\* _c_");
+    }
+}
@@ -0,0 +1,293 @@
+use crate::{
+    types::{MessageEntity, MessageEntityKind, User},
+    utils::render::{render_html, render_markdown_v2},
+};
+
+/// A text with [`MessageEntity`]s describing its formatting.
+///
+/// Unlike [`html`] and [`markdown`], it doesn't need escaping: the text is
+/// sent as is and the formatting is passed via the `entities` (or
+/// `caption_entities`) parameter, so [`KnownApiErrorKind::CantParseEntities`]
+/// cannot happen. Offsets and lengths of the entities are computed in UTF-16
+/// code units, as Telegram requires.
+///
+/// # Example
+/// ```
+/// use teloxide::utils::FormattedText;
+///
+/// let text = FormattedText::new()
+///     .plain("Hello, ")
+///     .bold(FormattedText::new().plain("dear ").italic("<friend>"))
+///     .plain("!");
+///
+/// assert_eq!(text.text(), "Hello, dear <friend>!");
+/// assert_eq!(text.to_html(), "Hello, <b>dear <i>&lt;friend&gt;</i></b>!");
+/// ```
+///
+/// [`MessageEntity`]: crate::types::MessageEntity
+/// [`html`]: crate::utils::html
+/// [`markdown`]: crate::utils::markdown
+/// [`KnownApiErrorKind::CantParseEntities`]: crate::KnownApiErrorKind::CantParseEntities
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct FormattedText {
+    text: String,
+    entities: Vec<MessageEntity>,
+    utf16_len: usize,
+}
+
+impl FormattedText {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Constructs from a plain text and entities describing its formatting,
+    /// e.g. from [`Message::text`] and [`Message::entities`].
+    ///
+    /// [`Message::text`]: crate::types::Message::text
+    /// [`Message::entities`]: crate::types::Message::entities
+    pub fn from_parts<T, E>(text: T, entities: E) -> Self
+    where
+        T: Into<String>,
+        E: Into<Vec<MessageEntity>>,
+    {
+        let text = text.into();
+        let utf16_len = utf16_len(&text);
+        Self { text, entities: entities.into(), utf16_len }
+    }
+
+    /// The plain text without any markup.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Entities describing formatting of [`FormattedText::text`].
+    ///
+    /// [`FormattedText::text`]: crate::utils::FormattedText::text
+    pub fn entities(&self) -> &[MessageEntity] {
+        &self.entities
+    }
+
+    /// The length of the text in UTF-16 code units.
+    pub fn utf16_len(&self) -> usize {
+        self.utf16_len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// Splits into the text and the entities, which can be passed into
+    /// [`SendMessage::entities`] or [`SendPhoto::caption_entities`].
+    ///
+    /// [`SendMessage::entities`]: crate::requests::SendMessage::entities
+    /// [`SendPhoto::caption_entities`]: crate::requests::SendPhoto::caption_entities
+    pub fn into_parts(self) -> (String, Vec<MessageEntity>) {
+        (self.text, self.entities)
+    }
+
+    /// Appends unformatted text.
+    pub fn plain<T>(mut self, text: T) -> Self
+    where
+        T: AsRef<str>,
+    {
+        let text = text.as_ref();
+        self.text.push_str(text);
+        self.utf16_len += utf16_len(text);
+        self
+    }
+
+    /// Appends another formatted text.
+    pub fn append<T>(mut self, other: T) -> Self
+    where
+        T: Into<FormattedText>,
+    {
+        let other = other.into();
+        let shift = self.utf16_len;
+
+        self.text.push_str(&other.text);
+        self.entities.extend(
+            other.entities.into_iter().map(|entity| {
+                MessageEntity::new(entity.kind, entity.offset + shift, entity.length)
+            }),
+        );
+        self.utf16_len += other.utf16_len;
+        self
+    }
+
+    /// Appends `inner`, formatted with an entity of the specified `kind`.
+    pub fn entity<T>(mut self, kind: MessageEntityKind, inner: T) -> Self
+    where
+        T: Into<FormattedText>,
+    {
+        let inner = inner.into();
+        if !inner.is_empty() {
+            self.entities.push(MessageEntity::new(kind, self.utf16_len, inner.utf16_len));
+        }
+        self.append(inner)
+    }
+
+    /// Appends bold `inner`.
+    pub fn bold<T>(self, inner: T) -> Self
+    where
+        T: Into<FormattedText>,
+    {
+        self.entity(MessageEntityKind::Bold, inner)
+    }
+
+    /// Appends italic `inner`.
+    pub fn italic<T>(self, inner: T) -> Self
+    where
+        T: Into<FormattedText>,
+    {
+        self.entity(MessageEntityKind::Italic, inner)
+    }
+
+    /// Appends underlined `inner`.
+    pub fn underline<T>(self, inner: T) -> Self
+    where
+        T: Into<FormattedText>,
+    {
+        self.entity(MessageEntityKind::Underline, inner)
+    }
+
+    /// Appends strikethrough `inner`.
+    pub fn strike<T>(self, inner: T) -> Self
+    where
+        T: Into<FormattedText>,
+    {
+        self.entity(MessageEntityKind::Strikethrough, inner)
+    }
+
+    /// Appends `code` as an inline code.
+    pub fn code<T>(self, code: T) -> Self
+    where
+        T: AsRef<str>,
+    {
+        self.entity(MessageEntityKind::Code, FormattedText::new().plain(code))
+    }
+
+    /// Appends `code` as a code block.
+    pub fn code_block<T>(self, code: T) -> Self
+    where
+        T: AsRef<str>,
+    {
+        self.entity(MessageEntityKind::Pre { language: None }, FormattedText::new().plain(code))
+    }
+
+    /// Appends `code` as a code block with a specific language syntax.
+    pub fn code_block_with_lang<T, L>(self, code: T, lang: L) -> Self
+    where
+        T: AsRef<str>,
+        L: Into<String>,
+    {
+        self.entity(
+            MessageEntityKind::Pre { language: Some(lang.into()) },
+            FormattedText::new().plain(code),
+        )
+    }
+
+    /// Appends `inner` as an inline link to `url`.
+    pub fn link<U, T>(self, url: U, inner: T) -> Self
+    where
+        U: Into<String>,
+        T: Into<FormattedText>,
+    {
+        self.entity(MessageEntityKind::TextLink { url: url.into() }, inner)
+    }
+
+    /// Appends `inner` as a mention of `user`, who may have no username.
+    pub fn user_mention<T>(self, user: User, inner: T) -> Self
+    where
+        T: Into<FormattedText>,
+    {
+        self.entity(MessageEntityKind::TextMention { user }, inner)
+    }
+
+    /// Renders into a string with the [HTML style].
+    ///
+    /// [HTML style]: https://core.telegram.org/bots/api#html-style
+    pub fn to_html(&self) -> String {
+        render_html(&self.text, &self.entities)
+    }
+
+    /// Renders into a string with the [MarkdownV2 style].
+    ///
+    /// [MarkdownV2 style]: https://core.telegram.org/bots/api#markdownv2-style
+    pub fn to_markdown_v2(&self) -> String {
+        render_markdown_v2(&self.text, &self.entities)
+    }
+}
+
+impl From<&str> for FormattedText {
+    fn from(text: &str) -> Self {
+        Self::new().plain(text)
+    }
+}
+
+impl From<String> for FormattedText {
+    fn from(text: String) -> Self {
+        Self::from_parts(text, Vec::new())
+    }
+}
+
+fn utf16_len(s: &str) -> usize {
+    s.encode_utf16().count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nested_entities() {
+        let text = FormattedText::new()
+            .plain("a ")
+            .bold(FormattedText::new().plain("b ").italic("c"))
+            .plain(" d");
+
+        assert_eq!(text.text(), "a b c d");
+        assert_eq!(
+            text.entities(),
+            &[
+                MessageEntity::new(MessageEntityKind::Bold, 2, 3),
+                MessageEntity::new(MessageEntityKind::Italic, 4, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn utf16_offsets() {
+        let text = FormattedText::new().plain("🦀👍").bold("дa").code("x");
+
+        assert_eq!(
+            text.entities(),
+            &[
+                MessageEntity::new(MessageEntityKind::Bold, 4, 2),
+                MessageEntity::new(MessageEntityKind::Code, 6, 1),
+            ]
+        );
+        assert_eq!(text.utf16_len(), 7);
+    }
+
+    #[test]
+    fn empty_entities_are_skipped() {
+        let text = FormattedText::new().bold("").plain("x");
+        assert!(text.entities().is_empty());
+    }
+
+    #[test]
+    fn render() {
+        let text = FormattedText::new()
+            .link("https://example.com/(x)", "a_b")
+            .plain(" ")
+            .code_block_with_lang("<&>", "rust");
+
+        assert_eq!(
+            text.to_html(),
+            "<a href=\"https://example.com/(x)\">a_b</a> <pre><code \
+             class=\"language-rust\">&lt;&amp;&gt;</code></pre>"
+        );
+        assert_eq!(text.to_markdown_v2(), "[a\\_b](https://example.com/(x\\)) ```rust\n<&>\n```");
+    }
+}
@@ -4,6 +4,8 @@
 use crate::types::User;
 use std::string::String;
 
+pub use crate::utils::sanitize::sanitize;
+
 /// Applies the bold font style to the string.
 ///
 /// Passed string will not be automatically escaped because it can contain
@@ -0,0 +1,269 @@
+//! Construction of keyboards.
+//!
+//! [`keyboard!`] and [`inline_keyboard!`] build keyboards row by row, while
+//! [`rows`] and [`fit`] lay out a list of buttons automatically. All of them
+//! produce [`ReplyKeyboardMarkup`] and [`InlineKeyboardMarkup`] (or rows of
+//! buttons for them), so they can be passed to `reply_markup` of any request.
+//!
+//! # Example
+//! ```
+//! use teloxide::{
+//!     types::{InlineKeyboardButton, InlineKeyboardMarkup},
+//!     utils::keyboard,
+//! };
+//!
+//! let buttons = (1..=7).map(|n| InlineKeyboardButton::callback(n.to_string(), n.to_string()));
+//! let markup = InlineKeyboardMarkup::new(keyboard::rows(buttons, 3));
+//!
+//! assert_eq!(markup.inline_keyboard.iter().map(Vec::len).collect::<Vec<_>>(), [3, 3, 1]);
+//! ```
+//!
+//! [`keyboard!`]: crate::keyboard
+//! [`inline_keyboard!`]: crate::inline_keyboard
+//! [`rows`]: crate::utils::keyboard::rows
+//! [`fit`]: crate::utils::keyboard::fit
+//! [`ReplyKeyboardMarkup`]: crate::types::ReplyKeyboardMarkup
+//! [`InlineKeyboardMarkup`]: crate::types::InlineKeyboardMarkup
+
+use crate::types::{InlineKeyboardButton, KeyboardButton};
+
+/// Builds a [`ReplyKeyboardMarkup`] from rows of buttons.
+///
+/// Every row is written in square brackets. Buttons are strings or
+/// [`KeyboardButton`]s.
+///
+/// # Example
+/// ```
+/// use teloxide::{
+///     keyboard,
+///     types::{ButtonRequest, KeyboardButton},
+/// };
+///
+/// let markup = keyboard![
+///     ["Yes", "No"],
+///     [KeyboardButton::new("Share contact").request(ButtonRequest::Contact)],
+/// ]
+/// .resize_keyboard(true);
+///
+/// assert_eq!(markup.keyboard[0][1].text, "No");
+/// ```
+///
+/// [`ReplyKeyboardMarkup`]: crate::types::ReplyKeyboardMarkup
+/// [`KeyboardButton`]: crate::types::KeyboardButton
+#[macro_export]
+macro_rules! keyboard {
+    ($([$($button:expr),* $(,)?]),* $(,)?) => {{
+        let rows: ::std::vec::Vec<::std::vec::Vec<$crate::types::KeyboardButton>> =
+            ::std::vec![$(::std::vec![$($crate::types::KeyboardButton::from($button)),*]),*];
+        $crate::types::ReplyKeyboardMarkup::new(rows)
+    }};
+}
+
+/// Builds an [`InlineKeyboardMarkup`] from rows of buttons.
+///
+/// Every row is written in square brackets. `text => data` is a callback
+/// button, any other expression must be an [`InlineKeyboardButton`].
+///
+/// # Example
+/// ```
+/// use teloxide::{inline_keyboard, types::InlineKeyboardButton};
+///
+/// let page = 2;
+/// let markup = inline_keyboard![
+///     ["Yes" => "yes", "No" => "no"],
+///     ["Next page" => format!("page:{}", page + 1)],
+///     [InlineKeyboardButton::url("Docs".to_owned(), "https://docs.rs".to_owned())],
+/// ];
+///
+/// assert_eq!(markup.inline_keyboard[1][0].text, "Next page");
+/// ```
+///
+/// [`InlineKeyboardMarkup`]: crate::types::InlineKeyboardMarkup
+/// [`InlineKeyboardButton`]: crate::types::InlineKeyboardButton
+#[macro_export]
+macro_rules! inline_keyboard {
+    (@row [$($buttons:expr),*]) => {
+        ::std::vec![$($buttons),*]
+    };
+    (@row [$($buttons:expr),*] $text:expr => $data:expr $(, $($rest:tt)*)?) => {
+        $crate::inline_keyboard!(
+            @row [
+                $($buttons,)*
+                $crate::types::InlineKeyboardButton::callback(
+                    ::std::string::String::from($text),
+                    ::std::string::String::from($data),
+                )
+            ]
+            $($($rest)*)?
+        )
+    };
+    (@row [$($buttons:expr),*] $button:expr $(, $($rest:tt)*)?) => {
+        $crate::inline_keyboard!(@row [$($buttons,)* $button] $($($rest)*)?)
+    };
+    ($([$($row:tt)*]),* $(,)?) => {{
+        let rows: ::std::vec::Vec<::std::vec::Vec<$crate::types::InlineKeyboardButton>> =
+            ::std::vec![$($crate::inline_keyboard!(@row [] $($row)*)),*];
+        $crate::types::InlineKeyboardMarkup::new(rows)
+    }};
+}
+
+/// The maximum number of buttons in a row, shown by Telegram apps.
+pub const MAX_ROW_WIDTH: usize = 8;
+
+/// A button of a keyboard.
+pub trait Button {
+    /// Label text on the button.
+    fn text(&self) -> &str;
+}
+
+impl Button for KeyboardButton {
+    fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+impl Button for InlineKeyboardButton {
+    fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+/// Flows `buttons` into rows of `width` buttons (the last row may be
+/// shorter).
+///
+/// # Panics
+/// If `width` is 0.
+pub fn rows<I>(buttons: I, width: usize) -> Vec<Vec<I::Item>>
+where
+    I: IntoIterator,
+{
+    assert!(width > 0, "width of rows must be positive");
+
+    let mut rows = Vec::new();
+    let mut row = Vec::with_capacity(width);
+    for button in buttons {
+        row.push(button);
+        if row.len() == width {
+            rows.push(std::mem::replace(&mut row, Vec::with_capacity(width)));
+        }
+    }
+    if !row.is_empty() {
+        rows.push(row);
+    }
+
+    rows
+}
+
+/// Flows `buttons` into rows, fitting them by length of their texts.
+///
+/// A row is filled while the total number of characters of its buttons
+/// doesn't exceed `max_chars` (a button longer than that takes a whole row)
+/// and there are less than [`MAX_ROW_WIDTH`] buttons. The order of buttons is
+/// kept.
+///
+/// # Example
+/// ```
+/// use teloxide::{types::KeyboardButton, utils::keyboard};
+///
+/// let buttons = ["Yes", "No", "Maybe later", "Never ask me again"];
+/// let rows = keyboard::fit(buttons.iter().map(|&text| KeyboardButton::new(text)), 16);
+///
+/// assert_eq!(rows.iter().map(Vec::len).collect::<Vec<_>>(), [3, 1]);
+/// ```
+///
+/// [`MAX_ROW_WIDTH`]: crate::utils::keyboard::MAX_ROW_WIDTH
+pub fn fit<I>(buttons: I, max_chars: usize) -> Vec<Vec<I::Item>>
+where
+    I: IntoIterator,
+    I::Item: Button,
+{
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut row_chars = 0;
+    for button in buttons {
+        let chars = button.text().chars().count();
+        if !row.is_empty() && (row_chars + chars > max_chars || row.len() == MAX_ROW_WIDTH) {
+            rows.push(std::mem::take(&mut row));
+            row_chars = 0;
+        }
+        row.push(button);
+        row_chars += chars;
+    }
+    if !row.is_empty() {
+        rows.push(row);
+    }
+
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{InlineKeyboardButtonKind, InlineKeyboardMarkup, ReplyKeyboardMarkup};
+
+    fn texts<B>(rows: &[Vec<B>]) -> Vec<Vec<&str>>
+    where
+        B: Button,
+    {
+        rows.iter().map(|row| row.iter().map(Button::text).collect()).collect()
+    }
+
+    #[test]
+    fn keyboard_macro() {
+        assert_eq!(
+            keyboard![["a", String::from("b")], [KeyboardButton::new("c")],],
+            ReplyKeyboardMarkup::new(vec![
+                vec![KeyboardButton::new("a"), KeyboardButton::new("b")],
+                vec![KeyboardButton::new("c")],
+            ])
+        );
+        assert_eq!(keyboard![], ReplyKeyboardMarkup::new(Vec::<Vec<KeyboardButton>>::new()));
+    }
+
+    #[test]
+    fn inline_keyboard_macro() {
+        let url = InlineKeyboardButton::url("u".to_owned(), "https://example.com".to_owned());
+        let markup = inline_keyboard![
+            ["a" => "1", url.clone(), "b" => 2.to_string()],
+            [],
+            [url.clone()],
+        ];
+
+        assert_eq!(
+            markup,
+            InlineKeyboardMarkup::new(vec![
+                vec![
+                    InlineKeyboardButton::callback("a".to_owned(), "1".to_owned()),
+                    url.clone(),
+                    InlineKeyboardButton::callback("b".to_owned(), "2".to_owned()),
+                ],
+                vec![],
+                vec![url],
+            ])
+        );
+        assert_eq!(
+            inline_keyboard![["a" => "1"]].inline_keyboard[0][0].kind,
+            InlineKeyboardButtonKind::CallbackData("1".to_owned())
+        );
+    }
+
+    #[test]
+    fn flow_into_rows() {
+        let buttons = ["a", "b", "c", "d", "e"].iter().map(|&text| KeyboardButton::new(text));
+        assert_eq!(texts(&rows(buttons, 2)), vec![vec!["a", "b"], vec!["c", "d"], vec!["e"]]);
+        assert!(rows(Vec::<KeyboardButton>::new(), 2).is_empty());
+    }
+
+    #[test]
+    fn fit_by_length() {
+        let buttons =
+            ["aaaa", "bb", "cccccccc", "d", "e"].iter().map(|&text| KeyboardButton::new(text));
+        assert_eq!(
+            texts(&fit(buttons, 6)),
+            vec![vec!["aaaa", "bb"], vec!["cccccccc"], vec!["d", "e"]]
+        );
+
+        let buttons = (0..10).map(|n| KeyboardButton::new(n.to_string()));
+        assert_eq!(fit(buttons, 100).iter().map(Vec::len).collect::<Vec<_>>(), [8, 2]);
+    }
+}
@@ -0,0 +1,618 @@
+//! Parsing of the [HTML] and [MarkdownV2] styles into [`FormattedText`].
+//!
+//! The parsers follow the rules of Telegram, so markup accepted by them is
+//! (as far as possible) accepted by Telegram too.
+//!
+//! [HTML]: https://core.telegram.org/bots/api#html-style
+//! [MarkdownV2]: https://core.telegram.org/bots/api#markdownv2-style
+//! [`FormattedText`]: crate::utils::FormattedText
+
+use crate::{
+    types::{MessageEntity, MessageEntityKind, ParseMode},
+    utils::FormattedText,
+    Bot,
+};
+use thiserror::Error;
+
+/// An error while parsing markup.
+#[derive(Debug, Error, Clone, Eq, Hash, PartialEq)]
+#[error("{kind} at byte {offset}")]
+pub struct MarkupError {
+    /// The byte offset in the markup where the error was found.
+    pub offset: usize,
+    pub kind: MarkupErrorKind,
+}
+
+#[derive(Debug, Error, Clone, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum MarkupErrorKind {
+    #[error("unsupported tag <{0}>")]
+    UnsupportedTag(String),
+
+    #[error("unexpected end tag </{0}>")]
+    UnexpectedEndTag(String),
+
+    #[error("unclosed tag <{0}>")]
+    UnclosedTag(String),
+
+    #[error("unterminated tag")]
+    UnterminatedTag,
+
+    #[error("a link without the href attribute")]
+    MissingHref,
+
+    #[error("character '{0}' is reserved and must be escaped")]
+    UnescapedCharacter(char),
+
+    #[error("unclosed entity starting with `{0}`")]
+    UnclosedEntity(String),
+}
+
+impl MarkupError {
+    fn new(offset: usize, kind: MarkupErrorKind) -> Self {
+        Self { offset, kind }
+    }
+}
+
+/// Parses a string with the [HTML style].
+///
+/// # Example
+/// ```
+/// use teloxide::utils::markup::parse_html;
+///
+/// let text = parse_html("<b>Hello</b>, &lt;world&gt;!").unwrap();
+/// assert_eq!(text.text(), "Hello, <world>!");
+/// assert_eq!(text.entities().len(), 1);
+/// ```
+///
+/// [HTML style]: https://core.telegram.org/bots/api#html-style
+pub fn parse_html(s: &str) -> Result<FormattedText, MarkupError> {
+    let mut builder = Builder::default();
+    // Tag names, byte offsets, UTF-16 offsets in the text and kinds of open
+    // tags.
+    let mut open: Vec<(String, usize, usize, Option<MessageEntityKind>)> = Vec::new();
+
+    let mut i = 0;
+    while let Some(c) = s[i..].chars().next() {
+        match c {
+            '<' => {
+                let len = s[i..]
+                    .find('>')
+                    .ok_or_else(|| MarkupError::new(i, MarkupErrorKind::UnterminatedTag))?;
+                let tag = &s[i + 1..i + len];
+
+                if let Some(name) = tag.strip_prefix('/') {
+                    let name = name.trim().to_lowercase();
+                    match open.pop() {
+                        Some((open_name, _, start, kind)) if open_name == name => {
+                            if let Some(kind) = kind {
+                                builder.entity(kind, start);
+                            }
+                        }
+                        _ => {
+                            return Err(MarkupError::new(
+                                i,
+                                MarkupErrorKind::UnexpectedEndTag(name),
+                            ))
+                        }
+                    }
+                } else {
+                    let (name, attrs) = split_tag(tag);
+                    let kind = match name.as_str() {
+                        "b" | "strong" => Some(MessageEntityKind::Bold),
+                        "i" | "em" => Some(MessageEntityKind::Italic),
+                        "u" | "ins" => Some(MessageEntityKind::Underline),
+                        "s" | "strike" | "del" => Some(MessageEntityKind::Strikethrough),
+                        "pre" => Some(MessageEntityKind::Pre { language: None }),
+                        "a" => match attribute(attrs, "href") {
+                            Some(url) => Some(MessageEntityKind::TextLink { url }),
+                            None => return Err(MarkupError::new(i, MarkupErrorKind::MissingHref)),
+                        },
+                        "code" => {
+                            let language = attribute(attrs, "class").and_then(|class| {
+                                class.strip_prefix("language-").map(ToOwned::to_owned)
+                            });
+
+                            // `<pre><code class="language-x">` is a single
+                            // code block with the specified language.
+                            match (open.last_mut(), language) {
+                                (
+                                    Some((_, _, start, Some(MessageEntityKind::Pre { language }))),
+                                    Some(lang),
+                                ) if *start == builder.utf16_len => {
+                                    *language = Some(lang);
+                                    None
+                                }
+                                _ => Some(MessageEntityKind::Code),
+                            }
+                        }
+                        _ => {
+                            return Err(MarkupError::new(i, MarkupErrorKind::UnsupportedTag(name)))
+                        }
+                    };
+                    open.push((name, i, builder.utf16_len, kind));
+                }
+
+                i += len + 1;
+            }
+            '&' => {
+                let (decoded, len) = decode_entity(&s[i..]);
+                builder.push(decoded);
+                i += len;
+            }
+            c => {
+                builder.push(c);
+                i += c.len_utf8();
+            }
+        }
+    }
+
+    match open.pop() {
+        Some((name, offset, ..)) => {
+            Err(MarkupError::new(offset, MarkupErrorKind::UnclosedTag(name)))
+        }
+        None => Ok(builder.finish()),
+    }
+}
+
+/// Parses a string with the [MarkdownV2 style].
+///
+/// # Example
+/// ```
+/// use teloxide::utils::markup::parse_markdown_v2;
+///
+/// let text = parse_markdown_v2(r"*Hello*, \_world\_\!").unwrap();
+/// assert_eq!(text.text(), "Hello, _world_!");
+/// assert_eq!(text.entities().len(), 1);
+///
+/// assert!(parse_markdown_v2("Hello, world!").is_err());
+/// ```
+///
+/// [MarkdownV2 style]: https://core.telegram.org/bots/api#markdownv2-style
+pub fn parse_markdown_v2(s: &str) -> Result<FormattedText, MarkupError> {
+    let mut builder = Builder::default();
+    // Markers, byte offsets and UTF-16 offsets in the text of open entities.
+    let mut open: Vec<(&str, usize, usize)> = Vec::new();
+
+    let mut i = 0;
+    while let Some(c) = s[i..].chars().next() {
+        let rest = &s[i..];
+        match c {
+            '\\' => {
+                let escaped = rest[1..].chars().next().ok_or_else(|| {
+                    MarkupError::new(i, MarkupErrorKind::UnescapedCharacter('\\'))
+                })?;
+                builder.push(escaped);
+                i += 1 + escaped.len_utf8();
+            }
+            // Ignored by Telegram, used to separate `_` and `__`.
+            '\r' => i += 1,
+            '*' | '_' | '~' => {
+                let marker = if rest.starts_with("__") { "__" } else { &rest[..1] };
+                match open.iter().rposition(|&(open_marker, ..)| open_marker == marker) {
+                    Some(pos) if pos + 1 == open.len() => {
+                        let (_, _, start) = open.pop().unwrap();
+                        let kind = match marker {
+                            "*" => MessageEntityKind::Bold,
+                            "_" => MessageEntityKind::Italic,
+                            "__" => MessageEntityKind::Underline,
+                            _ => MessageEntityKind::Strikethrough,
+                        };
+                        builder.entity(kind, start);
+                    }
+                    // Entities must be properly nested.
+                    Some(_) => {
+                        let &(marker, offset, _) = open.last().unwrap();
+                        return Err(MarkupError::new(
+                            offset,
+                            MarkupErrorKind::UnclosedEntity(marker.to_owned()),
+                        ));
+                    }
+                    None => open.push((marker, i, builder.utf16_len)),
+                }
+                i += marker.len();
+            }
+            '[' => {
+                open.push(("[", i, builder.utf16_len));
+                i += 1;
+            }
+            ']' => {
+                let start = match open.last() {
+                    Some(&("[", _, start)) if rest[1..].starts_with('(') => start,
+                    _ => return Err(MarkupError::new(i, MarkupErrorKind::UnescapedCharacter(']'))),
+                };
+                open.pop();
+
+                let (url, len) = read_until(&rest[2..], ")").ok_or_else(|| {
+                    MarkupError::new(i + 1, MarkupErrorKind::UnclosedEntity("(".to_owned()))
+                })?;
+                builder.entity(MessageEntityKind::TextLink { url }, start);
+                i += 2 + len;
+            }
+            '`' if rest.starts_with("```") => {
+                let (block, len) = read_until(&rest[3..], "```").ok_or_else(|| {
+                    MarkupError::new(i, MarkupErrorKind::UnclosedEntity("```".to_owned()))
+                })?;
+
+                // The first line is the language, if it contains no whitespace.
+                let (language, code) = match block.find('\n') {
+                    Some(pos) if !block[..pos].contains(char::is_whitespace) => {
+                        let language = &block[..pos];
+                        let language =
+                            if language.is_empty() { None } else { Some(language.to_owned()) };
+                        (language, &block[pos + 1..])
+                    }
+                    _ => (None, block.as_str()),
+                };
+                let code = code.strip_suffix('\n').unwrap_or(code);
+
+                let start = builder.utf16_len;
+                builder.push_str(code);
+                builder.entity(MessageEntityKind::Pre { language }, start);
+                i += 3 + len;
+            }
+            '`' => {
+                let (code, len) = read_until(&rest[1..], "`").ok_or_else(|| {
+                    MarkupError::new(i, MarkupErrorKind::UnclosedEntity("`".to_owned()))
+                })?;
+
+                let start = builder.utf16_len;
+                builder.push_str(&code);
+                builder.entity(MessageEntityKind::Code, start);
+                i += 1 + len;
+            }
+            '(' | ')' | '>' | '#' | '+' | '-' | '=' | '|' | '{' | '}' | '.' | '!' => {
+                return Err(MarkupError::new(i, MarkupErrorKind::UnescapedCharacter(c)))
+            }
+            c => {
+                builder.push(c);
+                i += c.len_utf8();
+            }
+        }
+    }
+
+    match open.pop() {
+        Some((marker, offset, _)) => {
+            Err(MarkupError::new(offset, MarkupErrorKind::UnclosedEntity(marker.to_owned())))
+        }
+        None => Ok(builder.finish()),
+    }
+}
+
+/// Checks that `text` is valid according to the rules of `parse_mode`.
+///
+/// Telegram reports invalid markup only as
+/// [`KnownApiErrorKind::CantParseEntities`] without a position, so it's useful
+/// to check texts built at runtime (e.g. forgotten [`markdown::escape`])
+/// locally.
+///
+/// # Example
+/// ```
+/// use teloxide::{
+///     types::ParseMode,
+///     utils::markup::{validate, MarkupErrorKind},
+/// };
+///
+/// assert!(validate("<b>Hello</b>", ParseMode::HTML).is_ok());
+///
+/// let error = validate("*Total:* 1.5", ParseMode::MarkdownV2).unwrap_err();
+/// assert_eq!(error.offset, 10);
+/// assert_eq!(error.kind, MarkupErrorKind::UnescapedCharacter('.'));
+/// ```
+///
+/// [`KnownApiErrorKind::CantParseEntities`]: crate::KnownApiErrorKind::CantParseEntities
+/// [`markdown::escape`]: crate::utils::markdown::escape
+pub fn validate(text: &str, parse_mode: ParseMode) -> Result<(), MarkupError> {
+    #[allow(deprecated)]
+    match parse_mode {
+        ParseMode::HTML => parse_html(text).map(drop),
+        ParseMode::MarkdownV2 => parse_markdown_v2(text).map(drop),
+        ParseMode::Markdown => validate_markdown(text),
+    }
+}
+
+/// Logs invalid markup in debug builds, before a request of `bot` with it is
+/// sent, unless it's disabled by [`BotBuilder::skip_markup_validation`].
+///
+/// [`BotBuilder::skip_markup_validation`]: crate::BotBuilder::skip_markup_validation
+pub(crate) fn debug_validate(bot: &Bot, text: &str, parse_mode: Option<ParseMode>) {
+    if !cfg!(debug_assertions) || bot.skips_markup_validation() {
+        return;
+    }
+
+    if let Some(parse_mode) = parse_mode {
+        if let Err(error) = validate(text, parse_mode) {
+            log::warn!("Invalid markup ({:?}) in {:?}: {}", parse_mode, text, error);
+        }
+    }
+}
+
+/// Validates the legacy [Markdown style], where entities cannot be nested.
+///
+/// [Markdown style]: https://core.telegram.org/bots/api#markdown-style
+fn validate_markdown(s: &str) -> Result<(), MarkupError> {
+    let unclosed = |offset: usize, marker: &str| {
+        MarkupError::new(offset, MarkupErrorKind::UnclosedEntity(marker.to_owned()))
+    };
+
+    let mut i = 0;
+    while let Some(c) = s[i..].chars().next() {
+        let rest = &s[i..];
+        i += match c {
+            '\\' if rest[1..].starts_with(&['_', '*', '`', '['][..]) => 2,
+            '*' | '_' => 1 + rest[1..].find(c).ok_or_else(|| unclosed(i, &rest[..1]))? + 1,
+            '`' if rest.starts_with("```") => {
+                3 + rest[3..].find("```").ok_or_else(|| unclosed(i, "```"))? + 3
+            }
+            '`' => 1 + rest[1..].find('`').ok_or_else(|| unclosed(i, "`"))? + 1,
+            '[' => {
+                let text_len = rest.find("](").ok_or_else(|| unclosed(i, "["))?;
+                let url_len = rest[text_len..].find(')').ok_or_else(|| unclosed(i, "["))?;
+                text_len + url_len + 1
+            }
+            c => c.len_utf8(),
+        };
+    }
+
+    Ok(())
+}
+
+#[derive(Default)]
+struct Builder {
+    text: String,
+    utf16_len: usize,
+    entities: Vec<MessageEntity>,
+}
+
+impl Builder {
+    fn push(&mut self, c: char) {
+        self.text.push(c);
+        self.utf16_len += c.len_utf16();
+    }
+
+    fn push_str(&mut self, s: &str) {
+        self.text.push_str(s);
+        self.utf16_len += s.encode_utf16().count();
+    }
+
+    /// Adds an entity from `start` to the current end of the text, if it's
+    /// non-empty.
+    fn entity(&mut self, kind: MessageEntityKind, start: usize) {
+        if self.utf16_len > start {
+            self.entities.push(MessageEntity::new(kind, start, self.utf16_len - start));
+        }
+    }
+
+    fn finish(mut self) -> FormattedText {
+        // Entities are added when they are closed, so inner ones go first. The
+        // sort is stable, so the order of entities with the same range is
+        // reversed beforehand.
+        self.entities.reverse();
+        self.entities.sort_by(|a, b| a.offset.cmp(&b.offset).then(b.length.cmp(&a.length)));
+        FormattedText::from_parts(self.text, self.entities)
+    }
+}
+
+/// Splits the contents of a tag into its lowercase name and attributes.
+pub(crate) fn split_tag(tag: &str) -> (String, &str) {
+    let tag = tag.trim();
+    let end = tag.find(char::is_whitespace).unwrap_or(tag.len());
+    (tag[..end].to_lowercase(), &tag[end..])
+}
+
+/// Finds the value of the attribute `name` in `attrs`.
+pub(crate) fn attribute(mut attrs: &str, name: &str) -> Option<String> {
+    loop {
+        attrs = attrs.trim_start();
+        if attrs.is_empty() {
+            return None;
+        }
+
+        let name_end = attrs.find(|c: char| c == '=' || c.is_whitespace()).unwrap_or(attrs.len());
+        let attr_name = attrs[..name_end].to_lowercase();
+        attrs = attrs[name_end..].trim_start();
+
+        let value = if attrs.starts_with('=') {
+            attrs = attrs[1..].trim_start();
+            let (value, len) = match attrs.chars().next() {
+                Some(quote @ '"') | Some(quote @ '\'') => match attrs[1..].find(quote) {
+                    Some(end) => (&attrs[1..=end], end + 2),
+                    None => (&attrs[1..], attrs.len()),
+                },
+                _ => {
+                    let end = attrs.find(char::is_whitespace).unwrap_or(attrs.len());
+                    (&attrs[..end], end)
+                }
+            };
+            attrs = &attrs[len..];
+            decode_entities(value)
+        } else {
+            String::new()
+        };
+
+        if attr_name == name {
+            return Some(value);
+        }
+    }
+}
+
+/// Decodes an HTML entity at the beginning of `s`, returning the decoded
+/// character and the length of the entity.
+///
+/// Unknown entities are left as is, so only `&` is decoded in this case.
+pub(crate) fn decode_entity(s: &str) -> (char, usize) {
+    let decoded = s.find(';').and_then(|end| {
+        let name = &s[1..end];
+        let c = match name {
+            "lt" => '<',
+            "gt" => '>',
+            "amp" => '&',
+            "quot" => '"',
+            _ if name.starts_with("#x") || name.starts_with("#X") => {
+                u32::from_str_radix(&name[2..], 16).ok().and_then(std::char::from_u32)?
+            }
+            _ if name.starts_with('#') => name[1..].parse().ok().and_then(std::char::from_u32)?,
+            _ => return None,
+        };
+        Some((c, end + 1))
+    });
+
+    decoded.unwrap_or(('&', 1))
+}
+
+fn decode_entities(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut i = 0;
+    while let Some(c) = s[i..].chars().next() {
+        if c == '&' {
+            let (decoded, len) = decode_entity(&s[i..]);
+            out.push(decoded);
+            i += len;
+        } else {
+            out.push(c);
+            i += c.len_utf8();
+        }
+    }
+    out
+}
+
+/// Reads MarkdownV2 until the unescaped `terminator`, returning the unescaped
+/// string and the length of the read markup (including the terminator).
+fn read_until(s: &str, terminator: &str) -> Option<(String, usize)> {
+    let mut out = String::new();
+    let mut i = 0;
+    while let Some(c) = s[i..].chars().next() {
+        if s[i..].starts_with(terminator) {
+            return Some((out, i + terminator.len()));
+        }
+
+        if c == '\\' {
+            let escaped = s[i + 1..].chars().next()?;
+            out.push(escaped);
+            i += 1 + escaped.len_utf8();
+        } else {
+            out.push(c);
+            i += c.len_utf8();
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entity(kind: MessageEntityKind, offset: usize, length: usize) -> MessageEntity {
+        MessageEntity::new(kind, offset, length)
+    }
+
+    #[test]
+    fn html() {
+        let text = parse_html(
+            "<b>bold <I>&lt;i&gt;ic</I></b> <a href='https://a.b/?x=1&amp;y=2'>and</a> &foo; \
+             &#127;",
+        )
+        .unwrap();
+
+        assert_eq!(text.text(), "bold <i>ic and &foo; \u{7f}");
+        assert_eq!(
+            text.entities(),
+            &[
+                entity(MessageEntityKind::Bold, 0, 10),
+                entity(MessageEntityKind::Italic, 5, 5),
+                entity(MessageEntityKind::TextLink { url: "https://a.b/?x=1&y=2".into() }, 11, 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn html_pre_with_language() {
+        let text =
+            parse_html("<pre><code class=\"language-rust\">a &amp;&amp; b</code></pre>").unwrap();
+
+        assert_eq!(text.text(), "a && b");
+        assert_eq!(
+            text.entities(),
+            &[entity(MessageEntityKind::Pre { language: Some("rust".into()) }, 0, 6)]
+        );
+    }
+
+    #[test]
+    fn html_errors() {
+        let error = |s| parse_html(s).unwrap_err();
+
+        assert_eq!(error("a <b>b"), MarkupError::new(2, MarkupErrorKind::UnclosedTag("b".into())));
+        assert_eq!(
+            error("<b><i>a</b></i>"),
+            MarkupError::new(7, MarkupErrorKind::UnexpectedEndTag("b".into()))
+        );
+        assert_eq!(
+            error("a <span>"),
+            MarkupError::new(2, MarkupErrorKind::UnsupportedTag("span".into()))
+        );
+        assert_eq!(error("<a>x</a>"), MarkupError::new(0, MarkupErrorKind::MissingHref));
+        assert_eq!(error("<b"), MarkupError::new(0, MarkupErrorKind::UnterminatedTag));
+    }
+
+    #[test]
+    fn markdown_v2() {
+        let text = parse_markdown_v2(
+            "*bold _\r__ic__\r_* [a\\_b](https://a.b/(x\\)) `\\`c` ```rust\n<&>\n``` 1\\.5",
+        )
+        .unwrap();
+
+        assert_eq!(text.text(), "bold ic a_b `c <&> 1.5");
+        assert_eq!(
+            text.entities(),
+            &[
+                entity(MessageEntityKind::Bold, 0, 7),
+                entity(MessageEntityKind::Italic, 5, 2),
+                entity(MessageEntityKind::Underline, 5, 2),
+                entity(MessageEntityKind::TextLink { url: "https://a.b/(x)".into() }, 8, 3),
+                entity(MessageEntityKind::Code, 12, 2),
+                entity(MessageEntityKind::Pre { language: Some("rust".into()) }, 15, 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn markdown_v2_errors() {
+        let error = |s| parse_markdown_v2(s).unwrap_err();
+
+        assert_eq!(error("1.5"), MarkupError::new(1, MarkupErrorKind::UnescapedCharacter('.')));
+        assert_eq!(error("a *b"), MarkupError::new(2, MarkupErrorKind::UnclosedEntity("*".into())));
+        assert_eq!(
+            error("*a _b* c_"),
+            MarkupError::new(3, MarkupErrorKind::UnclosedEntity("_".into()))
+        );
+        assert_eq!(error("[a]"), MarkupError::new(2, MarkupErrorKind::UnescapedCharacter(']')));
+        assert_eq!(error("`a"), MarkupError::new(0, MarkupErrorKind::UnclosedEntity("`".into())));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn legacy_markdown() {
+        assert!(validate("*a* _b_ `c_` ```d*``` [e](f) g\\_h", ParseMode::Markdown).is_ok());
+        assert_eq!(
+            validate("a *b_", ParseMode::Markdown),
+            Err(MarkupError::new(2, MarkupErrorKind::UnclosedEntity("*".into())))
+        );
+        assert_eq!(
+            validate("[a] b", ParseMode::Markdown),
+            Err(MarkupError::new(0, MarkupErrorKind::UnclosedEntity("[".into())))
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let text = FormattedText::new()
+            .plain("🦀 ")
+            .bold(FormattedText::new().plain("a<b> ").italic("_c_").underline("d"))
+            .plain(" ")
+            .link("https://t.me/(x)", "e")
+            .code_block_with_lang("fn main() {}\n", "rust");
+
+        assert_eq!(parse_html(&text.to_html()).unwrap(), text);
+        assert_eq!(parse_markdown_v2(&text.to_markdown_v2()).unwrap(), text);
+    }
+}
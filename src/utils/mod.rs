@@ -1,12 +1,21 @@
 //! Some useful utilities.
 
+pub mod callback_data;
 mod client_from_env;
 pub mod command;
+pub mod format;
+mod formatted_text;
 pub mod html;
+pub mod keyboard;
 pub mod markdown;
+pub mod markup;
+pub(crate) mod render;
+mod sanitize;
+pub mod split;
 mod up_state;
 
 pub use client_from_env::client_from_env;
+pub use formatted_text::FormattedText;
 
 #[cfg(feature = "frunk")]
 pub use up_state::UpState;
@@ -0,0 +1,298 @@
+//! Rendering of text with [`MessageEntity`]s into markup.
+//!
+//! [`MessageEntity`]: crate::types::MessageEntity
+
+use crate::{
+    types::{MessageEntity, MessageEntityKind},
+    utils::{html, markdown},
+};
+
+/// Renders `text` with `entities` into a string with the [HTML style].
+///
+/// [HTML style]: https://core.telegram.org/bots/api#html-style
+pub(crate) fn render_html(text: &str, entities: &[MessageEntity]) -> String {
+    render(text, entities, &mut Html)
+}
+
+/// Renders `text` with `entities` into a string with the [MarkdownV2 style].
+///
+/// [MarkdownV2 style]: https://core.telegram.org/bots/api#markdownv2-style
+pub(crate) fn render_markdown_v2(text: &str, entities: &[MessageEntity]) -> String {
+    render(text, entities, &mut MarkdownV2 { after_underscore: false })
+}
+
+/// A markup language which text with entities is rendered into.
+trait Markup {
+    fn open(&mut self, out: &mut String, kind: &MessageEntityKind);
+    fn close(&mut self, out: &mut String, kind: &MessageEntityKind);
+    fn text(&mut self, out: &mut String, text: &str, in_code: bool);
+}
+
+fn render<M>(text: &str, entities: &[MessageEntity], markup: &mut M) -> String
+where
+    M: Markup,
+{
+    let mut entities: Vec<&MessageEntity> =
+        entities.iter().filter(|entity| entity.length > 0).collect();
+    // Outer entities go before inner ones, so they are opened first.
+    entities.sort_by(|a, b| a.offset.cmp(&b.offset).then(b.length.cmp(&a.length)));
+
+    let mut out = String::with_capacity(text.len());
+    let mut stack: Vec<&MessageEntity> = Vec::new();
+    let mut next = 0;
+    let mut segment = String::new();
+    let mut pos = 0;
+
+    let mut chars = text.chars();
+    loop {
+        if stack.iter().any(|entity| end(entity) <= pos) {
+            flush(&mut out, &mut segment, &stack, markup);
+
+            // Overlapping entities cannot be expressed by markup, so entities
+            // above an ending one are closed and reopened after it.
+            let mut reopen = Vec::new();
+            while let Some(entity) = stack.pop() {
+                markup.close(&mut out, &entity.kind);
+                if end(entity) > pos {
+                    reopen.push(entity);
+                }
+                if stack.iter().all(|entity| end(entity) > pos) {
+                    break;
+                }
+            }
+            for entity in reopen.into_iter().rev() {
+                markup.open(&mut out, &entity.kind);
+                stack.push(entity);
+            }
+        }
+
+        while let Some(&entity) = entities.get(next) {
+            if entity.offset > pos {
+                break;
+            }
+            flush(&mut out, &mut segment, &stack, markup);
+            markup.open(&mut out, &entity.kind);
+            stack.push(entity);
+            next += 1;
+        }
+
+        match chars.next() {
+            Some(c) => {
+                segment.push(c);
+                pos += c.len_utf16();
+            }
+            None => break,
+        }
+    }
+
+    flush(&mut out, &mut segment, &stack, markup);
+    while let Some(entity) = stack.pop() {
+        markup.close(&mut out, &entity.kind);
+    }
+
+    out
+}
+
+fn end(entity: &MessageEntity) -> usize {
+    entity.offset + entity.length
+}
+
+fn flush<M>(out: &mut String, segment: &mut String, stack: &[&MessageEntity], markup: &mut M)
+where
+    M: Markup,
+{
+    if segment.is_empty() {
+        return;
+    }
+
+    let in_code = stack.iter().any(|entity| {
+        matches!(entity.kind, MessageEntityKind::Code | MessageEntityKind::Pre { .. })
+    });
+    markup.text(out, segment, in_code);
+    segment.clear();
+}
+
+fn user_url(user_id: i32) -> String {
+    format!("tg://user?id={}", user_id)
+}
+
+struct Html;
+
+impl Markup for Html {
+    fn open(&mut self, out: &mut String, kind: &MessageEntityKind) {
+        match kind {
+            MessageEntityKind::Bold => out.push_str("<b>"),
+            MessageEntityKind::Italic => out.push_str("<i>"),
+            MessageEntityKind::Underline => out.push_str("<u>"),
+            MessageEntityKind::Strikethrough => out.push_str("<s>"),
+            MessageEntityKind::Code => out.push_str("<code>"),
+            MessageEntityKind::Pre { language: None } => out.push_str("<pre>"),
+            MessageEntityKind::Pre { language: Some(language) } => {
+                out.push_str("<pre><code class=\"language-");
+                out.push_str(&escape_attribute(language));
+                out.push_str("\">");
+            }
+            MessageEntityKind::TextLink { url } => {
+                out.push_str("<a href=\"");
+                out.push_str(&escape_attribute(url));
+                out.push_str("\">");
+            }
+            MessageEntityKind::TextMention { user } => {
+                out.push_str("<a href=\"");
+                out.push_str(&user_url(user.id));
+                out.push_str("\">");
+            }
+            _ => {}
+        }
+    }
+
+    fn close(&mut self, out: &mut String, kind: &MessageEntityKind) {
+        match kind {
+            MessageEntityKind::Bold => out.push_str("</b>"),
+            MessageEntityKind::Italic => out.push_str("</i>"),
+            MessageEntityKind::Underline => out.push_str("</u>"),
+            MessageEntityKind::Strikethrough => out.push_str("</s>"),
+            MessageEntityKind::Code => out.push_str("</code>"),
+            MessageEntityKind::Pre { language: None } => out.push_str("</pre>"),
+            MessageEntityKind::Pre { language: Some(_) } => out.push_str("</code></pre>"),
+            MessageEntityKind::TextLink { .. } | MessageEntityKind::TextMention { .. } => {
+                out.push_str("</a>")
+            }
+            _ => {}
+        }
+    }
+
+    fn text(&mut self, out: &mut String, text: &str, _in_code: bool) {
+        out.push_str(&html::escape(text));
+    }
+}
+
+fn escape_attribute(s: &str) -> String {
+    html::escape(s).replace("\"", "&quot;")
+}
+
+struct MarkdownV2 {
+    /// Whether the last written character is `_` of italic or underline
+    /// markup.
+    after_underscore: bool,
+}
+
+impl MarkdownV2 {
+    fn marker(&mut self, out: &mut String, marker: &str) {
+        // `___` is ambiguous, so italic and underline markers are separated by
+        // `\r`, which is ignored by Telegram.
+        if self.after_underscore && marker.starts_with('_') {
+            out.push('\r');
+        }
+        out.push_str(marker);
+        self.after_underscore = marker.ends_with('_');
+    }
+}
+
+impl Markup for MarkdownV2 {
+    fn open(&mut self, out: &mut String, kind: &MessageEntityKind) {
+        match kind {
+            MessageEntityKind::Bold => self.marker(out, "*"),
+            MessageEntityKind::Italic => self.marker(out, "_"),
+            MessageEntityKind::Underline => self.marker(out, "__"),
+            MessageEntityKind::Strikethrough => self.marker(out, "~"),
+            MessageEntityKind::Code => self.marker(out, "`"),
+            MessageEntityKind::Pre { language } => {
+                let language = language.as_deref().map(markdown::escape).unwrap_or_default();
+                self.marker(out, &format!("```{}\n", language));
+            }
+            MessageEntityKind::TextLink { .. } | MessageEntityKind::TextMention { .. } => {
+                self.marker(out, "[")
+            }
+            _ => {}
+        }
+    }
+
+    fn close(&mut self, out: &mut String, kind: &MessageEntityKind) {
+        match kind {
+            MessageEntityKind::Bold => self.marker(out, "*"),
+            MessageEntityKind::Italic => self.marker(out, "_"),
+            MessageEntityKind::Underline => self.marker(out, "__"),
+            MessageEntityKind::Strikethrough => self.marker(out, "~"),
+            MessageEntityKind::Code => self.marker(out, "`"),
+            MessageEntityKind::Pre { .. } => self.marker(out, "\n```"),
+            MessageEntityKind::TextLink { url } => {
+                self.marker(out, &format!("]({})", markdown::escape_link_url(url)))
+            }
+            MessageEntityKind::TextMention { user } => {
+                self.marker(out, &format!("]({})", user_url(user.id)))
+            }
+            _ => {}
+        }
+    }
+
+    fn text(&mut self, out: &mut String, text: &str, in_code: bool) {
+        if in_code {
+            out.push_str(&markdown::escape_code(text));
+        } else {
+            out.push_str(&markdown::escape(&text.replace(r"\", r"\\")));
+        }
+        self.after_underscore = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entity(kind: MessageEntityKind, offset: usize, length: usize) -> MessageEntity {
+        MessageEntity::new(kind, offset, length)
+    }
+
+    #[test]
+    fn html_nested() {
+        let entities = vec![
+            entity(MessageEntityKind::Bold, 0, 10),
+            entity(MessageEntityKind::Italic, 5, 5),
+            entity(MessageEntityKind::TextLink { url: "https://a.b/?x=\"1\"".into() }, 11, 3),
+        ];
+        assert_eq!(
+            render_html("bold <i>ic and", &entities),
+            "<b>bold <i>&lt;i&gt;ic</i></b> <a href=\"https://a.b/?x=&quot;1&quot;\">and</a>"
+        );
+    }
+
+    #[test]
+    fn html_pre_with_language() {
+        let entities = vec![entity(MessageEntityKind::Pre { language: Some("rust".into()) }, 0, 6)];
+        assert_eq!(
+            render_html("a && b", &entities),
+            "<pre><code class=\"language-rust\">a &amp;&amp; b</code></pre>"
+        );
+    }
+
+    #[test]
+    fn markdown_v2_escaping() {
+        let entities =
+            vec![entity(MessageEntityKind::Bold, 0, 4), entity(MessageEntityKind::Code, 5, 5)];
+        assert_eq!(render_markdown_v2("1.5! `a\\b`", &entities), "*1\\.5\\!* `\\`a\\\\b\\``");
+    }
+
+    #[test]
+    fn markdown_v2_italic_underline() {
+        let entities = vec![
+            entity(MessageEntityKind::Italic, 0, 3),
+            entity(MessageEntityKind::Underline, 0, 3),
+        ];
+        assert_eq!(render_markdown_v2("abc", &entities), "_\r__abc__\r_");
+    }
+
+    #[test]
+    fn overlapping() {
+        let entities =
+            vec![entity(MessageEntityKind::Bold, 0, 3), entity(MessageEntityKind::Italic, 2, 3)];
+        assert_eq!(render_html("ab_cd", &entities), "<b>ab<i>_</i></b><i>cd</i>");
+        assert_eq!(render_markdown_v2("ab_cd", &entities), "*ab_\\__*_cd_");
+    }
+
+    #[test]
+    fn utf16_offsets() {
+        let entities = vec![entity(MessageEntityKind::Bold, 3, 2)];
+        assert_eq!(render_html("🦀 hi", &entities), "🦀 <b>hi</b>");
+    }
+}
@@ -0,0 +1,391 @@
+use crate::{
+    types::{MessageEntity, MessageEntityKind},
+    utils::{
+        markup::{attribute, decode_entity, split_tag},
+        FormattedText,
+    },
+};
+
+/// Converts arbitrary HTML into the subset supported by the [HTML style].
+///
+/// Paragraphs, headings, line breaks and other blocks become newlines,
+/// headings are made bold, list items are prefixed with bullets or numbers
+/// and images are replaced by their `alt` text. Links, code blocks and
+/// supported formatting are preserved, unsupported tags are dropped (the
+/// contents of `<script>`, `<style>` and `<head>` too), whitespace is
+/// collapsed and named entities unknown to Telegram are decoded. The result
+/// is always valid, even for broken input.
+///
+/// # Example
+/// ```
+/// use teloxide::utils::html::sanitize;
+///
+/// let html = "<h1>News</h1>\n<p>Read <a href=\"https://example.com\">more</a>&nbsp;&mdash;</p>\
+///             <ul><li>one<li><strong>two</strong></ul>";
+///
+/// assert_eq!(
+///     sanitize(html),
+///     "<b>News</b>\n\nRead <a href=\"https://example.com\">more</a>\u{a0}—\n\n• one\n• \
+///      <b>two</b>"
+/// );
+/// ```
+///
+/// [HTML style]: https://core.telegram.org/bots/api#html-style
+pub fn sanitize(html: &str) -> String {
+    let mut sanitizer = Sanitizer::default();
+
+    let mut i = 0;
+    while let Some(c) = html[i..].chars().next() {
+        let rest = &html[i..];
+        if rest.starts_with("<!--") {
+            i += rest.find("-->").map_or(rest.len(), |end| end + 3);
+        } else if rest.starts_with("<!") || rest.starts_with("<?") {
+            i += rest.find('>').map_or(rest.len(), |end| end + 1);
+        } else if c == '<' && rest[1..].starts_with(|c: char| c.is_ascii_alphabetic() || c == '/') {
+            let end = match rest.find('>') {
+                Some(end) => end,
+                None => break,
+            };
+            i += end + 1;
+
+            let tag = &rest[1..end];
+            if let Some(name) = tag.strip_prefix('/') {
+                sanitizer.close(&name.trim().to_lowercase());
+            } else {
+                let (name, attrs) = split_tag(tag);
+                let name = name.trim_end_matches('/');
+                if matches!(name, "script" | "style" | "head" | "template") {
+                    // Contents of these tags are not shown.
+                    let rest = &html[i..];
+                    i += find_ignore_ascii_case(rest, &format!("</{}", name)).unwrap_or(rest.len());
+                } else {
+                    sanitizer.open(name, attrs);
+                }
+            }
+        } else if c == '&' {
+            let (decoded, len) = decode_entity_lenient(rest);
+            sanitizer.push_char(decoded);
+            i += len;
+        } else {
+            sanitizer.push_char(c);
+            i += c.len_utf8();
+        }
+    }
+
+    sanitizer.finish().to_html()
+}
+
+#[derive(Default)]
+struct Sanitizer {
+    text: String,
+    utf16_len: usize,
+    entities: Vec<MessageEntity>,
+    /// Names, kinds and starts of open elements.
+    open: Vec<(String, Option<MessageEntityKind>, usize)>,
+    /// Numbers of next items of open lists (`None` for unordered ones).
+    lists: Vec<Option<usize>>,
+    pending_space: bool,
+    pending_newlines: usize,
+}
+
+impl Sanitizer {
+    fn open(&mut self, name: &str, attrs: &str) {
+        let kind = match name {
+            "b" | "strong" => Some(MessageEntityKind::Bold),
+            "i" | "em" | "cite" | "dfn" | "var" => Some(MessageEntityKind::Italic),
+            "u" | "ins" => Some(MessageEntityKind::Underline),
+            "s" | "strike" | "del" => Some(MessageEntityKind::Strikethrough),
+            "code" | "kbd" | "samp" | "tt" => Some(MessageEntityKind::Code),
+            "pre" => {
+                self.line_break(2);
+                Some(MessageEntityKind::Pre { language: None })
+            }
+            "a" => attribute(attrs, "href")
+                .filter(|url| !url.is_empty())
+                .map(|url| MessageEntityKind::TextLink { url }),
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                self.line_break(2);
+                Some(MessageEntityKind::Bold)
+            }
+            "br" => {
+                self.pending_space = false;
+                self.push_raw("\n");
+                return;
+            }
+            "hr" => {
+                self.line_break(2);
+                return;
+            }
+            "img" => {
+                if let Some(alt) = attribute(attrs, "alt") {
+                    alt.chars().for_each(|c| self.push_char(c));
+                }
+                return;
+            }
+            "td" | "th" => {
+                self.pending_space = true;
+                None
+            }
+            "ul" | "ol" => {
+                self.line_break(1);
+                self.lists.push(if name == "ol" { Some(1) } else { None });
+                None
+            }
+            "li" => {
+                self.line_break(1);
+                let indent = "  ".repeat(self.lists.len().saturating_sub(1));
+                let marker = match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        format!("{}{}. ", indent, *number - 1)
+                    }
+                    _ => format!("{}• ", indent),
+                };
+                self.pending_space = false;
+                self.push_raw(&marker);
+                None
+            }
+            _ => {
+                self.line_break(block_breaks(name));
+                None
+            }
+        };
+
+        // Entities cannot be nested into code and links into links.
+        let kind = kind.filter(|kind| {
+            !self.open.iter().any(|(_, open, _)| match open {
+                Some(MessageEntityKind::Code) | Some(MessageEntityKind::Pre { .. }) => true,
+                Some(MessageEntityKind::TextLink { .. }) => {
+                    matches!(kind, MessageEntityKind::TextLink { .. })
+                }
+                _ => false,
+            })
+        });
+
+        // `<pre><code class="language-x">` is a code block with the language.
+        if name == "code" {
+            if let Some((_, Some(MessageEntityKind::Pre { language }), start)) =
+                self.open.last_mut()
+            {
+                if *start == self.utf16_len {
+                    *language = attribute(attrs, "class")
+                        .and_then(|class| class.strip_prefix("language-").map(ToOwned::to_owned));
+                }
+            }
+        }
+
+        if kind.is_some() {
+            self.flush();
+        }
+        self.open.push((name.to_owned(), kind, self.utf16_len));
+    }
+
+    fn close(&mut self, name: &str) {
+        let pos = match self.open.iter().rposition(|(open, ..)| open == name) {
+            Some(pos) => pos,
+            None => return,
+        };
+
+        for (name, kind, start) in self.open.drain(pos..).rev().collect::<Vec<_>>() {
+            if let Some(kind) = kind {
+                if self.utf16_len > start {
+                    self.entities.push(MessageEntity::new(kind, start, self.utf16_len - start));
+                }
+            }
+
+            match name.as_str() {
+                "ul" | "ol" => {
+                    self.lists.pop();
+                    self.line_break(1);
+                }
+                "li" => self.line_break(1),
+                "pre" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => self.line_break(2),
+                name => self.line_break(block_breaks(name)),
+            }
+        }
+    }
+
+    fn in_pre(&self) -> bool {
+        self.open.iter().any(|(_, kind, _)| matches!(kind, Some(MessageEntityKind::Pre { .. })))
+    }
+
+    fn push_char(&mut self, c: char) {
+        if self.in_pre() {
+            self.flush();
+        } else if c.is_whitespace() && c != '\u{a0}' {
+            self.pending_space = !self.text.is_empty() && !self.text.ends_with('\n');
+            return;
+        } else {
+            self.flush();
+        }
+
+        self.text.push(c);
+        self.utf16_len += c.len_utf16();
+    }
+
+    fn push_raw(&mut self, s: &str) {
+        self.flush();
+        self.text.push_str(s);
+        self.utf16_len += s.encode_utf16().count();
+    }
+
+    /// Requests at least `count` newlines before the next text.
+    fn line_break(&mut self, count: usize) {
+        if self.text.is_empty() {
+            return;
+        }
+
+        let trailing = self.text.chars().rev().take_while(|&c| c == '\n').count();
+        self.pending_newlines = self.pending_newlines.max(count.saturating_sub(trailing));
+    }
+
+    fn flush(&mut self) {
+        if self.pending_newlines > 0 {
+            let newlines = "\n".repeat(self.pending_newlines);
+            self.text.push_str(&newlines);
+            self.utf16_len += self.pending_newlines;
+        } else if self.pending_space {
+            self.text.push(' ');
+            self.utf16_len += 1;
+        }
+        self.pending_newlines = 0;
+        self.pending_space = false;
+    }
+
+    fn finish(mut self) -> FormattedText {
+        self.close_all();
+
+        let trimmed = self.text.trim_end().len();
+        self.text.truncate(trimmed);
+        let len = self.text.encode_utf16().count();
+        let entities: Vec<MessageEntity> = self
+            .entities
+            .into_iter()
+            .filter(|entity| entity.offset < len)
+            .map(|entity| {
+                let length = entity.length.min(len - entity.offset);
+                MessageEntity::new(entity.kind, entity.offset, length)
+            })
+            .collect();
+
+        FormattedText::from_parts(self.text, entities)
+    }
+
+    fn close_all(&mut self) {
+        if let Some((name, ..)) = self.open.first() {
+            let name = name.clone();
+            self.close(&name);
+        }
+    }
+}
+
+/// The number of newlines separating a block element from its surroundings.
+fn block_breaks(name: &str) -> usize {
+    match name {
+        "p" | "blockquote" | "table" | "section" | "article" | "header" | "footer" | "figure"
+        | "dl" | "aside" | "nav" | "main" => 2,
+        "div" | "tr" | "dt" | "dd" | "address" | "figcaption" | "caption" => 1,
+        _ => 0,
+    }
+}
+
+/// Finds the first occurrence of the ASCII `needle` in `haystack`, ignoring
+/// ASCII case.
+fn find_ignore_ascii_case(haystack: &str, needle: &str) -> Option<usize> {
+    haystack
+        .as_bytes()
+        .windows(needle.len())
+        .position(|window| window.eq_ignore_ascii_case(needle.as_bytes()))
+}
+
+/// Decodes an HTML entity at the beginning of `s`, like [`decode_entity`],
+/// but also common named entities, which aren't supported by Telegram.
+///
+/// [`decode_entity`]: crate::utils::markup::decode_entity
+fn decode_entity_lenient(s: &str) -> (char, usize) {
+    let named = s.find(';').filter(|&end| end <= 10).and_then(|end| {
+        let c = match &s[1..end] {
+            "nbsp" => '\u{a0}',
+            "apos" => '\'',
+            "copy" => '©',
+            "reg" => '®',
+            "trade" => '™',
+            "mdash" => '—',
+            "ndash" => '–',
+            "hellip" => '…',
+            "laquo" => '«',
+            "raquo" => '»',
+            "lsquo" => '‘',
+            "rsquo" => '’',
+            "ldquo" => '“',
+            "rdquo" => '”',
+            "bull" => '•',
+            "middot" => '·',
+            "euro" => '€',
+            "times" => '×',
+            _ => return None,
+        };
+        Some((c, end + 1))
+    });
+
+    named.unwrap_or_else(|| decode_entity(s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blocks() {
+        assert_eq!(
+            sanitize("<html><head><title>x</title></head><body><p>a\n  b</p><p>c<br>d</p></body>"),
+            "a b\n\nc\nd"
+        );
+        assert_eq!(sanitize("<div>a</div><div>b</div><hr>c"), "a\nb\n\nc");
+    }
+
+    #[test]
+    fn lists() {
+        assert_eq!(
+            sanitize("<ol><li>a</li><li>b<ul><li>c</li></ul></li></ol>"),
+            "1. a\n2. b\n  • c"
+        );
+    }
+
+    #[test]
+    fn formatting() {
+        assert_eq!(
+            sanitize("<B>a <span class=x>b</span></B> <em>c</em> <a name=x>d</a> <font>e"),
+            "<b>a b</b> <i>c</i> d e"
+        );
+        assert_eq!(sanitize("<b>a <i>b</b> c</i>"), "<b>a <i>b</i></b> c");
+    }
+
+    #[test]
+    fn code() {
+        assert_eq!(
+            sanitize(
+                "<pre><code class=\"language-rust\">fn main() {\n    <b>x</b> &lt; \
+                 1\n}</code></pre>"
+            ),
+            "<pre><code class=\"language-rust\">fn main() {\n    x &lt; 1\n}</code></pre>"
+        );
+    }
+
+    #[test]
+    fn entities() {
+        assert_eq!(
+            sanitize("a &amp; &lt;b&gt; &hellip; &foo; 1 < 2"),
+            "a &amp; &lt;b&gt; … &amp;foo; 1 &lt; 2"
+        );
+    }
+
+    #[test]
+    fn broken_input() {
+        assert_eq!(
+            sanitize("<script>alert(1)</script>a</b><i>b<a href='x'>c"),
+            "a<i>b<a href=\"x\">c</a></i>"
+        );
+        assert_eq!(sanitize("<STYLE>p {}</Style>a<script>"), "a");
+    }
+}
@@ -0,0 +1,231 @@
+//! Splitting of long texts into several messages.
+//!
+//! Telegram limits the length of a message text to [`MAX_TEXT_LENGTH`] and the
+//! length of a media caption to [`MAX_CAPTION_LENGTH`] (both are measured in
+//! UTF-16 code units after parsing markup). The functions of this module cut
+//! longer texts at paragraph, line or word boundaries, if possible, and never
+//! break markup or entities like URLs, mentions and inline code.
+//!
+//! See also [`SendMessage::send_split`].
+//!
+//! [`MAX_TEXT_LENGTH`]: crate::utils::split::MAX_TEXT_LENGTH
+//! [`MAX_CAPTION_LENGTH`]: crate::utils::split::MAX_CAPTION_LENGTH
+//! [`SendMessage::send_split`]: crate::requests::SendMessage::send_split
+
+use crate::{
+    types::{MessageEntity, MessageEntityKind},
+    utils::{
+        markup::{parse_html, parse_markdown_v2, MarkupError},
+        FormattedText,
+    },
+};
+
+/// The maximum length of a message text.
+pub const MAX_TEXT_LENGTH: usize = 4096;
+
+/// The maximum length of a media caption.
+pub const MAX_CAPTION_LENGTH: usize = 1024;
+
+/// Splits `text` into parts not longer than `max_len` UTF-16 code units.
+///
+/// A text is cut at the last paragraph break (`\n\n`) that fits into
+/// `max_len`, otherwise at the last line break, otherwise at the last
+/// whitespace. The separator at a cut is dropped. Entities which cannot be
+/// broken (URLs, mentions, inline code, etc.) are never cut, unless a single
+/// one is longer than `max_len`; other entities are continued in the next
+/// part.
+///
+/// # Example
+/// ```
+/// use teloxide::utils::{split::split_formatted, FormattedText};
+///
+/// let text = FormattedText::new().bold("Hello, world!").plain("\n\nBye!");
+/// let parts = split_formatted(&text, 15);
+///
+/// assert_eq!(parts, vec![FormattedText::new().bold("Hello, world!"), "Bye!".into()]);
+/// ```
+pub fn split_formatted(text: &FormattedText, max_len: usize) -> Vec<FormattedText> {
+    let chars: Vec<(usize, char)> = text.text().char_indices().collect();
+    // UTF-16 offsets of the characters and of the end of the text.
+    let offsets: Vec<usize> = chars
+        .iter()
+        .scan(0, |offset, &(_, c)| {
+            let current = *offset;
+            *offset += c.len_utf16();
+            Some(current)
+        })
+        .chain(Some(text.utf16_len()))
+        .collect();
+    let byte = |i: usize| chars.get(i).map_or(text.text().len(), |&(byte, _)| byte);
+
+    let unbreakable: Vec<&MessageEntity> =
+        text.entities().iter().filter(|entity| is_unbreakable(&entity.kind)).collect();
+    let can_cut_at = |i: usize| {
+        let offset = offsets[i];
+        unbreakable.iter().all(|entity| offset <= entity.offset || offset >= end(entity))
+    };
+
+    let part = |start: usize, cut: usize| {
+        let (start_offset, cut_offset) = (offsets[start], offsets[cut]);
+        let entities: Vec<MessageEntity> = text
+            .entities()
+            .iter()
+            .filter_map(|entity| {
+                let from = entity.offset.max(start_offset);
+                let to = end(entity).min(cut_offset);
+                if from < to {
+                    Some(MessageEntity::new(entity.kind.clone(), from - start_offset, to - from))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        FormattedText::from_parts(&text.text()[byte(start)..byte(cut)], entities)
+    };
+
+    let mut parts = Vec::new();
+    let mut start = 0;
+    loop {
+        if offsets[chars.len()] - offsets[start] <= max_len {
+            if start < chars.len() || parts.is_empty() {
+                parts.push(part(start, chars.len()));
+            }
+            break;
+        }
+
+        // The number of characters after `start` fitting into `max_len`, at
+        // least one to make progress.
+        let fits =
+            offsets[start..].iter().take_while(|&&offset| offset - offsets[start] <= max_len);
+        let limit = (start + fits.count() - 1).max(start + 1);
+
+        // Candidates for a cut are pairs of the end of a part and the start of
+        // the next one.
+        let separated_by = |len: usize, is_separator: &dyn Fn(char) -> bool| {
+            (start + 1..=limit)
+                .rev()
+                .filter(|&i| i + len <= chars.len())
+                .find(|&i| chars[i..i + len].iter().all(|&(_, c)| is_separator(c)) && can_cut_at(i))
+                .map(|i| (i, i + len))
+        };
+        let (cut, next) = separated_by(2, &|c| c == '\n')
+            .or_else(|| separated_by(1, &|c| c == '\n'))
+            .or_else(|| separated_by(1, &char::is_whitespace))
+            .or_else(|| separated_by(0, &|_| true))
+            .unwrap_or((limit, limit));
+
+        parts.push(part(start, cut));
+        start = next;
+    }
+
+    parts
+}
+
+/// Splits a plain `text` into parts not longer than `max_len` UTF-16 code
+/// units.
+///
+/// See [`split_formatted`] for details.
+///
+/// [`split_formatted`]: crate::utils::split::split_formatted
+pub fn split_plain(text: &str, max_len: usize) -> Vec<String> {
+    split_formatted(&text.into(), max_len).into_iter().map(|part| part.into_parts().0).collect()
+}
+
+/// Splits a text with the [HTML style] into parts, which are not longer than
+/// `max_len` UTF-16 code units after parsing.
+///
+/// Each part is valid HTML on its own: tags open at a cut are closed and
+/// reopened in the next part. See [`split_formatted`] for details.
+///
+/// [HTML style]: https://core.telegram.org/bots/api#html-style
+/// [`split_formatted`]: crate::utils::split::split_formatted
+pub fn split_html(text: &str, max_len: usize) -> Result<Vec<String>, MarkupError> {
+    let text = parse_html(text)?;
+    Ok(split_formatted(&text, max_len).iter().map(FormattedText::to_html).collect())
+}
+
+/// Splits a text with the [MarkdownV2 style] into parts, which are not longer
+/// than `max_len` UTF-16 code units after parsing.
+///
+/// Each part is valid MarkdownV2 on its own: entities open at a cut are
+/// closed and reopened in the next part. See [`split_formatted`] for details.
+///
+/// [MarkdownV2 style]: https://core.telegram.org/bots/api#markdownv2-style
+/// [`split_formatted`]: crate::utils::split::split_formatted
+pub fn split_markdown_v2(text: &str, max_len: usize) -> Result<Vec<String>, MarkupError> {
+    let text = parse_markdown_v2(text)?;
+    Ok(split_formatted(&text, max_len).iter().map(FormattedText::to_markdown_v2).collect())
+}
+
+fn end(entity: &MessageEntity) -> usize {
+    entity.offset + entity.length
+}
+
+fn is_unbreakable(kind: &MessageEntityKind) -> bool {
+    match kind {
+        MessageEntityKind::Mention
+        | MessageEntityKind::Hashtag
+        | MessageEntityKind::Cashtag
+        | MessageEntityKind::BotCommand
+        | MessageEntityKind::Url
+        | MessageEntityKind::Email
+        | MessageEntityKind::PhoneNumber
+        | MessageEntityKind::Code
+        | MessageEntityKind::TextLink { .. }
+        | MessageEntityKind::TextMention { .. } => true,
+        MessageEntityKind::Bold
+        | MessageEntityKind::Italic
+        | MessageEntityKind::Underline
+        | MessageEntityKind::Strikethrough
+        | MessageEntityKind::Pre { .. } => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_text() {
+        assert_eq!(split_plain("", 5), vec![""]);
+        assert_eq!(split_plain("abc", 5), vec!["abc"]);
+    }
+
+    #[test]
+    fn separators_priority() {
+        assert_eq!(split_plain("aa\n\nb\nc d", 8), vec!["aa", "b\nc d"]);
+        assert_eq!(split_plain("aa b\ncc", 5), vec!["aa b", "cc"]);
+        assert_eq!(split_plain("a b c d", 5), vec!["a b c", "d"]);
+        assert_eq!(split_plain("abcdefg", 3), vec!["abc", "def", "g"]);
+    }
+
+    #[test]
+    fn utf16_lengths() {
+        assert_eq!(split_plain("🦀🦀🦀", 4), vec!["🦀🦀", "🦀"]);
+        assert_eq!(split_plain("🦀🦀🦀", 3), vec!["🦀", "🦀", "🦀"]);
+    }
+
+    #[test]
+    fn entities() {
+        let text = FormattedText::new().plain("a ").bold("bc de").plain(" ").code("f g");
+        let parts = split_formatted(&text, 5);
+
+        assert_eq!(
+            parts,
+            vec![
+                FormattedText::new().plain("a ").bold("bc"),
+                FormattedText::new().bold("de"),
+                FormattedText::new().code("f g"),
+            ]
+        );
+    }
+
+    #[test]
+    fn markup() {
+        assert_eq!(
+            split_html("<b>aaa <i>bbb</i></b> <code>c c</code>", 6).unwrap(),
+            vec!["<b>aaa</b>", "<b><i>bbb</i></b>", "<code>c c</code>"]
+        );
+        assert_eq!(split_markdown_v2("*aaa _bbb\\._*", 6).unwrap(), vec!["*aaa*", "*_bbb\\._*"]);
+    }
+}